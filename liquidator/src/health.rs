@@ -0,0 +1,558 @@
+use std::cell::RefCell;
+
+use anchor_client::anchor_lang::prelude::*;
+use exchange::account::{
+    AssetsList, CollateralEntry, Decimal, ExchangeAccount, State, Vault, VaultEntry,
+};
+use exchange::decimal::{Add, Compare};
+use exchange::math::{
+    calculate_debt, calculate_max_debt_in_usd, calculate_user_debt_in_usd, calculate_value_in_usd,
+    calculate_vault_basket_borrow_limit, calculate_vault_borrow_limit,
+};
+use exchange::migration::EXCHANGE_ACCOUNT_VERSION;
+use exchange::utils::{adjust_vault_entry_interest_debt, calculate_debt_with_adjustment};
+use exchange::ErrorCode;
+
+// Errors of exchange converted to errors understood by client
+pub type Result<T> = std::result::Result<T, ProgramError>;
+
+#[derive(Debug, PartialEq)]
+pub enum Action {
+    // Healthy or still within liquidation buffer
+    Skip,
+    // Undercollateralized but liquidation_deadline is not set yet
+    CheckCollateralization,
+    // Liquidation deadline passed, seize given collateral
+    Liquidate(Pubkey),
+}
+
+// Collateral of vault entry seized by liquidation
+#[derive(Debug, PartialEq)]
+pub enum VaultCollateralChoice {
+    // Seized by liquidate_vault
+    Primary,
+    // Seized by liquidate_vault_collateral with index of basket collateral
+    Basket(u8),
+}
+
+// Debt of entire pool including interest accrued up to timestamp
+// Fails on outdated oracles just like exchange instructions
+pub fn calculate_total_debt(
+    state: &State,
+    assets_list: &AssetsList,
+    slot: u64,
+    timestamp: i64,
+) -> Result<Decimal> {
+    if slot < state.max_delay.into() {
+        return Err(ErrorCode::OutdatedOracle.into());
+    }
+    // Work on copies, on-chain accounts are adjusted by instructions
+    let mut state = *state;
+    let assets_list = RefCell::new(*assets_list);
+    let mut assets_list = assets_list.borrow_mut();
+
+    calculate_debt(&assets_list, slot, state.max_delay, false)?;
+    Ok(calculate_debt_with_adjustment(
+        &mut state,
+        &mut assets_list,
+        slot,
+        timestamp,
+    )?)
+}
+
+pub fn exchange_account_action(
+    exchange_account: &ExchangeAccount,
+    assets_list: &AssetsList,
+    total_debt: Decimal,
+    debt_shares: u64,
+    slot: u64,
+) -> Result<Action> {
+    // Instructions reject accounts awaiting migration
+    if exchange_account.debt_shares == 0 || exchange_account.version != EXCHANGE_ACCOUNT_VERSION {
+        return Ok(Action::Skip);
+    }
+    let user_debt = calculate_user_debt_in_usd(exchange_account, total_debt, debt_shares);
+    let max_debt = calculate_max_debt_in_usd(exchange_account, assets_list);
+
+    if max_debt.gt(user_debt)? {
+        return Ok(Action::Skip);
+    }
+    if exchange_account.liquidation_deadline == u64::MAX {
+        return Ok(Action::CheckCollateralization);
+    }
+    if exchange_account.liquidation_deadline > slot {
+        return Ok(Action::Skip);
+    }
+    // Insolvent accounts can only be written off by admin
+    match most_valuable_collateral(exchange_account, assets_list)? {
+        Some(entry) => Ok(Action::Liquidate(entry.collateral_address)),
+        None => Ok(Action::Skip),
+    }
+}
+
+// Seizing most valuable collateral maximizes amount repaid in single liquidation
+pub fn most_valuable_collateral(
+    exchange_account: &ExchangeAccount,
+    assets_list: &AssetsList,
+) -> Result<Option<CollateralEntry>> {
+    let mut best: Option<(CollateralEntry, Decimal)> = None;
+
+    for entry in exchange_account.collaterals[..exchange_account.head as usize].iter() {
+        if entry.amount == 0 {
+            continue;
+        }
+        let collateral = &assets_list.collaterals[entry.index as usize];
+        let asset = &assets_list.assets[collateral.asset_index as usize];
+        let value = calculate_value_in_usd(
+            asset.price,
+            Decimal::new(entry.amount.into(), collateral.reserve_balance.scale),
+        );
+
+        let is_better = match best {
+            Some((_, best_value)) => value.gt(best_value)?,
+            None => true,
+        };
+        if is_better {
+            best = Some((*entry, value));
+        }
+    }
+    Ok(best.map(|(entry, _)| entry))
+}
+
+// Same condition as in liquidate_vault with interest accrued up to timestamp
+pub fn is_vault_entry_liquidatable(
+    vault: &Vault,
+    vault_entry: &VaultEntry,
+    assets_list: &AssetsList,
+    collateral_price: Decimal,
+    basket_prices: &[Decimal],
+    timestamp: i64,
+) -> Result<bool> {
+    if vault_entry.synthetic_amount.val == 0 {
+        return Ok(false);
+    }
+    let synthetic = match assets_list.synthetics[..assets_list.head_synthetics as usize]
+        .iter()
+        .find(|x| x.asset_address == vault.synthetic)
+    {
+        Some(s) => s,
+        None => return Err(ErrorCode::NoAssetFound.into()),
+    };
+    let mut vault = *vault;
+    let mut vault_entry = *vault_entry;
+    let mut synthetic = *synthetic;
+    let synthetic_asset = assets_list.assets[synthetic.asset_index as usize];
+
+    adjust_vault_entry_interest_debt(&mut vault, &mut vault_entry, &mut synthetic, timestamp);
+
+    let amount_liquidation_limit = calculate_vault_borrow_limit(
+        collateral_price,
+        synthetic_asset,
+        synthetic,
+        vault_entry.collateral_amount,
+        vault.liquidation_threshold,
+    )
+    .add(calculate_vault_basket_borrow_limit(
+        &vault,
+        &vault_entry,
+        basket_prices,
+        synthetic_asset,
+        synthetic,
+        true,
+        None,
+    ))?;
+    Ok(amount_liquidation_limit.lt(vault_entry.synthetic_amount)?)
+}
+
+// Same as most_valuable_collateral, debt backed by basket has to be repaid from basket
+pub fn most_valuable_vault_collateral(
+    vault: &Vault,
+    vault_entry: &VaultEntry,
+    collateral_price: Decimal,
+    basket_prices: &[Decimal],
+) -> Result<Option<VaultCollateralChoice>> {
+    let mut best: Option<(VaultCollateralChoice, Decimal)> = None;
+
+    if vault_entry.collateral_amount.val != 0 {
+        let value = calculate_value_in_usd(collateral_price, vault_entry.collateral_amount);
+        best = Some((VaultCollateralChoice::Primary, value));
+    }
+    for (index, price) in basket_prices[..vault.head_collaterals as usize]
+        .iter()
+        .enumerate()
+    {
+        let amount = vault_entry.collateral_amounts[index];
+        if amount.val == 0 {
+            continue;
+        }
+        let value = calculate_value_in_usd(*price, amount);
+
+        let is_better = match best {
+            Some((_, best_value)) => value.gt(best_value)?,
+            None => true,
+        };
+        if is_better {
+            best = Some((VaultCollateralChoice::Basket(index as u8), value));
+        }
+    }
+    Ok(best.map(|(choice, _)| choice))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use exchange::account::{Asset, Collateral, Synthetic, VaultCollateral};
+    use exchange::decimal::XUSD_SCALE;
+
+    fn assets_list_with_collaterals(slot: u64) -> AssetsList {
+        let mut assets_list = AssetsList {
+            ..Default::default()
+        };
+        // xUSD
+        assets_list.append_asset(Asset {
+            price: Decimal::from_integer(1).to_price(),
+            last_update: slot,
+            ..Default::default()
+        });
+        assets_list.append_synthetic(Synthetic {
+            asset_index: 0,
+            asset_address: Pubkey::new_unique(),
+            supply: Decimal::from_integer(1_000).to_usd(),
+            swapline_supply: Decimal::from_usd(0),
+            borrowed_supply: Decimal::from_usd(0),
+            ..Default::default()
+        });
+        // SNY
+        assets_list.append_asset(Asset {
+            price: Decimal::from_integer(2).to_price(),
+            last_update: slot,
+            ..Default::default()
+        });
+        assets_list.append_collateral(Collateral {
+            asset_index: 1,
+            collateral_address: Pubkey::new_unique(),
+            reserve_balance: Decimal::from_integer(0).to_scale(6),
+            collateral_ratio: Decimal::from_percent(50),
+            ..Default::default()
+        });
+        // SOL
+        assets_list.append_asset(Asset {
+            price: Decimal::from_integer(100).to_price(),
+            last_update: slot,
+            ..Default::default()
+        });
+        assets_list.append_collateral(Collateral {
+            asset_index: 2,
+            collateral_address: Pubkey::new_unique(),
+            reserve_balance: Decimal::from_integer(0).to_scale(9),
+            collateral_ratio: Decimal::from_percent(50),
+            ..Default::default()
+        });
+        assets_list
+    }
+
+    #[test]
+    fn test_calculate_total_debt() {
+        let slot = 1_000;
+        let assets_list = assets_list_with_collaterals(slot);
+        let state = State {
+            max_delay: 100,
+            ..Default::default()
+        };
+        // Up to date oracles
+        {
+            let result = calculate_total_debt(&state, &assets_list, slot, 0).unwrap();
+            assert_eq!(result, Decimal::from_integer(1_000).to_usd());
+        }
+        // Outdated oracles
+        {
+            let result = calculate_total_debt(&state, &assets_list, slot + 101, 0);
+            assert!(result.is_err());
+        }
+        // Slot lower than max delay
+        {
+            let result = calculate_total_debt(&state, &assets_list, 50, 0);
+            assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn test_exchange_account_action() {
+        let slot = 1_000;
+        let assets_list = assets_list_with_collaterals(slot);
+        let total_debt = Decimal::from_integer(1_000).to_usd();
+        let debt_shares = 1_000;
+        let sny = assets_list.collaterals[0].collateral_address;
+        let sol = assets_list.collaterals[1].collateral_address;
+
+        let mut exchange_account = ExchangeAccount {
+            version: EXCHANGE_ACCOUNT_VERSION,
+            debt_shares: 100,
+            liquidation_deadline: u64::MAX,
+            ..Default::default()
+        };
+        // 80 USD of SNY collateral value
+        exchange_account.append(CollateralEntry {
+            amount: 40 * 10u64.pow(6),
+            collateral_address: sny,
+            index: 0,
+        });
+        // 100 USD of SOL collateral value
+        exchange_account.append(CollateralEntry {
+            amount: 10u64.pow(9),
+            collateral_address: sol,
+            index: 1,
+        });
+        // Healthy: max debt 90 USD, debt 10 USD
+        {
+            let mut exchange_account = exchange_account;
+            exchange_account.debt_shares = 10;
+            let result = exchange_account_action(
+                &exchange_account,
+                &assets_list,
+                total_debt,
+                debt_shares,
+                slot,
+            );
+            assert_eq!(result, Ok(Action::Skip));
+        }
+        // No debt
+        {
+            let mut exchange_account = exchange_account;
+            exchange_account.debt_shares = 0;
+            let result = exchange_account_action(
+                &exchange_account,
+                &assets_list,
+                total_debt,
+                debt_shares,
+                slot,
+            );
+            assert_eq!(result, Ok(Action::Skip));
+        }
+        // Not migrated
+        {
+            let mut exchange_account = exchange_account;
+            exchange_account.version = EXCHANGE_ACCOUNT_VERSION - 1;
+            exchange_account.liquidation_deadline = slot;
+            let result = exchange_account_action(
+                &exchange_account,
+                &assets_list,
+                total_debt,
+                debt_shares,
+                slot,
+            );
+            assert_eq!(result, Ok(Action::Skip));
+        }
+        // Undercollateralized without deadline
+        {
+            let result = exchange_account_action(
+                &exchange_account,
+                &assets_list,
+                total_debt,
+                debt_shares,
+                slot,
+            );
+            assert_eq!(result, Ok(Action::CheckCollateralization));
+        }
+        // Deadline not reached
+        {
+            let mut exchange_account = exchange_account;
+            exchange_account.liquidation_deadline = slot + 1;
+            let result = exchange_account_action(
+                &exchange_account,
+                &assets_list,
+                total_debt,
+                debt_shares,
+                slot,
+            );
+            assert_eq!(result, Ok(Action::Skip));
+        }
+        // Deadline passed, most valuable collateral seized
+        {
+            let mut exchange_account = exchange_account;
+            exchange_account.liquidation_deadline = slot;
+            let result = exchange_account_action(
+                &exchange_account,
+                &assets_list,
+                total_debt,
+                debt_shares,
+                slot,
+            );
+            assert_eq!(result, Ok(Action::Liquidate(sol)));
+        }
+        // Insolvent account
+        {
+            let mut exchange_account = exchange_account;
+            exchange_account.liquidation_deadline = slot;
+            exchange_account.collaterals[0].amount = 0;
+            exchange_account.collaterals[1].amount = 0;
+            let result = exchange_account_action(
+                &exchange_account,
+                &assets_list,
+                total_debt,
+                debt_shares,
+                slot,
+            );
+            assert_eq!(result, Ok(Action::Skip));
+        }
+    }
+
+    #[test]
+    fn test_is_vault_entry_liquidatable() {
+        let assets_list = assets_list_with_collaterals(0);
+        let interest_rate = Decimal::from_integer(1).to_interest_rate();
+        let vault = Vault {
+            synthetic: assets_list.synthetics[0].asset_address,
+            liquidation_threshold: Decimal::from_percent(80),
+            accumulated_interest_rate: interest_rate,
+            last_update: 0,
+            ..Default::default()
+        };
+        let collateral_price = Decimal::from_integer(2).to_price();
+        let vault_entry = VaultEntry {
+            last_accumulated_interest_rate: interest_rate,
+            collateral_amount: Decimal::from_integer(100).to_scale(6),
+            synthetic_amount: Decimal::from_integer(150).to_scale(XUSD_SCALE),
+            ..Default::default()
+        };
+        // Collateral worth 200 USD, liquidation limit 160 xUSD
+        {
+            let result = is_vault_entry_liquidatable(
+                &vault,
+                &vault_entry,
+                &assets_list,
+                collateral_price,
+                &[],
+                0,
+            );
+            assert_eq!(result, Ok(false));
+        }
+        // Collateral worth 180 USD, liquidation limit 144 xUSD
+        {
+            let collateral_price = Decimal::from_price(18 * 10u128.pow(7));
+            let result = is_vault_entry_liquidatable(
+                &vault,
+                &vault_entry,
+                &assets_list,
+                collateral_price,
+                &[],
+                0,
+            );
+            assert_eq!(result, Ok(true));
+        }
+        // Basket collateral worth 20 USD covers remaining 16 xUSD
+        {
+            let mut vault = vault;
+            vault.append_collateral(VaultCollateral {
+                liquidation_threshold: Decimal::from_percent(80),
+                ..Default::default()
+            });
+            let mut vault_entry = vault_entry;
+            vault_entry.collateral_amounts[0] = Decimal::from_integer(10).to_scale(6);
+            let collateral_price = Decimal::from_price(18 * 10u128.pow(7));
+            let result = is_vault_entry_liquidatable(
+                &vault,
+                &vault_entry,
+                &assets_list,
+                collateral_price,
+                &[Decimal::from_integer(2).to_price()],
+                0,
+            );
+            assert_eq!(result, Ok(false));
+        }
+        // Empty entry
+        {
+            let mut vault_entry = vault_entry;
+            vault_entry.synthetic_amount = Decimal::from_integer(0).to_scale(XUSD_SCALE);
+            let result = is_vault_entry_liquidatable(
+                &vault,
+                &vault_entry,
+                &assets_list,
+                collateral_price,
+                &[],
+                0,
+            );
+            assert_eq!(result, Ok(false));
+        }
+    }
+
+    #[test]
+    fn test_most_valuable_vault_collateral() {
+        let mut vault = Vault {
+            ..Default::default()
+        };
+        vault.append_collateral(VaultCollateral {
+            ..Default::default()
+        });
+        vault.append_collateral(VaultCollateral {
+            ..Default::default()
+        });
+        let collateral_price = Decimal::from_integer(2).to_price();
+        let basket_prices = [
+            Decimal::from_integer(1).to_price(),
+            Decimal::from_integer(100).to_price(),
+        ];
+        let mut vault_entry = VaultEntry {
+            collateral_amount: Decimal::from_integer(10).to_scale(6),
+            ..Default::default()
+        };
+        vault_entry.collateral_amounts[0] = Decimal::from_integer(50).to_scale(6);
+        vault_entry.collateral_amounts[1] = Decimal::from_integer(0).to_scale(9);
+        // 20 USD of primary, 50 USD of first basket collateral
+        {
+            let result = most_valuable_vault_collateral(
+                &vault,
+                &vault_entry,
+                collateral_price,
+                &basket_prices,
+            );
+            assert_eq!(result, Ok(Some(VaultCollateralChoice::Basket(0))));
+        }
+        // 100 USD of second basket collateral, larger value wins over larger amount
+        {
+            let mut vault_entry = vault_entry;
+            vault_entry.collateral_amounts[1] = Decimal::from_integer(1).to_scale(9);
+            let result = most_valuable_vault_collateral(
+                &vault,
+                &vault_entry,
+                collateral_price,
+                &basket_prices,
+            );
+            assert_eq!(result, Ok(Some(VaultCollateralChoice::Basket(1))));
+        }
+        // Primary collateral worth more than basket
+        {
+            let mut vault_entry = vault_entry;
+            vault_entry.collateral_amount = Decimal::from_integer(100).to_scale(6);
+            let result = most_valuable_vault_collateral(
+                &vault,
+                &vault_entry,
+                collateral_price,
+                &basket_prices,
+            );
+            assert_eq!(result, Ok(Some(VaultCollateralChoice::Primary)));
+        }
+        // Vault without basket
+        {
+            let vault = Vault {
+                ..Default::default()
+            };
+            let result =
+                most_valuable_vault_collateral(&vault, &vault_entry, collateral_price, &[]);
+            assert_eq!(result, Ok(Some(VaultCollateralChoice::Primary)));
+        }
+        // Entry without collateral
+        {
+            let mut vault_entry = vault_entry;
+            vault_entry.collateral_amount = Decimal::from_integer(0).to_scale(6);
+            vault_entry.collateral_amounts[0] = Decimal::from_integer(0).to_scale(6);
+            let result = most_valuable_vault_collateral(
+                &vault,
+                &vault_entry,
+                collateral_price,
+                &basket_prices,
+            );
+            assert_eq!(result, Ok(None));
+        }
+    }
+}
@@ -0,0 +1,46 @@
+// Liquidation bot of Synthetify exchange
+//
+// Usage: liquidator [cluster] [keypair] [interval in seconds]
+// Against local validator (e.g. started by `anchor localnet`):
+//     cargo run -- localnet ~/.config/solana/id.json 10
+// Interval 0 runs single pass, which is handy for tests.
+// Liquidator repays debt from its associated token accounts of xUSD and vault synthetics,
+// seized collateral goes to associated token accounts which have to exist beforehand.
+mod health;
+mod rpc;
+
+use std::str::FromStr;
+use std::{env, thread, time::Duration};
+
+use anchor_client::solana_sdk::commitment_config::CommitmentConfig;
+use anchor_client::solana_sdk::signature::read_keypair_file;
+use anchor_client::{Client, Cluster};
+use rpc::Liquidator;
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let cluster = Cluster::from_str(args.get(1).map(String::as_str).unwrap_or("localnet"))
+        .expect("Invalid cluster");
+    let keypair_path = match args.get(2) {
+        Some(path) => path.clone(),
+        None => format!("{}/.config/solana/id.json", env::var("HOME").unwrap()),
+    };
+    let interval: u64 = match args.get(3) {
+        Some(interval) => interval.parse().expect("Invalid interval"),
+        None => 10,
+    };
+
+    let payer = read_keypair_file(&keypair_path).expect("Unable to read keypair");
+    let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+    let liquidator = Liquidator::new(client.program(exchange::ID));
+
+    loop {
+        if let Err(err) = liquidator.run() {
+            println!("Liquidation pass failed: {}", err);
+        }
+        if interval == 0 {
+            return;
+        }
+        thread::sleep(Duration::from_secs(interval));
+    }
+}
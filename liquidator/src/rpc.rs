@@ -0,0 +1,393 @@
+use std::mem::size_of;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anchor_client::anchor_lang::prelude::*;
+use anchor_client::anchor_lang::Discriminator;
+use anchor_client::solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use anchor_client::solana_client::rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType};
+use anchor_client::solana_sdk::account::Account;
+use anchor_client::solana_sdk::bs58;
+use anchor_client::solana_sdk::signature::Signature;
+use anchor_client::{ClientError, Program};
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token;
+use exchange::account::{AssetsList, Decimal, ExchangeAccount, State, Vault, VaultEntry};
+use exchange::utils::{load_price_from_feed, vault_collateral_price};
+use solana_account_decoder::UiAccountEncoding;
+
+use crate::health::{
+    calculate_total_debt, exchange_account_action, is_vault_entry_liquidatable,
+    most_valuable_vault_collateral, Action, VaultCollateralChoice,
+};
+
+// Keeps set_assets_prices transactions below size limit
+const PRICE_FEEDS_PER_TRANSACTION: usize = 20;
+
+pub struct Liquidator {
+    program: Program,
+    state_address: Pubkey,
+}
+
+impl Liquidator {
+    pub fn new(program: Program) -> Self {
+        let (state_address, _) = Pubkey::find_program_address(&[b"statev1"], &program.id());
+        Liquidator {
+            program,
+            state_address,
+        }
+    }
+
+    // Single pass over all exchange accounts and vault entries
+    pub fn run(&self) -> std::result::Result<(), ClientError> {
+        let state: State = self.program.account(self.state_address)?;
+        if state.halted {
+            return Ok(());
+        }
+        let assets_list: Box<AssetsList> = Box::new(self.program.account(state.assets_list)?);
+        self.refresh_prices(&state, &assets_list)?;
+        let assets_list: Box<AssetsList> = Box::new(self.program.account(state.assets_list)?);
+
+        let slot = self.program.rpc().get_slot()?;
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        let total_debt = calculate_total_debt(&state, &assets_list, slot, timestamp)?;
+        for (address, exchange_account) in self.program_accounts::<ExchangeAccount>()? {
+            let action = exchange_account_action(
+                &exchange_account,
+                &assets_list,
+                total_debt,
+                state.debt_shares,
+                slot,
+            )?;
+            let result = match action {
+                Action::Skip => continue,
+                Action::CheckCollateralization => self.check_collateralization(&state, address),
+                Action::Liquidate(collateral_address) => {
+                    self.liquidate(&state, &assets_list, address, collateral_address)
+                }
+            };
+            report(address, &action, result);
+        }
+
+        let mut vaults = vec![];
+        for (address, vault) in self.program_accounts::<Vault>()? {
+            if vault.halted {
+                continue;
+            }
+            match self.vault_prices(&vault, &assets_list) {
+                Ok((price, basket_prices)) => vaults.push((address, vault, price, basket_prices)),
+                Err(err) => println!(
+                    "Vault {}: unable to load collateral price: {}",
+                    address, err
+                ),
+            }
+        }
+        for (address, vault_entry) in self.program_accounts::<VaultEntry>()? {
+            let (vault_address, vault, collateral_price, basket_prices) =
+                match vaults.iter().find(|(a, _, _, _)| *a == vault_entry.vault) {
+                    Some(v) => v,
+                    None => continue,
+                };
+            if !is_vault_entry_liquidatable(
+                vault,
+                &vault_entry,
+                &assets_list,
+                *collateral_price,
+                basket_prices,
+                timestamp,
+            )? {
+                continue;
+            }
+            let choice = most_valuable_vault_collateral(
+                vault,
+                &vault_entry,
+                *collateral_price,
+                basket_prices,
+            )?;
+            let (collateral, result) = match choice {
+                Some(VaultCollateralChoice::Primary) => (
+                    vault.collateral,
+                    self.liquidate_vault(&state, *vault_address, vault, address, &vault_entry),
+                ),
+                Some(VaultCollateralChoice::Basket(index)) => (
+                    vault.collaterals[index as usize].collateral,
+                    self.liquidate_vault_collateral(
+                        &state,
+                        *vault_address,
+                        vault,
+                        address,
+                        &vault_entry,
+                        index,
+                    ),
+                ),
+                None => continue,
+            };
+            report(address, &Action::Liquidate(collateral), result);
+        }
+        Ok(())
+    }
+
+    // Prices used by instructions have to be fresher than state.max_delay
+    fn refresh_prices(
+        &self,
+        state: &State,
+        assets_list: &AssetsList,
+    ) -> std::result::Result<(), ClientError> {
+        let feeds: Vec<AccountMeta> = assets_list.assets[..assets_list.head_assets as usize]
+            .iter()
+            .filter(|x| x.feed_address != Pubkey::default())
+            .map(|x| AccountMeta::new_readonly(x.feed_address, false))
+            .collect();
+
+        for chunk in feeds.chunks(PRICE_FEEDS_PER_TRANSACTION) {
+            self.program
+                .request()
+                .accounts(exchange::accounts::SetAssetsPrices {
+                    assets_list: state.assets_list,
+                })
+                .accounts(chunk.to_vec())
+                .args(exchange::instruction::SetAssetsPrices {})
+                .send()?;
+        }
+        Ok(())
+    }
+
+    // Accounts of given type with current layout, not migrated accounts are skipped
+    fn program_accounts<T: AccountDeserialize + Discriminator>(
+        &self,
+    ) -> std::result::Result<Vec<(Pubkey, T)>, ClientError> {
+        let config = RpcProgramAccountsConfig {
+            filters: Some(vec![
+                RpcFilterType::DataSize((8 + size_of::<T>()) as u64),
+                RpcFilterType::Memcmp(Memcmp {
+                    offset: 0,
+                    bytes: MemcmpEncodedBytes::Base58(
+                        bs58::encode(T::discriminator()).into_string(),
+                    ),
+                    encoding: None,
+                }),
+            ]),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                ..RpcAccountInfoConfig::default()
+            },
+            with_context: None,
+        };
+        let accounts = self
+            .program
+            .rpc()
+            .get_program_accounts_with_config(&self.program.id(), config)?;
+
+        Ok(accounts
+            .into_iter()
+            .filter_map(|(address, account)| {
+                T::try_deserialize(&mut account.data.as_slice())
+                    .ok()
+                    .map(|x| (address, x))
+            })
+            .collect())
+    }
+
+    // Price of vault collateral followed by prices of basket collaterals
+    fn vault_prices(
+        &self,
+        vault: &Vault,
+        assets_list: &AssetsList,
+    ) -> std::result::Result<(Decimal, Vec<Decimal>), ClientError> {
+        let oracle_price =
+            self.feed_price(vault.collateral_price_feed, vault.oracle_type, assets_list)?;
+        let collateral_price =
+            vault_collateral_price(vault, oracle_price).map_err(ProgramError::from)?;
+        let basket_prices = vault.collaterals[..vault.head_collaterals as usize]
+            .iter()
+            .map(|x| self.feed_price(x.collateral_price_feed, x.oracle_type, assets_list))
+            .collect::<std::result::Result<Vec<Decimal>, ClientError>>()?;
+        Ok((collateral_price, basket_prices))
+    }
+
+    fn feed_price(
+        &self,
+        key: Pubkey,
+        oracle_type: u8,
+        assets_list: &AssetsList,
+    ) -> std::result::Result<Decimal, ClientError> {
+        // Default feed means price is taken from assets list
+        let mut account = match key == Pubkey::default() {
+            true => Account::default(),
+            false => self.program.rpc().get_account(&key)?,
+        };
+        let price_feed = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut account.lamports,
+            &mut account.data,
+            &account.owner,
+            false,
+            0,
+        );
+        let price = load_price_from_feed(&price_feed, oracle_type, &assets_list.assets)
+            .map_err(ProgramError::from)?;
+        Ok(price)
+    }
+
+    fn check_collateralization(
+        &self,
+        state: &State,
+        exchange_account: Pubkey,
+    ) -> std::result::Result<Signature, ClientError> {
+        self.program
+            .request()
+            .accounts(exchange::accounts::CheckCollateralization {
+                state: self.state_address,
+                exchange_account,
+                assets_list: state.assets_list,
+            })
+            .args(exchange::instruction::CheckAccountCollateralization {})
+            .send()
+    }
+
+    // Repays maximal amount of debt using liquidator's xUSD
+    fn liquidate(
+        &self,
+        state: &State,
+        assets_list: &AssetsList,
+        exchange_account: Pubkey,
+        collateral_address: Pubkey,
+    ) -> std::result::Result<Signature, ClientError> {
+        let collateral = match assets_list.collaterals[..assets_list.head_collaterals as usize]
+            .iter()
+            .find(|x| x.collateral_address == collateral_address)
+        {
+            Some(c) => c,
+            None => return Err(ClientError::AccountNotFound),
+        };
+        let usd_token = assets_list.synthetics[0].asset_address;
+        let payer = self.program.payer();
+
+        self.program
+            .request()
+            .accounts(exchange::accounts::Liquidate {
+                state: self.state_address,
+                exchange_authority: state.exchange_authority,
+                assets_list: state.assets_list,
+                token_program: token::ID,
+                usd_token,
+                liquidator_usd_account: get_associated_token_address(&payer, &usd_token),
+                liquidator_collateral_account: get_associated_token_address(
+                    &payer,
+                    &collateral_address,
+                ),
+                exchange_account,
+                signer: payer,
+                liquidation_fund: collateral.liquidation_fund,
+                reserve_account: collateral.reserve_address,
+            })
+            .args(exchange::instruction::Liquidate { amount: u64::MAX })
+            .send()
+    }
+
+    // Repays maximal amount of vault debt using liquidator's synthetic
+    fn liquidate_vault(
+        &self,
+        state: &State,
+        vault_address: Pubkey,
+        vault: &Vault,
+        vault_entry_address: Pubkey,
+        vault_entry: &VaultEntry,
+    ) -> std::result::Result<Signature, ClientError> {
+        let payer = self.program.payer();
+
+        self.program
+            .request()
+            .accounts(exchange::accounts::LiquidateVault {
+                state: self.state_address,
+                vault_entry: vault_entry_address,
+                vault: vault_address,
+                synthetic: vault.synthetic,
+                collateral: vault.collateral,
+                collateral_price_feed: vault.collateral_price_feed,
+                assets_list: state.assets_list,
+                collateral_reserve: vault.collateral_reserve,
+                liquidator_synthetic_account: get_associated_token_address(
+                    &payer,
+                    &vault.synthetic,
+                ),
+                liquidator_collateral_account: get_associated_token_address(
+                    &payer,
+                    &vault.collateral,
+                ),
+                liquidation_fund: vault.liquidation_fund,
+                token_program: token::ID,
+                owner: vault_entry.owner,
+                liquidator: payer,
+                exchange_authority: state.exchange_authority,
+            })
+            .accounts(basket_feeds(vault))
+            .args(exchange::instruction::LiquidateVault { amount: u64::MAX })
+            .send()
+    }
+
+    // Repays maximal amount of vault debt seizing basket collateral of given index
+    fn liquidate_vault_collateral(
+        &self,
+        state: &State,
+        vault_address: Pubkey,
+        vault: &Vault,
+        vault_entry_address: Pubkey,
+        vault_entry: &VaultEntry,
+        index: u8,
+    ) -> std::result::Result<Signature, ClientError> {
+        let payer = self.program.payer();
+        let vault_collateral = &vault.collaterals[index as usize];
+
+        self.program
+            .request()
+            .accounts(exchange::accounts::LiquidateVaultCollateral {
+                state: self.state_address,
+                vault_entry: vault_entry_address,
+                vault: vault_address,
+                synthetic: vault.synthetic,
+                collateral_price_feed: vault.collateral_price_feed,
+                assets_list: state.assets_list,
+                collateral_reserve: vault_collateral.collateral_reserve,
+                liquidator_synthetic_account: get_associated_token_address(
+                    &payer,
+                    &vault.synthetic,
+                ),
+                liquidator_collateral_account: get_associated_token_address(
+                    &payer,
+                    &vault_collateral.collateral,
+                ),
+                liquidation_fund: vault_collateral.liquidation_fund,
+                token_program: token::ID,
+                owner: vault_entry.owner,
+                liquidator: payer,
+                exchange_authority: state.exchange_authority,
+            })
+            .accounts(basket_feeds(vault))
+            .args(exchange::instruction::LiquidateVaultCollateral {
+                index,
+                amount: u64::MAX,
+            })
+            .send()
+    }
+}
+
+// Basket price feeds are passed in order of vault collaterals
+fn basket_feeds(vault: &Vault) -> Vec<AccountMeta> {
+    vault.collaterals[..vault.head_collaterals as usize]
+        .iter()
+        .map(|x| AccountMeta::new_readonly(x.collateral_price_feed, false))
+        .collect()
+}
+
+fn report(address: Pubkey, action: &Action, result: std::result::Result<Signature, ClientError>) {
+    match result {
+        Ok(signature) => println!("{} {:?}: {}", address, action, signature),
+        Err(err) => println!("{} {:?} failed: {}", address, action, err),
+    }
+}
@@ -46,6 +46,24 @@ pub struct UserStaking {
 }
 #[zero_copy]
 #[derive(PartialEq, Default, Debug)]
+pub struct RewardStream {
+    // 114
+    pub mint: Pubkey,              // 32 Token distributed to debt stakers
+    pub fund_account: Pubkey,      // 32 Source account of rewards owned by exchange authority
+    pub amount_per_slot: Decimal,  // 17 Amount distributed every slot, in decimals of mint
+    pub end_slot: u64,             // 8 Slot after which stream stops accruing
+    pub reward_per_share: Decimal, // 17 Amount accrued per debt share
+    pub last_update: u64,          // 8 Slot up to which reward_per_share is accrued
+}
+#[zero_copy]
+#[derive(PartialEq, Default, Debug)]
+pub struct UserRewardStream {
+    // 34
+    pub amount_to_claim: Decimal,       // 17 Amount accumulated by account
+    pub last_reward_per_share: Decimal, // 17 Stream reward_per_share already moved to amount_to_claim
+}
+#[zero_copy]
+#[derive(PartialEq, Default, Debug)]
 pub struct Asset {
     // 109
     pub feed_address: Pubkey, // 32 Pyth oracle account address
@@ -59,14 +77,16 @@ pub struct Asset {
 #[zero_copy]
 #[derive(PartialEq, Default, Debug)]
 pub struct Collateral {
-    // 148
-    pub asset_index: u8,            // 1
-    pub collateral_address: Pubkey, // 32
-    pub reserve_address: Pubkey,    // 32
-    pub liquidation_fund: Pubkey,   // 32
-    pub reserve_balance: Decimal,   // 17
-    pub collateral_ratio: Decimal,  // 17
-    pub max_collateral: Decimal,    // 17
+    // 182
+    pub asset_index: u8,                // 1
+    pub collateral_address: Pubkey,     // 32
+    pub reserve_address: Pubkey,        // 32
+    pub liquidation_fund: Pubkey,       // 32
+    pub reserve_balance: Decimal,       // 17
+    pub collateral_ratio: Decimal,      // 17
+    pub max_collateral: Decimal,        // 17
+    pub penalty_to_liquidator: Decimal, // 17 Overrides global penalty, zero scale if not set
+    pub penalty_to_exchange: Decimal,   // 17 Overrides global penalty, zero scale if not set
 }
 #[zero_copy]
 #[derive(PartialEq, Default, Debug)]
@@ -105,7 +125,26 @@ pub struct State {
     pub staking: Staking, // 152
     pub exchange_authority: Pubkey, // 32
     pub bump: u8,       // 1
-    pub padding: [u8; 1620], // 1620 (2048 - 428) reserved for future use
+    pub version: u8,    // 1 Version of state struct
+    pub liquidation_auction_duration: u32, // 4 In slots rise of liquidator penalty after deadline, 0 disables
+    pub max_penalty_to_liquidator: Decimal, // 17 In % range 0-25% penalty at the end of auction
+    pub insurance_fund: Decimal, // 17 Amount of xUSD covering bad debt
+    pub insurance_fund_ratio: Decimal, // 17 In % share of swap tax and exchange penalty going to insurance fund
+    pub keeper_bounty: Decimal, // 17 Amount of xUSD paid for setting liquidation_deadline, 0 disables
+    pub keeper_bounty_cooldown: u32, // 4 In slots minimal time between bounties for the same account
+    pub flash_liquidation_debt: u64, // 8 Amount of xUSD to be returned by flash liquidator within transaction
+    pub full_liquidation_health: Decimal, // 17 Health (max_debt / user_debt) at which entire debt can be repaid, 0 keeps fixed liquidation_rate
+    pub reward_per_share: Decimal, // 17 SNY accrued per debt share since start of continuous staking
+    pub last_reward_update: u64, // 8 Slot up to which reward_per_share is accrued
+    pub head_reward_streams: u8, // 1
+    pub reward_streams: [RewardStream; 4], // 456 Rewards distributed to debt stakers next to SNY
+    pub rewards_vesting_duration: u32, // 4 In slots linear vesting of withdrawn SNY rewards, 0 disables
+    pub early_exit_penalty: Decimal, // 17 In % of unvested rewards redistributed to stakers on early withdrawal
+    pub swap_tax_reward_ratio: Decimal, // 17 In % of swap tax distributed to debt stakers instead of swap_tax_reserve
+    pub swap_tax_reward_per_share: Decimal, // 17 xUSD of swap tax accrued per debt share
+    pub max_staking_boost: Decimal, // 17 In % extra SNY rewards for SNY collateral covering whole debt, 0 disables
+    pub boosted_debt_shares: u64, // 8 Sum of boosted_debt_shares of accounts, base of SNY rewards
+    pub padding: [u8; 956], // 956 (2048 - 1092) reserved for future use
 }
 impl Default for State {
     #[inline]
@@ -131,15 +170,42 @@ impl Default for State {
             staking: Staking::default(),
             exchange_authority: Pubkey::default(),
             bump: 0,
-            padding: [0; 1620],
+            version: 0,
+            liquidation_auction_duration: 0,
+            max_penalty_to_liquidator: Decimal::default(),
+            insurance_fund: Decimal::default(),
+            insurance_fund_ratio: Decimal::default(),
+            keeper_bounty: Decimal::default(),
+            keeper_bounty_cooldown: 0,
+            flash_liquidation_debt: 0,
+            full_liquidation_health: Decimal::default(),
+            reward_per_share: Decimal::default(),
+            last_reward_update: 0,
+            head_reward_streams: 0,
+            reward_streams: [RewardStream {
+                ..Default::default()
+            }; 4],
+            rewards_vesting_duration: 0,
+            early_exit_penalty: Decimal::default(),
+            swap_tax_reward_ratio: Decimal::default(),
+            swap_tax_reward_per_share: Decimal::default(),
+            max_staking_boost: Decimal::default(),
+            boosted_debt_shares: 0,
+            padding: [0; 956],
         }
     }
 }
+impl State {
+    pub fn append_reward_stream(&mut self, new_reward_stream: RewardStream) {
+        self.reward_streams[(self.head_reward_streams) as usize] = new_reward_stream;
+        self.head_reward_streams += 1;
+    }
+}
 
 #[account(zero_copy)]
 #[derive(PartialEq, Debug)]
 pub struct ExchangeAccount {
-    // 1412
+    // 1924
     pub owner: Pubkey,                      // 32 Identity controlling account
     pub version: u8,                        // 1 Version of account struct
     pub debt_shares: u64,                   // 8 Shares representing part of entire debt pool
@@ -148,6 +214,19 @@ pub struct ExchangeAccount {
     pub head: u8,                           // 1
     pub bump: u8,                           // 1
     pub collaterals: [CollateralEntry; 32], // 1312
+    pub delegate: Pubkey,                   // 32 Operator allowed to act on behalf of owner
+    pub delegate_permissions: u8,           // 1 Bitmask of DELEGATE_* permissions
+    pub sub_account_index: u16,             // 2 Index of account among accounts of owner
+    pub last_keeper_bounty: u64,            // 8 Slot of last bounty paid for this account
+    pub last_reward_per_share: Decimal,     // 17 State reward_per_share already moved to amount_to_claim
+    pub reward_streams: [UserRewardStream; 4], // 136 Indexed like reward_streams of State
+    pub vesting_amount: u64,                // 8 SNY rewards vesting linearly until vesting_end
+    pub vesting_start: u64,                 // 8 Slot from which vesting_amount vests
+    pub vesting_end: u64,                   // 8 Slot when entire vesting_amount is vested
+    pub swap_tax_rewards: UserRewardStream, // 34 xUSD of swap tax distributed to account
+    pub staking_boost: Decimal,             // 17 In % extra SNY rewards, updated when SNY collateral or debt changes
+    pub boosted_debt_shares: u64,           // 8 Debt shares weighted by staking_boost, base of SNY rewards
+    pub padding: [u8; 233],                 // 233 (512 - 279) reserved for future use
 }
 impl Default for ExchangeAccount {
     #[inline]
@@ -163,6 +242,21 @@ impl Default for ExchangeAccount {
             collaterals: [CollateralEntry {
                 ..Default::default()
             }; 32],
+            delegate: Pubkey::default(),
+            delegate_permissions: 0,
+            sub_account_index: 0,
+            last_keeper_bounty: 0,
+            last_reward_per_share: Decimal::default(),
+            reward_streams: [UserRewardStream {
+                ..Default::default()
+            }; 4],
+            vesting_amount: 0,
+            vesting_start: 0,
+            vesting_end: 0,
+            swap_tax_rewards: UserRewardStream::default(),
+            staking_boost: Decimal::default(),
+            boosted_debt_shares: 0,
+            padding: [0; 233],
         }
     }
 }
@@ -174,7 +268,31 @@ pub struct CollateralEntry {
     pub collateral_address: Pubkey, // 32
     pub index: u8,                  // 1
 }
+// Permissions which can be granted to delegate of exchange account
+pub const DELEGATE_DEPOSIT: u8 = 1 << 0;
+pub const DELEGATE_BURN: u8 = 1 << 1;
+pub const DELEGATE_MINT: u8 = 1 << 2;
+pub const DELEGATE_WITHDRAW: u8 = 1 << 3; // withdraw only to accounts of owner
+pub const DELEGATE_CLAIM_REWARDS: u8 = 1 << 4; // withdraw rewards only to accounts of owner
+pub const DELEGATE_ALL: u8 =
+    DELEGATE_DEPOSIT | DELEGATE_BURN | DELEGATE_MINT | DELEGATE_WITHDRAW | DELEGATE_CLAIM_REWARDS;
+
 impl ExchangeAccount {
+    pub fn is_authorized(&self, signer: &Pubkey, permission: u8) -> bool {
+        if self.owner.eq(signer) {
+            return true;
+        }
+        self.delegate.eq(signer)
+            && self.delegate.ne(&Pubkey::default())
+            && self.delegate_permissions & permission == permission
+    }
+    // Account with debt but without any collateral left
+    pub fn is_insolvent(&self) -> bool {
+        self.debt_shares > 0
+            && self.collaterals[..self.head as usize]
+                .iter()
+                .all(|x| x.amount == 0)
+    }
     pub fn append(&mut self, entry: CollateralEntry) {
         self.collaterals[(self.head) as usize] = entry;
         self.head += 1;
@@ -206,12 +324,12 @@ pub struct Swapline {
 #[account(zero_copy)]
 // #[derive(Default)]
 pub struct AssetsList {
-    // 93333
+    // 102003
     pub head_assets: u8,                // 1
     pub head_collaterals: u8,           // 1
     pub head_synthetics: u8,            // 1
     pub assets: [Asset; 255],           // 27795
-    pub collaterals: [Collateral; 255], // 37740
+    pub collaterals: [Collateral; 255], // 46410
     pub synthetics: [Synthetic; 255],   // 27795
 }
 impl Default for AssetsList {
@@ -279,10 +397,23 @@ pub struct Decimal {
     pub scale: u8, // 1
 }
 
+#[zero_copy]
+#[derive(PartialEq, Default, Debug)]
+pub struct VaultCollateral {
+    // 180
+    pub collateral: Pubkey,             // 32
+    pub collateral_price_feed: Pubkey,  // 32
+    pub oracle_type: u8,                // 1
+    pub collateral_ratio: Decimal,      // 17
+    pub liquidation_threshold: Decimal, // 17
+    pub collateral_reserve: Pubkey,     // 32
+    pub liquidation_fund: Pubkey,       // 32
+    pub collateral_amount: Decimal,     // 17
+}
 #[account(zero_copy)]
-#[derive(Default, Debug)]
+#[derive(Debug)]
 pub struct Vault {
-    // 391
+    // 1536
     pub halted: bool,                            // 1
     pub synthetic: Pubkey,                       // 32
     pub collateral: Pubkey,                      // 32
@@ -305,15 +436,101 @@ pub struct Vault {
     pub last_update: i64,                        // 8
     pub bump: u8,                                // 1
     pub vault_type: u8,                          // 1
+    pub head_collaterals: u8,                    // 1 Number of basket collaterals next to collateral
+    pub collaterals: [VaultCollateral; 4],       // 720 Basket collaterals, entries hold them in collateral_amounts
+    pub interest_curve_enabled: bool,            // 1 Debt interest rate follows utilization of max_borrow
+    pub optimal_utilization: Decimal,            // 17
+    pub interest_rate_at_optimal: Decimal,       // 17
+    pub max_interest_rate: Decimal,              // 17 Rate at full utilization
+    pub current_interest_rate: Decimal,          // 17 Rate applied since last_update
+    pub deleverage_fee: Decimal,                 // 17 Share of sold collateral paid to keeper
+    pub kind: u8,                                // 1 Behaviour of vault (VaultType) fixed on creation, vault_type is only a seed
+    pub padding: [u8; 352],                      // 352 (1536 - 1184) reserved for future use
+}
+impl Default for Vault {
+    #[inline]
+    fn default() -> Vault {
+        Vault {
+            halted: false,
+            synthetic: Pubkey::default(),
+            collateral: Pubkey::default(),
+            collateral_price_feed: Pubkey::default(),
+            oracle_type: 0,
+            open_fee: Decimal::default(),
+            debt_interest_rate: Decimal::default(),
+            collateral_ratio: Decimal::default(),
+            liquidation_threshold: Decimal::default(),
+            liquidation_ratio: Decimal::default(),
+            liquidation_penalty_liquidator: Decimal::default(),
+            liquidation_penalty_exchange: Decimal::default(),
+            accumulated_interest: Decimal::default(),
+            accumulated_interest_rate: Decimal::default(),
+            liquidation_fund: Pubkey::default(),
+            collateral_reserve: Pubkey::default(),
+            mint_amount: Decimal::default(),
+            collateral_amount: Decimal::default(),
+            max_borrow: Decimal::default(),
+            last_update: 0,
+            bump: 0,
+            vault_type: 0,
+            head_collaterals: 0,
+            collaterals: [VaultCollateral {
+                ..Default::default()
+            }; 4],
+            interest_curve_enabled: false,
+            optimal_utilization: Decimal::default(),
+            interest_rate_at_optimal: Decimal::default(),
+            max_interest_rate: Decimal::default(),
+            current_interest_rate: Decimal::default(),
+            deleverage_fee: Decimal::default(),
+            kind: 0,
+            padding: [0; 352],
+        }
+    }
+}
+impl Vault {
+    pub fn append_collateral(&mut self, new_collateral: VaultCollateral) {
+        self.collaterals[(self.head_collaterals) as usize] = new_collateral;
+        self.head_collaterals += 1;
+    }
 }
 #[account(zero_copy)]
-#[derive(PartialEq, Default, Debug)]
+#[derive(PartialEq, Debug)]
 pub struct VaultEntry {
-    // 116
+    // 384
     pub owner: Pubkey,                           // 32
     pub vault: Pubkey,                           // 32
     pub last_accumulated_interest_rate: Decimal, // 17
     pub synthetic_amount: Decimal,               // 17
     pub collateral_amount: Decimal,              // 17
     pub bump: u8,                                // 1
+    pub collateral_amounts: [Decimal; 4],        // 68 Indexed like collaterals of Vault, zero scale if not used
+    pub deleverage_threshold: Decimal,           // 17 Debt to collateral value ratio allowing keeper deleverage, zero if disabled
+    pub deleverage_target: Decimal,              // 17 Ratio restored by deleverage
+    pub padding: [u8; 166],                      // 166 (384 - 218) reserved for future use
+}
+impl Default for VaultEntry {
+    #[inline]
+    fn default() -> VaultEntry {
+        VaultEntry {
+            owner: Pubkey::default(),
+            vault: Pubkey::default(),
+            last_accumulated_interest_rate: Decimal::default(),
+            synthetic_amount: Decimal::default(),
+            collateral_amount: Decimal::default(),
+            bump: 0,
+            collateral_amounts: [Decimal::default(); 4],
+            deleverage_threshold: Decimal::default(),
+            deleverage_target: Decimal::default(),
+            padding: [0; 166],
+        }
+    }
+}
+impl VaultEntry {
+    // Entry without debt or any collateral can be closed
+    pub fn is_empty(&self) -> bool {
+        self.synthetic_amount.val == 0
+            && self.collateral_amount.val == 0
+            && self.collateral_amounts.iter().all(|x| x.val == 0)
+    }
 }
@@ -2,7 +2,7 @@ use anchor_lang::prelude::*;
 use crate::account::*;
 use crate::oracle::*;
 use anchor_spl::token::{self, Burn, MintTo, TokenAccount, Transfer};
-use anchor_lang::solana_program::system_program;
+use anchor_lang::solana_program::{system_program, sysvar};
 use crate::decimal::XUSD_SCALE;
 
 #[derive(Accounts)]
@@ -285,6 +285,55 @@ impl<'a, 'b, 'c, 'info> From<&AdminWithdraw<'info>>
     }
 }
 #[derive(Accounts)]
+pub struct WithdrawSwapTaxRewards<'info> {
+    #[account(mut,
+        seeds = [b"statev1".as_ref()],
+        bump = state.load()?.bump,
+        constraint = state.to_account_info().owner == program_id
+    )]
+    pub state: Loader<'info, State>,
+    #[account(mut)]
+    pub exchange_account: Loader<'info, ExchangeAccount>,
+    // owner or delegate of exchange_account
+    #[account(signer)]
+    pub signer: AccountInfo<'info>,
+    #[account(
+        constraint = exchange_authority.key == &state.load()?.exchange_authority
+    )]
+    pub exchange_authority: AccountInfo<'info>,
+    #[account(
+        constraint = assets_list.to_account_info().key == &state.load()?.assets_list,
+        constraint = assets_list.to_account_info().owner == program_id
+    )]
+    pub assets_list: Loader<'info, AssetsList>,
+    #[account(mut,
+        constraint = usd_token.decimals == XUSD_SCALE,
+        constraint = usd_token.to_account_info().key == &assets_list.load()?.synthetics[0].asset_address
+    )]
+    pub usd_token: Account<'info, anchor_spl::token::Mint>,
+    #[account(mut,
+        constraint = &to.mint == usd_token.to_account_info().key
+    )]
+    pub to: Account<'info, TokenAccount>,
+    #[account(address = token::ID)]
+    pub token_program: AccountInfo<'info>,
+}
+impl<'a, 'b, 'c, 'info> From<&WithdrawSwapTaxRewards<'info>>
+    for CpiContext<'a, 'b, 'c, 'info, MintTo<'info>>
+{
+    fn from(
+        accounts: &WithdrawSwapTaxRewards<'info>,
+    ) -> CpiContext<'a, 'b, 'c, 'info, MintTo<'info>> {
+        let cpi_accounts = MintTo {
+            mint: accounts.usd_token.to_account_info(),
+            to: accounts.to.to_account_info(),
+            authority: accounts.exchange_authority.to_account_info(),
+        };
+        let cpi_program = accounts.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+#[derive(Accounts)]
 pub struct WithdrawAccumulatedDebtInterest<'info> {
     #[account(mut,
         seeds = [b"statev1".as_ref()],
@@ -431,6 +480,62 @@ pub struct SetMaxCollateral<'info> {
     pub collateral_address: Account<'info, anchor_spl::token::Mint>,
 }
 #[derive(Accounts)]
+pub struct SetCollateralLiquidationPenalties<'info> {
+    #[account(mut,
+        seeds = [b"statev1".as_ref()],
+        bump = state.load()?.bump,
+        constraint = state.to_account_info().owner == program_id
+    )]
+    pub state: Loader<'info, State>,
+    #[account(signer)]
+    pub admin: AccountInfo<'info>,
+    #[account(mut,
+        constraint = assets_list.to_account_info().key == &state.load()?.assets_list,
+        constraint = assets_list.to_account_info().owner == program_id
+    )]
+    pub assets_list: Loader<'info, AssetsList>,
+    pub collateral_address: Account<'info, anchor_spl::token::Mint>,
+}
+#[derive(Accounts)]
+pub struct MigrateAssetsList<'info> {
+    #[account(
+        seeds = [b"statev1".as_ref()],
+        bump = state.load()?.bump,
+        constraint = state.to_account_info().owner == program_id
+    )]
+    pub state: Loader<'info, State>,
+    #[account(mut, signer)]
+    pub admin: AccountInfo<'info>,
+    // legacy layout cannot be loaded
+    #[account(mut,
+        constraint = assets_list.key == &state.load()?.assets_list,
+        constraint = assets_list.owner == program_id
+    )]
+    pub assets_list: AccountInfo<'info>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+#[derive(Accounts)]
+pub struct MigrateVault<'info> {
+    #[account(
+        seeds = [b"statev1".as_ref()],
+        bump = state.load()?.bump,
+        constraint = state.to_account_info().owner == program_id
+    )]
+    pub state: Loader<'info, State>,
+    #[account(mut, signer)]
+    pub admin: AccountInfo<'info>,
+    // legacy layout cannot be loaded
+    #[account(mut,
+        constraint = vault.owner == program_id
+    )]
+    pub vault: AccountInfo<'info>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+#[derive(Accounts)]
 pub struct SetAdmin<'info> {
     #[account(mut,
         seeds = [b"statev1".as_ref()],
@@ -495,6 +600,117 @@ pub struct CreateExchangeAccount<'info> {
     #[account(address = system_program::ID)]
     pub system_program: AccountInfo<'info>,
 }
+#[derive(Accounts)]
+#[instruction(bump: u8, index: u16)]
+pub struct CreateSubAccount<'info> {
+    #[account(init, seeds = [b"accountv1", admin.key.as_ref(), &index.to_le_bytes()], bump=bump, payer=payer )]
+    pub exchange_account: Loader<'info, ExchangeAccount>,
+    pub admin: AccountInfo<'info>,
+    #[account(mut, signer)]
+    pub payer: AccountInfo<'info>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+#[derive(Accounts)]
+pub struct TransferCollateral<'info> {
+    #[account(mut,
+        seeds = [b"statev1".as_ref()],
+        bump = state.load()?.bump,
+        constraint = state.to_account_info().owner == program_id
+    )]
+    pub state: Loader<'info, State>,
+    #[account(mut,
+        constraint = assets_list.to_account_info().key == &state.load()?.assets_list,
+        constraint = assets_list.to_account_info().owner == program_id
+    )]
+    pub assets_list: Loader<'info, AssetsList>,
+    #[account(mut, has_one = owner)]
+    pub exchange_account: Loader<'info, ExchangeAccount>,
+    #[account(mut, has_one = owner,
+        constraint = to_exchange_account.to_account_info().key != exchange_account.to_account_info().key
+    )]
+    pub to_exchange_account: Loader<'info, ExchangeAccount>,
+    #[account(signer)]
+    pub owner: AccountInfo<'info>,
+}
+#[derive(Accounts)]
+pub struct TransferPosition<'info> {
+    #[account(mut,
+        seeds = [b"statev1".as_ref()],
+        bump = state.load()?.bump,
+        constraint = state.to_account_info().owner == program_id
+    )]
+    pub state: Loader<'info, State>,
+    #[account(mut,
+        constraint = assets_list.to_account_info().key == &state.load()?.assets_list,
+        constraint = assets_list.to_account_info().owner == program_id
+    )]
+    pub assets_list: Loader<'info, AssetsList>,
+    #[account(mut, has_one = owner)]
+    pub exchange_account: Loader<'info, ExchangeAccount>,
+    #[account(mut,
+        constraint = &to_exchange_account.load()?.owner == to_owner.key,
+        constraint = to_exchange_account.to_account_info().key != exchange_account.to_account_info().key
+    )]
+    pub to_exchange_account: Loader<'info, ExchangeAccount>,
+    #[account(signer)]
+    pub owner: AccountInfo<'info>,
+    // owner of destination has to accept debt
+    #[account(signer)]
+    pub to_owner: AccountInfo<'info>,
+}
+#[derive(Accounts)]
+pub struct MigrateExchangeAccount<'info> {
+    #[account(
+        seeds = [b"statev1".as_ref()],
+        bump = state.load()?.bump,
+        constraint = state.to_account_info().owner == program_id
+    )]
+    pub state: Loader<'info, State>,
+    // anyone can migrate any exchange_account
+    #[account(mut,
+        constraint = exchange_account.owner == program_id
+    )]
+    pub exchange_account: AccountInfo<'info>,
+    #[account(mut, signer)]
+    pub payer: AccountInfo<'info>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+#[derive(Accounts)]
+pub struct MigrateVaultEntry<'info> {
+    // anyone can migrate any vault_entry
+    #[account(mut,
+        constraint = vault_entry.owner == program_id
+    )]
+    pub vault_entry: AccountInfo<'info>,
+    #[account(mut, signer)]
+    pub payer: AccountInfo<'info>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+#[derive(Accounts)]
+pub struct SetDelegate<'info> {
+    #[account(mut, has_one = owner)]
+    pub exchange_account: Loader<'info, ExchangeAccount>,
+    #[account(signer)]
+    pub owner: AccountInfo<'info>,
+    pub delegate: AccountInfo<'info>,
+}
+#[derive(Accounts)]
+pub struct MigrateState<'info> {
+    #[account(mut,
+        seeds = [b"statev1".as_ref()],
+        bump = state.load()?.bump,
+        constraint = state.to_account_info().owner == program_id
+    )]
+    pub state: Loader<'info, State>,
+    #[account(signer)]
+    pub admin: AccountInfo<'info>,
+}
 
 
 #[derive(Accounts)]
@@ -522,10 +738,11 @@ pub struct Withdraw<'info> {
     pub user_collateral_account: Account<'info, TokenAccount>, // can withdraw to any account except reserve_account
     #[account(address = token::ID)]
     pub token_program: AccountInfo<'info>,
-    #[account(mut, has_one = owner)]
+    #[account(mut)]
     pub exchange_account: Loader<'info, ExchangeAccount>,
+    // owner or delegate of exchange_account
     #[account(signer)]
-    pub owner: AccountInfo<'info>,
+    pub signer: AccountInfo<'info>,
 }
 impl<'a, 'b, 'c, 'info> From<&Withdraw<'info>> for CpiContext<'a, 'b, 'c, 'info, Transfer<'info>> {
     fn from(accounts: &Withdraw<'info>) -> CpiContext<'a, 'b, 'c, 'info, Transfer<'info>> {
@@ -564,10 +781,11 @@ pub struct Mint<'info> {
     pub to: Account<'info, TokenAccount>, // mint xusd to any account
     #[account(address = token::ID)]
     pub token_program: AccountInfo<'info>,
-    #[account(mut, has_one = owner)]
+    #[account(mut)]
     pub exchange_account: Loader<'info, ExchangeAccount>,
+    // owner or delegate of exchange_account
     #[account(signer)]
-    pub owner: AccountInfo<'info>,
+    pub signer: AccountInfo<'info>,
 }
 impl<'a, 'b, 'c, 'info> From<&Mint<'info>> for CpiContext<'a, 'b, 'c, 'info, MintTo<'info>> {
     fn from(accounts: &Mint<'info>) -> CpiContext<'a, 'b, 'c, 'info, MintTo<'info>> {
@@ -588,14 +806,14 @@ pub struct Deposit<'info> {
         constraint = state.to_account_info().owner == program_id
     )]
     pub state: Loader<'info, State>,
-    #[account(mut, has_one = owner)]
+    #[account(mut)]
     pub exchange_account: Loader<'info, ExchangeAccount>,
     #[account(mut,
         constraint = &reserve_address.owner == exchange_authority.key
     )]
     pub reserve_address: Account<'info, TokenAccount>,
     #[account(mut,
-        constraint = &user_collateral_account.owner == owner.key,
+        constraint = &user_collateral_account.owner == signer.key,
         constraint = user_collateral_account.to_account_info().key != reserve_address.to_account_info().key
     )]
     pub user_collateral_account: Account<'info, TokenAccount>,
@@ -606,9 +824,9 @@ pub struct Deposit<'info> {
         constraint = assets_list.to_account_info().owner == program_id
     )]
     pub assets_list: Loader<'info, AssetsList>,
-    // owner can deposit to any exchange_account
+    // owner or delegate of exchange_account
     #[account(signer)]
-    pub owner: AccountInfo<'info>,
+    pub signer: AccountInfo<'info>,
     #[account(constraint = exchange_authority.key == &state.load()?.exchange_authority)]
     pub exchange_authority: AccountInfo<'info>,
 }
@@ -673,7 +891,13 @@ pub struct Liquidate<'info> {
     pub reserve_account: Account<'info, TokenAccount>,
 }
 #[derive(Accounts)]
-pub struct BurnToken<'info> {
+pub struct FlashLiquidate<'info> {
+    pub liquidate: Liquidate<'info>,
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+}
+#[derive(Accounts)]
+pub struct RepayFlashLiquidation<'info> {
     #[account(mut,
         seeds = [b"statev1".as_ref()],
         bump = state.load()?.bump,
@@ -682,7 +906,7 @@ pub struct BurnToken<'info> {
     pub state: Loader<'info, State>,
     #[account(constraint = exchange_authority.key == &state.load()?.exchange_authority)]
     pub exchange_authority: AccountInfo<'info>,
-    #[account(mut,
+    #[account(
         constraint = assets_list.to_account_info().key == &state.load()?.assets_list,
         constraint = assets_list.to_account_info().owner == program_id
     )]
@@ -695,20 +919,20 @@ pub struct BurnToken<'info> {
     )]
     pub usd_token: Account<'info, anchor_spl::token::Mint>,
     #[account(mut,
-        constraint = &user_token_account_burn.mint == usd_token.to_account_info().key,
-        constraint = &user_token_account_burn.owner == owner.key
+        constraint = &liquidator_usd_account.mint == usd_token.to_account_info().key,
+        constraint = &liquidator_usd_account.owner == signer.key
     )]
-    pub user_token_account_burn: Account<'info, TokenAccount>,
-    #[account(mut, has_one = owner)]
-    pub exchange_account: Loader<'info, ExchangeAccount>,
+    pub liquidator_usd_account: Account<'info, TokenAccount>,
     #[account(signer)]
-    pub owner: AccountInfo<'info>,
+    pub signer: AccountInfo<'info>,
 }
-impl<'a, 'b, 'c, 'info> From<&BurnToken<'info>> for CpiContext<'a, 'b, 'c, 'info, Burn<'info>> {
-    fn from(accounts: &BurnToken<'info>) -> CpiContext<'a, 'b, 'c, 'info, Burn<'info>> {
+impl<'a, 'b, 'c, 'info> From<&RepayFlashLiquidation<'info>>
+    for CpiContext<'a, 'b, 'c, 'info, Burn<'info>>
+{
+    fn from(accounts: &RepayFlashLiquidation<'info>) -> CpiContext<'a, 'b, 'c, 'info, Burn<'info>> {
         let cpi_accounts = Burn {
             mint: accounts.usd_token.to_account_info(),
-            to: accounts.user_token_account_burn.to_account_info(),
+            to: accounts.liquidator_usd_account.to_account_info(),
             authority: accounts.exchange_authority.to_account_info(),
         };
         let cpi_program = accounts.token_program.to_account_info();
@@ -716,7 +940,7 @@ impl<'a, 'b, 'c, 'info> From<&BurnToken<'info>> for CpiContext<'a, 'b, 'c, 'info
     }
 }
 #[derive(Accounts)]
-pub struct Swap<'info> {
+pub struct LiquidateMulti<'info> {
     #[account(mut,
         seeds = [b"statev1".as_ref()],
         bump = state.load()?.bump,
@@ -732,57 +956,198 @@ pub struct Swap<'info> {
     pub assets_list: Loader<'info, AssetsList>,
     #[account(address = token::ID)]
     pub token_program: AccountInfo<'info>,
-    #[account(mut)]
-    pub token_in: Account<'info, anchor_spl::token::Mint>,
-    #[account(mut)]
-    pub token_for: Account<'info, anchor_spl::token::Mint>,
     #[account(mut,
-        constraint = &user_token_account_in.mint == token_in.to_account_info().key,
-        constraint = &user_token_account_in.owner  == owner.key
+        constraint = usd_token.decimals == XUSD_SCALE,
+        constraint = usd_token.to_account_info().key == &assets_list.load()?.synthetics[0].asset_address
     )]
-    pub user_token_account_in: Account<'info, TokenAccount>,
-    // out token can be transfer to any account
+    pub usd_token: Account<'info, anchor_spl::token::Mint>,
     #[account(mut,
-        constraint = &user_token_account_for.mint == token_for.to_account_info().key,
+        constraint = &liquidator_usd_account.mint == usd_token.to_account_info().key,
+        constraint = &liquidator_usd_account.owner == signer.key
     )]
-    pub user_token_account_for: Account<'info, TokenAccount>,
+    pub liquidator_usd_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub exchange_account: Loader<'info, ExchangeAccount>,
     #[account(signer)]
-    pub owner: AccountInfo<'info>,
-}
-impl<'a, 'b, 'c, 'info> From<&Swap<'info>> for CpiContext<'a, 'b, 'c, 'info, Burn<'info>> {
-    fn from(accounts: &Swap<'info>) -> CpiContext<'a, 'b, 'c, 'info, Burn<'info>> {
-        let cpi_accounts = Burn {
-            mint: accounts.token_in.to_account_info(),
-            to: accounts.user_token_account_in.to_account_info(),
-            authority: accounts.exchange_authority.to_account_info(),
-        };
-        let cpi_program = accounts.token_program.to_account_info();
-        CpiContext::new(cpi_program, cpi_accounts)
-    }
-}
-impl<'a, 'b, 'c, 'info> From<&Swap<'info>> for CpiContext<'a, 'b, 'c, 'info, MintTo<'info>> {
-    fn from(accounts: &Swap<'info>) -> CpiContext<'a, 'b, 'c, 'info, MintTo<'info>> {
-        let cpi_accounts = MintTo {
-            mint: accounts.token_for.to_account_info(),
-            to: accounts.user_token_account_for.to_account_info(),
-            authority: accounts.exchange_authority.to_account_info(),
-        };
-        let cpi_program = accounts.token_program.to_account_info();
-        CpiContext::new(cpi_program, cpi_accounts)
-    }
+    pub signer: AccountInfo<'info>,
+    // remaining accounts: ordered [reserve_account, liquidation_fund, liquidator_collateral_account]
+    // for every collateral to seize
 }
-
 #[derive(Accounts)]
-pub struct CheckCollateralization<'info> {
+pub struct PreviewLiquidation<'info> {
+    #[account(
+        seeds = [b"statev1".as_ref()],
+        bump = state.load()?.bump,
+        constraint = state.to_account_info().owner == program_id
+    )]
+    pub state: Loader<'info, State>,
+    #[account(
+        constraint = assets_list.to_account_info().key == &state.load()?.assets_list,
+        constraint = assets_list.to_account_info().owner == program_id
+    )]
+    pub assets_list: Loader<'info, AssetsList>,
+    pub exchange_account: Loader<'info, ExchangeAccount>,
+}
+#[derive(Accounts)]
+pub struct BurnToken<'info> {
     #[account(mut,
         seeds = [b"statev1".as_ref()],
         bump = state.load()?.bump,
         constraint = state.to_account_info().owner == program_id
     )]
     pub state: Loader<'info, State>,
+    #[account(constraint = exchange_authority.key == &state.load()?.exchange_authority)]
+    pub exchange_authority: AccountInfo<'info>,
+    #[account(mut,
+        constraint = assets_list.to_account_info().key == &state.load()?.assets_list,
+        constraint = assets_list.to_account_info().owner == program_id
+    )]
+    pub assets_list: Loader<'info, AssetsList>,
+    #[account(address = token::ID)]
+    pub token_program: AccountInfo<'info>,
+    #[account(mut,
+        constraint = usd_token.decimals == XUSD_SCALE,
+        constraint = usd_token.to_account_info().key == &assets_list.load()?.synthetics[0].asset_address
+    )]
+    pub usd_token: Account<'info, anchor_spl::token::Mint>,
+    #[account(mut,
+        constraint = &user_token_account_burn.mint == usd_token.to_account_info().key,
+        constraint = &user_token_account_burn.owner == signer.key
+    )]
+    pub user_token_account_burn: Account<'info, TokenAccount>,
     #[account(mut)]
     pub exchange_account: Loader<'info, ExchangeAccount>,
-    #[account(
+    // owner or delegate of exchange_account
+    #[account(signer)]
+    pub signer: AccountInfo<'info>,
+}
+impl<'a, 'b, 'c, 'info> From<&BurnToken<'info>> for CpiContext<'a, 'b, 'c, 'info, Burn<'info>> {
+    fn from(accounts: &BurnToken<'info>) -> CpiContext<'a, 'b, 'c, 'info, Burn<'info>> {
+        let cpi_accounts = Burn {
+            mint: accounts.usd_token.to_account_info(),
+            to: accounts.user_token_account_burn.to_account_info(),
+            authority: accounts.exchange_authority.to_account_info(),
+        };
+        let cpi_program = accounts.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    #[account(mut,
+        seeds = [b"statev1".as_ref()],
+        bump = state.load()?.bump,
+        constraint = state.to_account_info().owner == program_id
+    )]
+    pub state: Loader<'info, State>,
+    #[account(constraint = exchange_authority.key == &state.load()?.exchange_authority)]
+    pub exchange_authority: AccountInfo<'info>,
+    #[account(mut,
+        constraint = assets_list.to_account_info().key == &state.load()?.assets_list,
+        constraint = assets_list.to_account_info().owner == program_id
+    )]
+    pub assets_list: Loader<'info, AssetsList>,
+    #[account(address = token::ID)]
+    pub token_program: AccountInfo<'info>,
+    #[account(mut)]
+    pub token_in: Account<'info, anchor_spl::token::Mint>,
+    #[account(mut)]
+    pub token_for: Account<'info, anchor_spl::token::Mint>,
+    #[account(mut,
+        constraint = &user_token_account_in.mint == token_in.to_account_info().key,
+        constraint = &user_token_account_in.owner  == owner.key
+    )]
+    pub user_token_account_in: Account<'info, TokenAccount>,
+    // out token can be transfer to any account
+    #[account(mut,
+        constraint = &user_token_account_for.mint == token_for.to_account_info().key,
+    )]
+    pub user_token_account_for: Account<'info, TokenAccount>,
+    #[account(signer)]
+    pub owner: AccountInfo<'info>,
+}
+impl<'a, 'b, 'c, 'info> From<&Swap<'info>> for CpiContext<'a, 'b, 'c, 'info, Burn<'info>> {
+    fn from(accounts: &Swap<'info>) -> CpiContext<'a, 'b, 'c, 'info, Burn<'info>> {
+        let cpi_accounts = Burn {
+            mint: accounts.token_in.to_account_info(),
+            to: accounts.user_token_account_in.to_account_info(),
+            authority: accounts.exchange_authority.to_account_info(),
+        };
+        let cpi_program = accounts.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+impl<'a, 'b, 'c, 'info> From<&Swap<'info>> for CpiContext<'a, 'b, 'c, 'info, MintTo<'info>> {
+    fn from(accounts: &Swap<'info>) -> CpiContext<'a, 'b, 'c, 'info, MintTo<'info>> {
+        let cpi_accounts = MintTo {
+            mint: accounts.token_for.to_account_info(),
+            to: accounts.user_token_account_for.to_account_info(),
+            authority: accounts.exchange_authority.to_account_info(),
+        };
+        let cpi_program = accounts.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+#[derive(Accounts)]
+pub struct CheckCollateralization<'info> {
+    #[account(mut,
+        seeds = [b"statev1".as_ref()],
+        bump = state.load()?.bump,
+        constraint = state.to_account_info().owner == program_id
+    )]
+    pub state: Loader<'info, State>,
+    #[account(mut)]
+    pub exchange_account: Loader<'info, ExchangeAccount>,
+    #[account(mut,
+        constraint = assets_list.to_account_info().key == &state.load()?.assets_list,
+        constraint = assets_list.to_account_info().owner == program_id
+    )]
+    pub assets_list: Loader<'info, AssetsList>,
+}
+// Optional remaining accounts of check_account_collateralization paying keeper bounty
+#[derive(Accounts)]
+pub struct KeeperBounty<'info> {
+    pub exchange_authority: AccountInfo<'info>,
+    #[account(mut,
+        constraint = usd_token.decimals == XUSD_SCALE
+    )]
+    pub usd_token: Account<'info, anchor_spl::token::Mint>,
+    #[account(mut,
+        constraint = &keeper_usd_account.mint == usd_token.to_account_info().key,
+        constraint = &keeper_usd_account.owner == signer.key
+    )]
+    pub keeper_usd_account: Account<'info, TokenAccount>,
+    #[account(address = token::ID)]
+    pub token_program: AccountInfo<'info>,
+    #[account(signer)]
+    pub signer: AccountInfo<'info>,
+}
+impl<'a, 'b, 'c, 'info> From<&KeeperBounty<'info>>
+    for CpiContext<'a, 'b, 'c, 'info, MintTo<'info>>
+{
+    fn from(accounts: &KeeperBounty<'info>) -> CpiContext<'a, 'b, 'c, 'info, MintTo<'info>> {
+        let cpi_accounts = MintTo {
+            mint: accounts.usd_token.to_account_info(),
+            to: accounts.keeper_usd_account.to_account_info(),
+            authority: accounts.exchange_authority.to_account_info(),
+        };
+        let cpi_program = accounts.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+#[derive(Accounts)]
+pub struct WriteOffBadDebt<'info> {
+    #[account(mut,
+        seeds = [b"statev1".as_ref()],
+        bump = state.load()?.bump,
+        constraint = state.to_account_info().owner == program_id
+    )]
+    pub state: Loader<'info, State>,
+    // everyone can write off insolvent exchange_account
+    #[account(mut)]
+    pub exchange_account: Loader<'info, ExchangeAccount>,
+    #[account(mut,
         constraint = assets_list.to_account_info().key == &state.load()?.assets_list,
         constraint = assets_list.to_account_info().owner == program_id
     )]
@@ -808,10 +1173,11 @@ pub struct WithdrawRewards<'info> {
         constraint = state.to_account_info().owner == program_id
     )]
     pub state: Loader<'info, State>,
-    #[account(mut, has_one = owner)]
+    #[account(mut)]
     pub exchange_account: Loader<'info, ExchangeAccount>,
+    // owner or delegate of exchange_account
     #[account(signer)]
-    pub owner: AccountInfo<'info>,
+    pub signer: AccountInfo<'info>,
     #[account(constraint = exchange_authority.key == &state.load()?.exchange_authority)]
     pub exchange_authority: AccountInfo<'info>,
     #[account(address = token::ID)]
@@ -829,6 +1195,69 @@ pub struct WithdrawRewards<'info> {
     pub staking_fund_account: Account<'info, TokenAccount>,
 }
 #[derive(Accounts)]
+pub struct CompoundRewards<'info> {
+    #[account(mut,
+        seeds = [b"statev1".as_ref()],
+        bump = state.load()?.bump,
+        constraint = state.to_account_info().owner == program_id
+    )]
+    pub state: Loader<'info, State>,
+    #[account(mut)]
+    pub exchange_account: Loader<'info, ExchangeAccount>,
+    #[account(mut,
+        constraint = assets_list.to_account_info().key == &state.load()?.assets_list,
+        constraint = assets_list.to_account_info().owner == program_id
+    )]
+    pub assets_list: Loader<'info, AssetsList>,
+    #[account(mut,
+        constraint = &reserve_address.owner == exchange_authority.key
+    )]
+    pub reserve_address: Account<'info, TokenAccount>,
+    #[account(mut,
+        constraint = &staking_fund_account.owner == exchange_authority.key,
+        constraint = staking_fund_account.to_account_info().key == &state.load()?.staking.fund_account
+    )]
+    pub staking_fund_account: Account<'info, TokenAccount>,
+    // owner or delegate of exchange_account
+    #[account(signer)]
+    pub signer: AccountInfo<'info>,
+    #[account(constraint = exchange_authority.key == &state.load()?.exchange_authority)]
+    pub exchange_authority: AccountInfo<'info>,
+    #[account(address = token::ID)]
+    pub token_program: AccountInfo<'info>,
+}
+impl<'a, 'b, 'c, 'info> From<&CompoundRewards<'info>>
+    for CpiContext<'a, 'b, 'c, 'info, Transfer<'info>>
+{
+    fn from(accounts: &CompoundRewards<'info>) -> CpiContext<'a, 'b, 'c, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: accounts.staking_fund_account.to_account_info(),
+            to: accounts.reserve_address.to_account_info(),
+            authority: accounts.exchange_authority.to_account_info(),
+        };
+        let cpi_program = accounts.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+#[derive(Accounts)]
+pub struct AddRewardStream<'info> {
+    #[account(mut,
+        seeds = [b"statev1".as_ref()],
+        bump = state.load()?.bump,
+        constraint = state.to_account_info().owner == program_id
+    )]
+    pub state: Loader<'info, State>,
+    #[account(signer)]
+    pub admin: AccountInfo<'info>,
+    pub mint: Account<'info, anchor_spl::token::Mint>,
+    #[account(
+        constraint = fund_account.owner == state.load()?.exchange_authority,
+        constraint = &fund_account.mint == mint.to_account_info().key,
+        constraint = fund_account.to_account_info().key != &state.load()?.staking.fund_account
+    )]
+    pub fund_account: Account<'info, TokenAccount>,
+}
+#[derive(Accounts)]
 pub struct WithdrawLiquidationPenalty<'info> {
     #[account(mut,
         seeds = [b"statev1".as_ref()],
@@ -1183,9 +1612,8 @@ impl<'a, 'b, 'c, 'info> From<&BorrowVault<'info>> for CpiContext<'a, 'b, 'c, 'in
         CpiContext::new(cpi_program, cpi_accounts)
     }
 }
-
 #[derive(Accounts)]
-pub struct WithdrawVault<'info> {
+pub struct LeverageVault<'info> {
     #[account(
         seeds = [b"statev1".as_ref()],
         bump = state.load()?.bump,
@@ -1202,47 +1630,51 @@ pub struct WithdrawVault<'info> {
         constraint = vault.to_account_info().owner == program_id
     )]
     pub vault: Loader<'info, Vault>,
+    #[account(mut,
+        seeds = [b"swaplinev1", synthetic.to_account_info().key.as_ref(),collateral.to_account_info().key.as_ref()], bump = swapline.load()?.bump,
+        constraint = swapline.to_account_info().owner == program_id
+    )]
+    pub swapline: Loader<'info, Swapline>,
     pub synthetic: Account<'info, anchor_spl::token::Mint>,
     pub collateral: Account<'info, anchor_spl::token::Mint>,
     #[account(constraint = vault.load()?.collateral_price_feed == collateral_price_feed.key(),)]
     pub collateral_price_feed: AccountInfo<'info>,
-    #[account(mut, 
-        constraint = &vault.load()?.collateral_reserve == reserve_address.to_account_info().key,
-        constraint = &reserve_address.owner == exchange_authority.key,
-        constraint = &reserve_address.mint == collateral.to_account_info().key,
-    )]
-    pub reserve_address: Account<'info, TokenAccount>,
-    #[account(mut,
-        constraint = &user_collateral_account.mint == collateral.to_account_info().key,
-        constraint = user_collateral_account.to_account_info().key != reserve_address.to_account_info().key
-    )]
-    pub user_collateral_account: Account<'info, TokenAccount>, // can withdraw to any accounts except reserve_address
-    #[account(address = token::ID)]
-    pub token_program: AccountInfo<'info>,
     #[account(mut,
         constraint = assets_list.to_account_info().key == &state.load()?.assets_list,
         constraint = assets_list.to_account_info().owner == program_id
     )]
     pub assets_list: Loader<'info, AssetsList>,
-    #[account(mut, signer)]
+    #[account(mut,
+        constraint = vault_collateral_reserve.to_account_info().key == &vault.load()?.collateral_reserve
+    )]
+    pub vault_collateral_reserve: Account<'info, TokenAccount>,
+    #[account(mut,
+        constraint = swapline_collateral_reserve.to_account_info().key == &swapline.load()?.collateral_reserve
+    )]
+    pub swapline_collateral_reserve: Account<'info, TokenAccount>,
+    #[account(address = token::ID)]
+    pub token_program: AccountInfo<'info>,
+    #[account(signer)]
     pub owner: AccountInfo<'info>,
+    #[account(constraint = exchange_authority.key == &state.load()?.exchange_authority)]
     pub exchange_authority: AccountInfo<'info>,
 }
-impl<'a, 'b, 'c, 'info> From<&WithdrawVault<'info>>
+impl<'a, 'b, 'c, 'info> From<&LeverageVault<'info>>
     for CpiContext<'a, 'b, 'c, 'info, Transfer<'info>>
 {
-    fn from(accounts: &WithdrawVault<'info>) -> CpiContext<'a, 'b, 'c, 'info, Transfer<'info>> {
+    fn from(accounts: &LeverageVault<'info>) -> CpiContext<'a, 'b, 'c, 'info, Transfer<'info>> {
         let cpi_accounts = Transfer {
-            from: accounts.reserve_address.to_account_info(),
-            to: accounts.user_collateral_account.to_account_info(),
+            from: accounts.swapline_collateral_reserve.to_account_info(),
+            to: accounts.vault_collateral_reserve.to_account_info(),
             authority: accounts.exchange_authority.to_account_info(),
         };
         let cpi_program = accounts.token_program.to_account_info();
         CpiContext::new(cpi_program, cpi_accounts)
     }
 }
+
 #[derive(Accounts)]
-pub struct RepayVault<'info> {
+pub struct WithdrawVault<'info> {
     #[account(
         seeds = [b"statev1".as_ref()],
         bump = state.load()?.bump,
@@ -1259,18 +1691,75 @@ pub struct RepayVault<'info> {
         constraint = vault.to_account_info().owner == program_id
     )]
     pub vault: Loader<'info, Vault>,
-    #[account(mut)]
     pub synthetic: Account<'info, anchor_spl::token::Mint>,
     pub collateral: Account<'info, anchor_spl::token::Mint>,
-    #[account(mut,
-        constraint = assets_list.to_account_info().key == &state.load()?.assets_list,
-        constraint = assets_list.to_account_info().owner == program_id
-    )]
-    pub assets_list: Loader<'info, AssetsList>,
+    #[account(constraint = vault.load()?.collateral_price_feed == collateral_price_feed.key(),)]
+    pub collateral_price_feed: AccountInfo<'info>,
     #[account(mut, 
-        constraint = &user_token_account_repay.owner == owner.key,
-        constraint = &user_token_account_repay.mint == synthetic.to_account_info().key,
-    )]
+        constraint = &vault.load()?.collateral_reserve == reserve_address.to_account_info().key,
+        constraint = &reserve_address.owner == exchange_authority.key,
+        constraint = &reserve_address.mint == collateral.to_account_info().key,
+    )]
+    pub reserve_address: Account<'info, TokenAccount>,
+    #[account(mut,
+        constraint = &user_collateral_account.mint == collateral.to_account_info().key,
+        constraint = user_collateral_account.to_account_info().key != reserve_address.to_account_info().key
+    )]
+    pub user_collateral_account: Account<'info, TokenAccount>, // can withdraw to any accounts except reserve_address
+    #[account(address = token::ID)]
+    pub token_program: AccountInfo<'info>,
+    #[account(mut,
+        constraint = assets_list.to_account_info().key == &state.load()?.assets_list,
+        constraint = assets_list.to_account_info().owner == program_id
+    )]
+    pub assets_list: Loader<'info, AssetsList>,
+    #[account(mut, signer)]
+    pub owner: AccountInfo<'info>,
+    pub exchange_authority: AccountInfo<'info>,
+}
+impl<'a, 'b, 'c, 'info> From<&WithdrawVault<'info>>
+    for CpiContext<'a, 'b, 'c, 'info, Transfer<'info>>
+{
+    fn from(accounts: &WithdrawVault<'info>) -> CpiContext<'a, 'b, 'c, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: accounts.reserve_address.to_account_info(),
+            to: accounts.user_collateral_account.to_account_info(),
+            authority: accounts.exchange_authority.to_account_info(),
+        };
+        let cpi_program = accounts.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+#[derive(Accounts)]
+pub struct RepayVault<'info> {
+    #[account(
+        seeds = [b"statev1".as_ref()],
+        bump = state.load()?.bump,
+        constraint = state.to_account_info().owner == program_id
+    )]
+    pub state: Loader<'info, State>,
+    #[account(mut,
+        seeds = [b"vault_entryv1", owner.key.as_ref(), vault.to_account_info().key.as_ref()],bump=vault_entry.load()?.bump,
+        constraint = vault_entry.to_account_info().owner == program_id
+    )]
+    pub vault_entry: Loader<'info, VaultEntry>,
+    #[account(mut,
+        seeds = [b"vaultv1", synthetic.to_account_info().key.as_ref(), collateral.to_account_info().key.as_ref(), &vault.load()?.vault_type.to_le_bytes()],bump=vault.load()?.bump,
+        constraint = vault.to_account_info().owner == program_id
+    )]
+    pub vault: Loader<'info, Vault>,
+    #[account(mut)]
+    pub synthetic: Account<'info, anchor_spl::token::Mint>,
+    pub collateral: Account<'info, anchor_spl::token::Mint>,
+    #[account(mut,
+        constraint = assets_list.to_account_info().key == &state.load()?.assets_list,
+        constraint = assets_list.to_account_info().owner == program_id
+    )]
+    pub assets_list: Loader<'info, AssetsList>,
+    #[account(mut, 
+        constraint = &user_token_account_repay.owner == owner.key,
+        constraint = &user_token_account_repay.mint == synthetic.to_account_info().key,
+    )]
     pub user_token_account_repay: Account<'info, TokenAccount>,
     #[account(address = token::ID)]
     pub token_program: AccountInfo<'info>,
@@ -1352,6 +1841,227 @@ pub struct LiquidateVault<'info> {
     #[account(constraint = exchange_authority.key == &state.load()?.exchange_authority)]
     pub exchange_authority: AccountInfo<'info>,
 }
+#[derive(Accounts)]
+pub struct PreviewVaultLiquidation<'info> {
+    #[account(
+        seeds = [b"statev1".as_ref()],
+        bump = state.load()?.bump,
+        constraint = state.to_account_info().owner == program_id
+    )]
+    pub state: Loader<'info, State>,
+    #[account(
+        has_one = owner,
+        seeds = [b"vault_entryv1", owner.key.as_ref(), vault.to_account_info().key.as_ref()],bump=vault_entry.load()?.bump,
+        constraint = vault_entry.to_account_info().owner == program_id
+    )]
+    pub vault_entry: Loader<'info, VaultEntry>,
+    #[account(
+        seeds = [b"vaultv1", synthetic.to_account_info().key.as_ref(), collateral.to_account_info().key.as_ref(), &vault.load()?.vault_type.to_le_bytes()],bump=vault.load()?.bump ,
+        constraint = vault.to_account_info().owner == program_id
+    )]
+    pub vault: Loader<'info, Vault>,
+    pub synthetic: Account<'info, anchor_spl::token::Mint>,
+    pub collateral: Account<'info, anchor_spl::token::Mint>,
+    #[account(constraint = vault.load()?.collateral_price_feed == collateral_price_feed.key(),)]
+    pub collateral_price_feed: AccountInfo<'info>,
+    #[account(
+        constraint = assets_list.to_account_info().key == &state.load()?.assets_list,
+        constraint = assets_list.to_account_info().owner == program_id
+    )]
+    pub assets_list: Loader<'info, AssetsList>,
+    pub owner: AccountInfo<'info>,
+}
+#[derive(Accounts)]
+pub struct AddVaultCollateral<'info> {
+    #[account(
+        seeds = [b"statev1".as_ref()],
+        bump = state.load()?.bump,
+        constraint = state.to_account_info().owner == program_id
+    )]
+    pub state: Loader<'info, State>,
+    #[account(signer)]
+    pub admin: AccountInfo<'info>,
+    #[account(mut,
+        constraint = vault.to_account_info().owner == program_id
+    )]
+    pub vault: Loader<'info, Vault>,
+    pub collateral: Account<'info, anchor_spl::token::Mint>,
+    #[account(
+        constraint = &collateral_reserve.mint == collateral.to_account_info().key,
+        constraint = collateral_reserve.owner == state.load()?.exchange_authority
+    )]
+    pub collateral_reserve: Account<'info, TokenAccount>,
+    #[account(
+        constraint = liquidation_fund.owner == state.load()?.exchange_authority,
+        constraint = liquidation_fund.mint == collateral.key(),
+        constraint = liquidation_fund.key() != collateral_reserve.key()
+    )]
+    pub liquidation_fund: Account<'info, TokenAccount>,
+    pub collateral_price_feed: AccountInfo<'info>,
+}
+// Basket collaterals are selected by index, their accounts are validated in instruction
+#[derive(Accounts)]
+pub struct DepositVaultCollateral<'info> {
+    #[account(
+        seeds = [b"statev1".as_ref()],
+        bump = state.load()?.bump,
+        constraint = state.to_account_info().owner == program_id
+    )]
+    pub state: Loader<'info, State>,
+    #[account(mut,
+        seeds = [b"vault_entryv1", owner.key.as_ref(), vault.to_account_info().key.as_ref()],bump=vault_entry.load()?.bump,
+        constraint = vault_entry.to_account_info().owner == program_id
+    )]
+    pub vault_entry: Loader<'info, VaultEntry>,
+    #[account(mut,
+        constraint = vault.to_account_info().owner == program_id
+    )]
+    pub vault: Loader<'info, Vault>,
+    #[account(mut,
+        constraint = &reserve_address.owner == exchange_authority.key
+    )]
+    pub reserve_address: Account<'info, TokenAccount>,
+    #[account(mut,
+        constraint = &user_collateral_account.owner == owner.key,
+        constraint = user_collateral_account.to_account_info().key != reserve_address.to_account_info().key
+    )]
+    pub user_collateral_account: Account<'info, TokenAccount>,
+    #[account(address = token::ID)]
+    pub token_program: AccountInfo<'info>,
+    #[account(mut, signer)]
+    pub owner: AccountInfo<'info>,
+    #[account(constraint = exchange_authority.key == &state.load()?.exchange_authority)]
+    pub exchange_authority: AccountInfo<'info>,
+}
+impl<'a, 'b, 'c, 'info> From<&DepositVaultCollateral<'info>>
+    for CpiContext<'a, 'b, 'c, 'info, Transfer<'info>>
+{
+    fn from(
+        accounts: &DepositVaultCollateral<'info>,
+    ) -> CpiContext<'a, 'b, 'c, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: accounts.user_collateral_account.to_account_info(),
+            to: accounts.reserve_address.to_account_info(),
+            authority: accounts.exchange_authority.to_account_info(),
+        };
+        let cpi_program = accounts.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+#[derive(Accounts)]
+pub struct WithdrawVaultCollateral<'info> {
+    #[account(
+        seeds = [b"statev1".as_ref()],
+        bump = state.load()?.bump,
+        constraint = state.to_account_info().owner == program_id
+    )]
+    pub state: Loader<'info, State>,
+    #[account(mut,
+        seeds = [b"vault_entryv1", owner.key.as_ref(), vault.to_account_info().key.as_ref()],bump=vault_entry.load()?.bump,
+        constraint = vault_entry.to_account_info().owner == program_id
+    )]
+    pub vault_entry: Loader<'info, VaultEntry>,
+    #[account(mut,
+        constraint = vault.to_account_info().owner == program_id
+    )]
+    pub vault: Loader<'info, Vault>,
+    #[account(constraint = synthetic.key() == vault.load()?.synthetic)]
+    pub synthetic: Account<'info, anchor_spl::token::Mint>,
+    #[account(constraint = vault.load()?.collateral_price_feed == collateral_price_feed.key())]
+    pub collateral_price_feed: AccountInfo<'info>,
+    #[account(mut,
+        constraint = &reserve_address.owner == exchange_authority.key
+    )]
+    pub reserve_address: Account<'info, TokenAccount>,
+    #[account(mut,
+        constraint = user_collateral_account.to_account_info().key != reserve_address.to_account_info().key
+    )]
+    pub user_collateral_account: Account<'info, TokenAccount>, // can withdraw to any accounts except reserve_address
+    #[account(address = token::ID)]
+    pub token_program: AccountInfo<'info>,
+    #[account(mut,
+        constraint = assets_list.to_account_info().key == &state.load()?.assets_list,
+        constraint = assets_list.to_account_info().owner == program_id
+    )]
+    pub assets_list: Loader<'info, AssetsList>,
+    #[account(mut, signer)]
+    pub owner: AccountInfo<'info>,
+    #[account(constraint = exchange_authority.key == &state.load()?.exchange_authority)]
+    pub exchange_authority: AccountInfo<'info>,
+}
+impl<'a, 'b, 'c, 'info> From<&WithdrawVaultCollateral<'info>>
+    for CpiContext<'a, 'b, 'c, 'info, Transfer<'info>>
+{
+    fn from(
+        accounts: &WithdrawVaultCollateral<'info>,
+    ) -> CpiContext<'a, 'b, 'c, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: accounts.reserve_address.to_account_info(),
+            to: accounts.user_collateral_account.to_account_info(),
+            authority: accounts.exchange_authority.to_account_info(),
+        };
+        let cpi_program = accounts.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+#[derive(Accounts)]
+pub struct LiquidateVaultCollateral<'info> {
+    #[account(
+        seeds = [b"statev1".as_ref()],
+        bump = state.load()?.bump,
+        constraint = state.to_account_info().owner == program_id
+    )]
+    pub state: Loader<'info, State>,
+    #[account(mut,
+        has_one = owner,
+        seeds = [b"vault_entryv1", owner.key.as_ref(), vault.to_account_info().key.as_ref()],bump=vault_entry.load()?.bump,
+        constraint = vault_entry.to_account_info().owner == program_id
+    )]
+    pub vault_entry: Loader<'info, VaultEntry>,
+    #[account(mut,
+        constraint = vault.to_account_info().owner == program_id
+    )]
+    pub vault: Loader<'info, Vault>,
+    #[account(mut,
+        constraint = synthetic.key() == vault.load()?.synthetic
+    )]
+    pub synthetic: Account<'info, anchor_spl::token::Mint>,
+    #[account(constraint = vault.load()?.collateral_price_feed == collateral_price_feed.key())]
+    pub collateral_price_feed: AccountInfo<'info>,
+    #[account(mut,
+        constraint = assets_list.to_account_info().key == &state.load()?.assets_list,
+        constraint = assets_list.to_account_info().owner == program_id
+    )]
+    pub assets_list: Loader<'info, AssetsList>,
+    #[account(mut,
+        constraint = &collateral_reserve.owner == exchange_authority.key
+    )]
+    pub collateral_reserve: Account<'info, TokenAccount>,
+    #[account(mut,
+        constraint = &liquidator_synthetic_account.mint == synthetic.to_account_info().key,
+        constraint = &liquidator_synthetic_account.owner == liquidator.key
+    )]
+    pub liquidator_synthetic_account: Account<'info, TokenAccount>,
+    #[account(mut,
+        constraint = &liquidator_collateral_account.owner == liquidator.key,
+        constraint = liquidator_collateral_account.mint == collateral_reserve.mint,
+        constraint = liquidator_collateral_account.to_account_info().key != liquidation_fund.to_account_info().key,
+        constraint = liquidator_collateral_account.to_account_info().key != collateral_reserve.to_account_info().key,
+    )]
+    pub liquidator_collateral_account: Box<Account<'info, TokenAccount>>,
+    #[account(mut,
+        constraint = liquidation_fund.owner == state.load()?.exchange_authority,
+        constraint = liquidation_fund.to_account_info().key != collateral_reserve.to_account_info().key
+    )]
+    pub liquidation_fund: Box<Account<'info, TokenAccount>>,
+    #[account(address = token::ID)]
+    pub token_program: AccountInfo<'info>,
+    pub owner: AccountInfo<'info>,
+    #[account(signer)]
+    pub liquidator: AccountInfo<'info>,
+    #[account(constraint = exchange_authority.key == &state.load()?.exchange_authority)]
+    pub exchange_authority: AccountInfo<'info>,
+}
 
 #[derive(Accounts)]
 pub struct TriggerVaultEntryDebtAdjustment<'info> {
@@ -1383,6 +2093,146 @@ pub struct TriggerVaultEntryDebtAdjustment<'info> {
     pub assets_list: Loader<'info, AssetsList>,
     pub owner: AccountInfo<'info>,
 }
+#[derive(Accounts)]
+pub struct SetVaultEntryDeleverage<'info> {
+    #[account(
+        seeds = [b"statev1".as_ref()],
+        bump = state.load()?.bump,
+        constraint = state.to_account_info().owner == program_id
+    )]
+    pub state: Loader<'info, State>,
+    #[account(mut,
+        seeds = [b"vault_entryv1", owner.key.as_ref(), vault.to_account_info().key.as_ref()],bump=vault_entry.load()?.bump,
+        constraint = vault_entry.to_account_info().owner == program_id
+    )]
+    pub vault_entry: Loader<'info, VaultEntry>,
+    #[account(constraint = vault.to_account_info().owner == program_id)]
+    pub vault: Loader<'info, Vault>,
+    #[account(signer)]
+    pub owner: AccountInfo<'info>,
+}
+#[derive(Accounts)]
+pub struct DeleverageVault<'info> {
+    #[account(
+        seeds = [b"statev1".as_ref()],
+        bump = state.load()?.bump,
+        constraint = state.to_account_info().owner == program_id
+    )]
+    pub state: Loader<'info, State>,
+    #[account(mut,
+        has_one = owner,
+        seeds = [b"vault_entryv1", owner.key.as_ref(), vault.to_account_info().key.as_ref()],bump=vault_entry.load()?.bump,
+        constraint = vault_entry.to_account_info().owner == program_id
+    )]
+    pub vault_entry: Loader<'info, VaultEntry>,
+    #[account(mut,
+        seeds = [b"vaultv1", synthetic.to_account_info().key.as_ref(), collateral.to_account_info().key.as_ref(), &vault.load()?.vault_type.to_le_bytes()],bump=vault.load()?.bump,
+        constraint = vault.to_account_info().owner == program_id
+    )]
+    pub vault: Loader<'info, Vault>,
+    #[account(mut,
+        seeds = [b"swaplinev1", synthetic.to_account_info().key.as_ref(),collateral.to_account_info().key.as_ref()], bump = swapline.load()?.bump,
+        constraint = swapline.to_account_info().owner == program_id
+    )]
+    pub swapline: Loader<'info, Swapline>,
+    pub synthetic: Account<'info, anchor_spl::token::Mint>,
+    pub collateral: Account<'info, anchor_spl::token::Mint>,
+    #[account(constraint = vault.load()?.collateral_price_feed == collateral_price_feed.key(),)]
+    pub collateral_price_feed: AccountInfo<'info>,
+    #[account(mut,
+        constraint = assets_list.to_account_info().key == &state.load()?.assets_list,
+        constraint = assets_list.to_account_info().owner == program_id
+    )]
+    pub assets_list: Loader<'info, AssetsList>,
+    #[account(mut,
+        constraint = vault_collateral_reserve.to_account_info().key == &vault.load()?.collateral_reserve
+    )]
+    pub vault_collateral_reserve: Account<'info, TokenAccount>,
+    #[account(mut,
+        constraint = swapline_collateral_reserve.to_account_info().key == &swapline.load()?.collateral_reserve
+    )]
+    pub swapline_collateral_reserve: Account<'info, TokenAccount>,
+    #[account(mut,
+        constraint = keeper_collateral_account.mint == collateral.key(),
+        constraint = &keeper_collateral_account.owner == keeper.key,
+        constraint = keeper_collateral_account.to_account_info().key != vault_collateral_reserve.to_account_info().key
+    )]
+    pub keeper_collateral_account: Account<'info, TokenAccount>,
+    #[account(address = token::ID)]
+    pub token_program: AccountInfo<'info>,
+    pub owner: AccountInfo<'info>,
+    #[account(signer)]
+    pub keeper: AccountInfo<'info>,
+    #[account(constraint = exchange_authority.key == &state.load()?.exchange_authority)]
+    pub exchange_authority: AccountInfo<'info>,
+}
+#[derive(Accounts)]
+pub struct CloseVaultEntry<'info> {
+    #[account(
+        seeds = [b"statev1".as_ref()],
+        bump = state.load()?.bump,
+        constraint = state.to_account_info().owner == program_id
+    )]
+    pub state: Loader<'info, State>,
+    #[account(mut,
+        close = owner,
+        seeds = [b"vault_entryv1", owner.key.as_ref(), vault.to_account_info().key.as_ref()],bump=vault_entry.load()?.bump,
+        constraint = vault_entry.to_account_info().owner == program_id
+    )]
+    pub vault_entry: Loader<'info, VaultEntry>,
+    #[account(mut,
+        seeds = [b"vaultv1", synthetic.to_account_info().key.as_ref(), collateral.to_account_info().key.as_ref(), &vault.load()?.vault_type.to_le_bytes()],bump=vault.load()?.bump,
+        constraint = vault.to_account_info().owner == program_id
+    )]
+    pub vault: Loader<'info, Vault>,
+    pub synthetic: Account<'info, anchor_spl::token::Mint>,
+    pub collateral: Account<'info, anchor_spl::token::Mint>,
+    #[account(mut,
+        constraint = assets_list.to_account_info().key == &state.load()?.assets_list,
+        constraint = assets_list.to_account_info().owner == program_id
+    )]
+    pub assets_list: Loader<'info, AssetsList>,
+    #[account(mut, signer)]
+    pub owner: AccountInfo<'info>,
+}
+#[derive(Accounts)]
+#[instruction(bump: u8)]
+pub struct TransferVaultEntry<'info> {
+    #[account(
+        seeds = [b"statev1".as_ref()],
+        bump = state.load()?.bump,
+        constraint = state.to_account_info().owner == program_id
+    )]
+    pub state: Loader<'info, State>,
+    #[account(mut,
+        seeds = [b"vault_entryv1", owner.key.as_ref(), vault.to_account_info().key.as_ref()],bump=vault_entry.load()?.bump,
+        constraint = vault_entry.to_account_info().owner == program_id
+    )]
+    pub vault_entry: Loader<'info, VaultEntry>,
+    // created if recipient has no entry in vault yet
+    #[account(init_if_needed, seeds = [b"vault_entryv1", recipient.key.as_ref(), vault.to_account_info().key.as_ref()], bump=bump, payer=owner)]
+    pub recipient_vault_entry: Loader<'info, VaultEntry>,
+    #[account(mut,
+        seeds = [b"vaultv1", synthetic.to_account_info().key.as_ref(), collateral.to_account_info().key.as_ref(), &vault.load()?.vault_type.to_le_bytes()],bump=vault.load()?.bump,
+        constraint = vault.to_account_info().owner == program_id
+    )]
+    pub vault: Loader<'info, Vault>,
+    pub synthetic: Account<'info, anchor_spl::token::Mint>,
+    pub collateral: Account<'info, anchor_spl::token::Mint>,
+    #[account(mut,
+        constraint = assets_list.to_account_info().key == &state.load()?.assets_list,
+        constraint = assets_list.to_account_info().owner == program_id
+    )]
+    pub assets_list: Loader<'info, AssetsList>,
+    #[account(mut, signer)]
+    pub owner: AccountInfo<'info>,
+    // recipient has to accept debt
+    #[account(signer, constraint = recipient.key != owner.key)]
+    pub recipient: AccountInfo<'info>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
 
 #[derive(Accounts)]
 pub struct SetVaultHalted<'info> {
@@ -7,6 +7,7 @@ pub const SNY_SCALE: u8 = 6;
 pub const PRICE_SCALE: u8 = 8;
 pub const UNIFIED_PERCENT_SCALE: u8 = 5;
 pub const INTEREST_RATE_SCALE: u8 = 18;
+pub const REWARD_PER_SHARE_SCALE: u8 = 18;
 
 impl Decimal {
     pub fn new(value: u128, scale: u8) -> Self {
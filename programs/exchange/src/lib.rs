@@ -2,12 +2,15 @@ pub mod account;
 pub mod context;
 pub mod decimal;
 pub mod math;
+pub mod migration;
 pub mod oracle;
 pub mod utils;
 use account::*;
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
 use anchor_spl::token::{self, Burn, MintTo, Transfer};
 use context::*;
+use migration::*;
 use pyth::pc::{Price, PriceStatus};
 use utils::*;
 
@@ -19,17 +22,22 @@ declare_id!("e9P7yZnZdYpvEWJbbbBbu8XEsYkFGuD3rMsVxMMfrf2");
 pub mod exchange {
     use std::{borrow::BorrowMut, convert::TryInto};
 
+    use anchor_lang::{Discriminator, InstructionData};
+
     use crate::math::{
         amount_to_discount, amount_to_shares_by_rounding_down, calculate_burned_shares,
-        calculate_max_debt_in_usd, calculate_max_withdraw_in_usd,
-        calculate_new_shares_by_rounding_up, calculate_swap_out_amount, calculate_swap_tax,
-        calculate_user_debt_in_usd, calculate_value_in_usd, calculate_vault_borrow_limit,
-        calculate_vault_max_borrow_based_max_debt, calculate_vault_withdraw_limit,
-        usd_to_token_amount,
+        calculate_close_factor, calculate_debt, calculate_liquidation_penalty_split,
+        calculate_liquidator_penalty, calculate_max_debt_in_usd, calculate_max_repay,
+        calculate_max_withdraw_in_usd, calculate_new_shares_by_rounding_up,
+        calculate_seized_collateral, calculate_swap_out_amount, calculate_swap_tax,
+        calculate_user_debt_in_usd, calculate_value_in_usd, calculate_vault_basket_borrow_limit,
+        calculate_vault_borrow_limit, calculate_vault_max_borrow_based_max_debt,
+        calculate_vault_seized_collateral, calculate_vault_uncovered_debt,
+        calculate_vault_withdraw_limit, usd_to_token_amount,
     };
 
     use crate::decimal::{
-        Add, Compare, DivScale, DivUp, Mul, MulUp, Sub, PRICE_SCALE, SNY_SCALE,
+        Add, Compare, DivUp, Mul, MulUp, Sub, PRICE_SCALE, REWARD_PER_SHARE_SCALE, SNY_SCALE,
         UNIFIED_PERCENT_SCALE, XUSD_SCALE,
     };
 
@@ -39,11 +47,139 @@ pub mod exchange {
         let exchange_account = &mut ctx.accounts.exchange_account.load_init()?;
         exchange_account.owner = *ctx.accounts.admin.key;
         exchange_account.debt_shares = 0;
-        exchange_account.version = 0;
+        exchange_account.version = EXCHANGE_ACCOUNT_VERSION;
+        exchange_account.bump = bump;
+        exchange_account.liquidation_deadline = u64::MAX;
+        exchange_account.user_staking_data = UserStaking::default();
+        exchange_account.user_staking_data.amount_to_claim = Decimal::from_sny(0);
+        exchange_account.last_reward_per_share = Decimal::new(0, REWARD_PER_SHARE_SCALE);
+        Ok(())
+    }
+    pub fn create_sub_account(ctx: Context<CreateSubAccount>, bump: u8, index: u16) -> Result<()> {
+        // Index 0 is reserved for account created by create_exchange_account
+        require!(index > 0, ParameterOutOfRange);
+
+        let exchange_account = &mut ctx.accounts.exchange_account.load_init()?;
+        exchange_account.owner = *ctx.accounts.admin.key;
+        exchange_account.debt_shares = 0;
+        exchange_account.version = EXCHANGE_ACCOUNT_VERSION;
         exchange_account.bump = bump;
+        exchange_account.sub_account_index = index;
         exchange_account.liquidation_deadline = u64::MAX;
         exchange_account.user_staking_data = UserStaking::default();
         exchange_account.user_staking_data.amount_to_claim = Decimal::from_sny(0);
+        exchange_account.last_reward_per_share = Decimal::new(0, REWARD_PER_SHARE_SCALE);
+        Ok(())
+    }
+    pub fn migrate_exchange_account(ctx: Context<MigrateExchangeAccount>) -> Result<()> {
+        msg!("Synthetify: MIGRATE EXCHANGE ACCOUNT");
+        let exchange_account_info = &ctx.accounts.exchange_account;
+
+        let version = read_exchange_account_version(exchange_account_info)?;
+        require!(version < EXCHANGE_ACCOUNT_VERSION, AccountAlreadyMigrated);
+        // Staking rewards are settled against migrated state
+        let state = ctx.accounts.state.load()?;
+        require!(state.version == STATE_VERSION, AccountNotMigrated);
+
+        realloc_account(
+            exchange_account_info,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            &ctx.accounts.rent,
+            exchange_account_size(EXCHANGE_ACCOUNT_VERSION),
+        )?;
+
+        let loader =
+            Loader::<'_, ExchangeAccount>::try_from(ctx.program_id, exchange_account_info)?;
+        let exchange_account = &mut loader.load_mut()?;
+        migrate_exchange_account_data(exchange_account, version, &state);
+        Ok(())
+    }
+    #[access_control(exchange_account_migrated(&ctx.accounts.exchange_account))]
+    pub fn set_delegate(ctx: Context<SetDelegate>, permissions: u8) -> Result<()> {
+        msg!("Synthetify: SET DELEGATE");
+        let exchange_account = &mut ctx.accounts.exchange_account.load_mut()?;
+
+        require!(permissions & !DELEGATE_ALL == 0, ParameterOutOfRange);
+        require!(
+            ctx.accounts.delegate.key.ne(&exchange_account.owner),
+            InvalidSigner
+        );
+
+        // Zero permissions revoke delegate
+        exchange_account.delegate = match permissions {
+            0 => Pubkey::default(),
+            _ => *ctx.accounts.delegate.key,
+        };
+        exchange_account.delegate_permissions = permissions;
+        Ok(())
+    }
+    pub fn migrate_state(ctx: Context<MigrateState>) -> Result<()> {
+        msg!("Synthetify:Admin: MIGRATE STATE");
+        let state = &mut ctx.accounts.state.load_mut()?;
+
+        // admin access control requires migrated state
+        require!(ctx.accounts.admin.key.eq(&state.admin), Unauthorized);
+        require!(state.version < STATE_VERSION, AccountAlreadyMigrated);
+
+        migrate_state_data(state, Clock::get()?.slot);
+        Ok(())
+    }
+    #[access_control(admin(&ctx.accounts.state, &ctx.accounts.admin))]
+    pub fn migrate_assets_list(ctx: Context<MigrateAssetsList>) -> Result<()> {
+        msg!("Synthetify:Admin: MIGRATE ASSETS LIST");
+        let assets_list_info = &ctx.accounts.assets_list;
+        require!(
+            assets_list_info.data_len() == ASSETS_LIST_V0_SIZE,
+            AccountAlreadyMigrated
+        );
+
+        realloc_account(
+            assets_list_info,
+            &ctx.accounts.admin,
+            &ctx.accounts.system_program,
+            &ctx.accounts.rent,
+            ASSETS_LIST_SIZE,
+        )?;
+        migrate_assets_list_data(&mut assets_list_info.try_borrow_mut_data()?);
+        Ok(())
+    }
+    #[access_control(admin(&ctx.accounts.state, &ctx.accounts.admin))]
+    pub fn migrate_vault(ctx: Context<MigrateVault>) -> Result<()> {
+        msg!("Synthetify:Admin: MIGRATE VAULT");
+        let vault_info = &ctx.accounts.vault;
+        check_legacy_layout(vault_info, Vault::discriminator(), VAULT_V0_SIZE)?;
+
+        // Appended basket collaterals are zeroed by realloc
+        realloc_account(
+            vault_info,
+            &ctx.accounts.admin,
+            &ctx.accounts.system_program,
+            &ctx.accounts.rent,
+            VAULT_SIZE,
+        )?;
+
+        let loader = Loader::<'_, Vault>::try_from(ctx.program_id, vault_info)?;
+        let vault = &mut loader.load_mut()?;
+        migrate_vault_data(vault);
+        Ok(())
+    }
+    pub fn migrate_vault_entry(ctx: Context<MigrateVaultEntry>) -> Result<()> {
+        msg!("Synthetify: MIGRATE VAULT ENTRY");
+        let vault_entry_info = &ctx.accounts.vault_entry;
+        check_legacy_layout(
+            vault_entry_info,
+            VaultEntry::discriminator(),
+            VAULT_ENTRY_V0_SIZE,
+        )?;
+
+        realloc_account(
+            vault_entry_info,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            &ctx.accounts.rent,
+            VAULT_ENTRY_SIZE,
+        )?;
         Ok(())
     }
     #[access_control(admin(&ctx.accounts.state, &ctx.accounts.admin))]
@@ -85,6 +221,9 @@ pub mod exchange {
             reserve_address: *ctx.accounts.sny_reserve.to_account_info().key,
             liquidation_fund: *ctx.accounts.sny_liquidation_fund.to_account_info().key,
             max_collateral: Decimal::from_sny(u64::MAX.into()),
+            // global liquidation penalties
+            penalty_to_liquidator: Decimal::default(),
+            penalty_to_exchange: Decimal::default(),
         };
 
         assets_list.append_asset(usd_asset);
@@ -200,6 +339,7 @@ pub mod exchange {
         let mut state = ctx.accounts.state.load_init()?;
 
         state.bump = bump;
+        state.version = STATE_VERSION;
         state.exchange_authority = *ctx.accounts.exchange_authority.key;
         state.admin = *ctx.accounts.admin.key;
         state.halted = false;
@@ -213,12 +353,19 @@ pub mod exchange {
         state.fee = Decimal::new(3, 3).to_percent(); // 0.3%
         state.swap_tax_ratio = Decimal::from_percent(20); // 20%
         state.swap_tax_reserve = Decimal::from_usd(0);
+        state.insurance_fund = Decimal::from_usd(0);
+        state.insurance_fund_ratio = Decimal::from_percent(0);
+        state.keeper_bounty = Decimal::from_usd(0);
+        state.keeper_bounty_cooldown = 0;
         state.debt_interest_rate = Decimal::from_percent(1).to_interest_rate(); //1% APR
         state.last_debt_adjustment = timestamp;
         state.penalty_to_liquidator = Decimal::from_percent(5); // 5%
         state.penalty_to_exchange = Decimal::from_percent(5); // 5%
+        state.liquidation_auction_duration = 0; // auction disabled
+        state.max_penalty_to_liquidator = Decimal::from_percent(5); // 5%
         state.accumulated_debt_interest = Decimal::from_usd(0);
         state.liquidation_rate = Decimal::from_percent(20); // 20%
+        state.full_liquidation_health = Decimal::from_percent(0); // fixed liquidation_rate
 
         state.liquidation_buffer = 2250; // about 15 minutes
         state.staking = Staking {
@@ -228,25 +375,25 @@ pub mod exchange {
                 scale: SNY_SCALE,
             },
             fund_account: *ctx.accounts.staking_fund_account.to_account_info().key,
-            finished_round: StakingRound {
-                all_points: 0,
-                amount: Decimal::from_sny(0),
-                start: 0,
-            },
-            current_round: StakingRound {
-                all_points: 0,
-                amount: Decimal::from_sny(0),
-                start: slot,
-            },
-            next_round: StakingRound {
-                all_points: 0,
-                amount: Decimal::from_sny(amount_per_round.into()),
-                start: slot.checked_add(staking_round_length.into()).unwrap(),
-            },
+            // Rounds are only kept for layout compatibility
+            ..Default::default()
         };
+        // amount_per_round is accrued evenly every slot of round_length
+        state.reward_per_share = Decimal::new(0, REWARD_PER_SHARE_SCALE);
+        state.last_reward_update = slot;
+        state.head_reward_streams = 0;
+        // SNY rewards are withdrawable immediately
+        state.rewards_vesting_duration = 0;
+        state.early_exit_penalty = Decimal::from_percent(0);
+        // Whole swap tax goes to swap_tax_reserve
+        state.swap_tax_reward_ratio = Decimal::from_percent(0);
+        state.swap_tax_reward_per_share = Decimal::new(0, REWARD_PER_SHARE_SCALE);
+        state.max_staking_boost = Decimal::from_percent(0);
+        state.boosted_debt_shares = 0;
         Ok(())
     }
-    #[access_control(halted(&ctx.accounts.state))]
+    #[access_control(halted(&ctx.accounts.state) exchange_account_migrated(&ctx.accounts.exchange_account)
+    authorized(&ctx.accounts.exchange_account, &ctx.accounts.signer, DELEGATE_DEPOSIT))]
     pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
         msg!("Synthetify: DEPOSIT");
         let state = &mut ctx.accounts.state.load_mut()?;
@@ -256,11 +403,11 @@ pub mod exchange {
 
         let slot = Clock::get()?.slot;
 
-        // Adjust staking round
-        adjust_staking_rounds(state, slot);
+        // Accrue staking rewards
+        adjust_staking_rewards(state, slot);
 
-        // adjust current staking points for exchange account
-        adjust_staking_account(exchange_account, &state.staking);
+        // move accrued staking rewards to exchange account
+        adjust_staking_account(exchange_account, state);
 
         // finding also valid reserve_address of collateral
         let collateral_index = match assets_list.collaterals.iter_mut().position(|x| {
@@ -298,6 +445,16 @@ pub mod exchange {
             }),
         }
 
+        // Deposited SNY raises staking boost, deposit does not require fresh prices
+        // so boost is left for next update when debt cannot be calculated
+        if collateral_index == 0 {
+            if let Ok(total_debt) = calculate_debt(assets_list, slot, state.max_delay, false) {
+                let user_debt =
+                    calculate_user_debt_in_usd(exchange_account, total_debt, state.debt_shares);
+                adjust_staking_boost(state, exchange_account, assets_list, user_debt);
+            }
+        }
+
         // Transfer token
         let seeds = &[SYNTHETIFY_EXCHANGE_SEED.as_bytes(), &[state.nonce]];
         let signer = &[&seeds[..]];
@@ -306,7 +463,8 @@ pub mod exchange {
         token::transfer(cpi_ctx, amount)?;
         Ok(())
     }
-    #[access_control(halted(&ctx.accounts.state))]
+    #[access_control(halted(&ctx.accounts.state) exchange_account_migrated(&ctx.accounts.exchange_account)
+    authorized(&ctx.accounts.exchange_account, &ctx.accounts.signer, DELEGATE_MINT))]
     pub fn mint(ctx: Context<Mint>, amount: u64) -> Result<()> {
         msg!("Synthetify: MINT");
         let mut state = &mut ctx.accounts.state.load_mut()?;
@@ -314,12 +472,19 @@ pub mod exchange {
         let slot = Clock::get()?.slot;
         let timestamp = Clock::get()?.unix_timestamp;
 
-        // Adjust staking round
-        adjust_staking_rounds(&mut state, slot);
+        // Accrue staking rewards
+        adjust_staking_rewards(&mut state, slot);
 
         let exchange_account = &mut ctx.accounts.exchange_account.load_mut()?;
-        // adjust current staking points for exchange account
-        adjust_staking_account(exchange_account, &state.staking);
+        // move accrued staking rewards to exchange account
+        adjust_staking_account(exchange_account, state);
+
+        // Delegate can mint only to accounts controlled by owner
+        require!(
+            ctx.accounts.signer.key.eq(&exchange_account.owner)
+                || ctx.accounts.to.owner == exchange_account.owner,
+            Unauthorized
+        );
 
         let assets_list = &mut ctx.accounts.assets_list.load_mut()?;
 
@@ -356,13 +521,13 @@ pub mod exchange {
             .debt_shares
             .checked_add(new_shares)
             .unwrap();
-        // Change points for next staking round
-        exchange_account.user_staking_data.next_round_points = exchange_account.debt_shares;
-        state.staking.next_round.all_points = state.debt_shares;
 
         let new_supply = xusd_synthetic.supply.add(amount).unwrap();
         xusd_synthetic.set_supply_safely(new_supply)?;
 
+        // Higher debt lowers staking boost
+        adjust_staking_boost(state, exchange_account, assets_list, debt_after_mint);
+
         let seeds = &[SYNTHETIFY_EXCHANGE_SEED.as_bytes(), &[state.nonce]];
         let signer = &[&seeds[..]];
         // Mint xUSD to user
@@ -370,22 +535,30 @@ pub mod exchange {
         token::mint_to(mint_cpi_ctx, amount.to_u64())?;
         Ok(())
     }
-    #[access_control(halted(&ctx.accounts.state))]
+    #[access_control(halted(&ctx.accounts.state) exchange_account_migrated(&ctx.accounts.exchange_account)
+    authorized(&ctx.accounts.exchange_account, &ctx.accounts.signer, DELEGATE_WITHDRAW))]
     pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
         msg!("Synthetify: WITHDRAW");
         let mut state = &mut ctx.accounts.state.load_mut()?;
         let slot = Clock::get()?.slot;
         let timestamp = Clock::get()?.unix_timestamp;
 
-        // Adjust staking round
-        adjust_staking_rounds(&mut state, slot);
+        // Accrue staking rewards
+        adjust_staking_rewards(&mut state, slot);
 
-        // adjust current staking points for exchange account
+        // move accrued staking rewards to exchange account
         let exchange_account = &mut ctx.accounts.exchange_account.load_mut()?;
-        adjust_staking_account(exchange_account, &state.staking);
+        adjust_staking_account(exchange_account, state);
 
         let user_collateral_account = &mut ctx.accounts.user_collateral_account;
 
+        // Delegate can withdraw only to accounts controlled by owner
+        require!(
+            ctx.accounts.signer.key.eq(&exchange_account.owner)
+                || user_collateral_account.owner == exchange_account.owner,
+            Unauthorized
+        );
+
         // Calculate debt
         let assets_list = &mut ctx.accounts.assets_list.load_mut()?;
         // calculate debt also validate if oracles are up-to-date
@@ -474,6 +647,9 @@ pub mod exchange {
         // Update reserve balance in AssetList
         collateral.reserve_balance = collateral.reserve_balance.sub(amount_to_withdraw).unwrap(); // should never fail
 
+        // Withdrawn SNY lowers staking boost
+        adjust_staking_boost(state, exchange_account, assets_list, user_debt);
+
         // Send withdrawn collateral to user
         let seeds = &[SYNTHETIFY_EXCHANGE_SEED.as_bytes(), &[state.nonce]];
         let signer = &[&seeds[..]];
@@ -481,14 +657,154 @@ pub mod exchange {
         token::transfer(cpi_ctx, amount_to_withdraw.val.try_into().unwrap())?;
         Ok(())
     }
+    #[access_control(halted(&ctx.accounts.state) exchange_account_migrated(&ctx.accounts.exchange_account)
+    exchange_account_migrated(&ctx.accounts.to_exchange_account))]
+    pub fn transfer_collateral(
+        ctx: Context<TransferCollateral>,
+        collateral_address: Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+        msg!("Synthetify: TRANSFER COLLATERAL");
+        let state = &mut ctx.accounts.state.load_mut()?;
+        let slot = Clock::get()?.slot;
+        let timestamp = Clock::get()?.unix_timestamp;
+
+        // Accrue staking rewards
+        adjust_staking_rewards(state, slot);
+
+        // move accrued staking rewards to both exchange accounts
+        let exchange_account = &mut ctx.accounts.exchange_account.load_mut()?;
+        let to_exchange_account = &mut ctx.accounts.to_exchange_account.load_mut()?;
+        adjust_staking_account(exchange_account, state);
+        adjust_staking_account(to_exchange_account, state);
+
+        // Calculate debt
+        let assets_list = &mut ctx.accounts.assets_list.load_mut()?;
+        // calculate debt also validate if oracles are up-to-date
+        let total_debt =
+            calculate_debt_with_adjustment(state, assets_list, slot, timestamp).unwrap();
+        let user_debt = calculate_user_debt_in_usd(exchange_account, total_debt, state.debt_shares);
+        let max_debt = calculate_max_debt_in_usd(exchange_account, assets_list);
+
+        let max_borrow = max_debt.mul(state.health_factor);
+
+        let (assets, collaterals, _) = assets_list.split_borrow();
+        let (collateral_index, collateral) = match collaterals
+            .iter()
+            .enumerate()
+            .find(|(_, x)| x.collateral_address.eq(&collateral_address))
+        {
+            Some(v) => v,
+            None => return Err(ErrorCode::NoAssetFound.into()),
+        };
+
+        let (entry_index, exchange_account_collateral) = match exchange_account
+            .collaterals
+            .iter_mut()
+            .enumerate()
+            .find(|(_, x)| x.collateral_address.eq(&collateral_address))
+        {
+            Some(v) => v,
+            None => return Err(ErrorCode::NoAssetFound.into()),
+        };
+
+        // Source account has to stay collateralized as after withdraw
+        let max_withdrawable_in_usd = calculate_max_withdraw_in_usd(
+            max_borrow,
+            user_debt,
+            collateral.collateral_ratio,
+            state.health_factor,
+        );
+        let amount_to_transfer = Decimal {
+            val: amount.into(),
+            scale: collateral.reserve_balance.scale,
+        };
+        let amount_to_transfer_in_usd = calculate_value_in_usd(
+            assets[collateral.asset_index as usize].price,
+            amount_to_transfer,
+        );
+        if max_withdrawable_in_usd.lt(amount_to_transfer_in_usd)? {
+            return Err(ErrorCode::WithdrawLimit.into());
+        }
+
+        exchange_account_collateral.amount =
+            match exchange_account_collateral.amount.checked_sub(amount) {
+                Some(v) => v,
+                None => return Err(ErrorCode::WithdrawLimit.into()),
+            };
+        if exchange_account_collateral.amount == 0 {
+            exchange_account.remove(entry_index);
+        }
+
+        // Collateral stays in reserve, only ownership between accounts changes
+        let to_exchange_account_collateral = to_exchange_account
+            .collaterals
+            .iter_mut()
+            .find(|x| x.collateral_address.eq(&collateral_address));
+
+        match to_exchange_account_collateral {
+            Some(entry) => entry.amount = entry.amount.checked_add(amount).unwrap(),
+            None => to_exchange_account.append(CollateralEntry {
+                amount,
+                collateral_address,
+                index: collateral_index.try_into().unwrap(),
+            }),
+        }
+
+        // Transferred SNY lowers staking boost of source and raises boost of destination
+        adjust_staking_boost(state, exchange_account, assets_list, user_debt);
+        let to_user_debt =
+            calculate_user_debt_in_usd(to_exchange_account, total_debt, state.debt_shares);
+        adjust_staking_boost(state, to_exchange_account, assets_list, to_user_debt);
+        Ok(())
+    }
+    #[access_control(halted(&ctx.accounts.state) exchange_account_migrated(&ctx.accounts.exchange_account)
+    exchange_account_migrated(&ctx.accounts.to_exchange_account))]
+    pub fn transfer_position(ctx: Context<TransferPosition>) -> Result<()> {
+        msg!("Synthetify: TRANSFER POSITION");
+        let state = &mut ctx.accounts.state.load_mut()?;
+        let slot = Clock::get()?.slot;
+        let timestamp = Clock::get()?.unix_timestamp;
+
+        // Accrue staking rewards
+        adjust_staking_rewards(state, slot);
+
+        // move accrued staking rewards to both exchange accounts
+        let exchange_account = &mut ctx.accounts.exchange_account.load_mut()?;
+        let to_exchange_account = &mut ctx.accounts.to_exchange_account.load_mut()?;
+        adjust_staking_account(exchange_account, state);
+        adjust_staking_account(to_exchange_account, state);
+
+        move_position(exchange_account, to_exchange_account, slot);
+
+        // Both accounts have to stay within mint limit as after mint
+        let assets_list = &mut ctx.accounts.assets_list.load_mut()?;
+        // calculate debt also validate if oracles are up-to-date
+        let total_debt =
+            calculate_debt_with_adjustment(state, assets_list, slot, timestamp).unwrap();
+        for account in [&**exchange_account, &**to_exchange_account] {
+            let user_debt = calculate_user_debt_in_usd(account, total_debt, state.debt_shares);
+            let max_debt = calculate_max_debt_in_usd(account, assets_list);
+            if max_debt.mul(state.health_factor).lt(user_debt)? {
+                return Err(ErrorCode::MintLimit.into());
+            }
+        }
+
+        // Moved SNY and debt change staking boost of both accounts
+        for account in [&mut **exchange_account, &mut **to_exchange_account] {
+            let user_debt = calculate_user_debt_in_usd(account, total_debt, state.debt_shares);
+            adjust_staking_boost(state, account, assets_list, user_debt);
+        }
+        Ok(())
+    }
     #[access_control(halted(&ctx.accounts.state))]
     pub fn swap(ctx: Context<Swap>, amount: u64) -> Result<()> {
         msg!("Synthetify: SWAP");
         let mut state = &mut ctx.accounts.state.load_mut()?;
 
         let slot = Clock::get()?.slot;
-        // Adjust staking round
-        adjust_staking_rounds(&mut state, slot);
+        // Accrue staking rewards
+        adjust_staking_rewards(&mut state, slot);
 
         let token_address_in = ctx.accounts.token_in.to_account_info().key;
         let token_address_for = ctx.accounts.token_for.to_account_info().key;
@@ -536,16 +852,11 @@ pub mod exchange {
         )?;
         let sny_collateral = &mut collaterals[0];
 
-        // find exchange account from reaming accounts
+        // find exchange account (any sub account of signer) from reaming accounts
         let signer = &ctx.accounts.owner;
-        let (exchange_account_address, _) = Pubkey::find_program_address(
-            &[b"accountv1", &signer.to_account_info().key.as_ref()],
-            ctx.program_id,
-        );
-        let remaining_account = ctx
-            .remaining_accounts
-            .iter()
-            .find(|account| *account.key == exchange_account_address);
+        let remaining_account = ctx.remaining_accounts.iter().find(|account| {
+            Loader::<'_, ExchangeAccount>::try_from(ctx.program_id, account).is_ok()
+        });
 
         let discount = match remaining_account.is_some() {
             true => {
@@ -555,6 +866,10 @@ pub mod exchange {
                 )
                 .unwrap();
 
+                require!(
+                    is_exchange_account_migrated(remaining_account.unwrap())?,
+                    AccountNotMigrated
+                );
                 let exchange_account = &loader.load()?;
                 require!(
                     exchange_account.owner == *signer.key,
@@ -585,12 +900,14 @@ pub mod exchange {
         let seeds = &[SYNTHETIFY_EXCHANGE_SEED.as_bytes(), &[state.nonce]];
         let signer = &[&seeds[..]];
 
-        // Update swap_tax_reserve
-        let swap_tax_reserve = calculate_swap_tax(fee_usd, state.swap_tax_ratio);
-        state.swap_tax_reserve = state.swap_tax_reserve.add(swap_tax_reserve).unwrap();
+        // Update swap_tax_reserve, part of tax goes to insurance fund and debt stakers
+        let swap_tax = calculate_swap_tax(fee_usd, state.swap_tax_ratio);
+        let insurance_fund_tax = swap_tax.mul(state.insurance_fund_ratio);
+        distribute_swap_tax(state, swap_tax.sub(insurance_fund_tax).unwrap());
+        state.insurance_fund = state.insurance_fund.add(insurance_fund_tax).unwrap();
 
         // Update xUSD supply based on tax
-        let new_xusd_supply = synthetics[0].supply.add(swap_tax_reserve).unwrap();
+        let new_xusd_supply = synthetics[0].supply.add(swap_tax).unwrap();
         synthetics[0].set_supply_safely(new_xusd_supply)?;
 
         // Set new supply output token
@@ -630,7 +947,8 @@ pub mod exchange {
         );
         Ok(())
     }
-    #[access_control(halted(&ctx.accounts.state))]
+    #[access_control(halted(&ctx.accounts.state) exchange_account_migrated(&ctx.accounts.exchange_account)
+    authorized(&ctx.accounts.exchange_account, &ctx.accounts.signer, DELEGATE_BURN))]
     pub fn burn(ctx: Context<BurnToken>, amount: u64) -> Result<()> {
         msg!("Synthetify: BURN");
 
@@ -638,12 +956,12 @@ pub mod exchange {
         let timestamp = Clock::get()?.unix_timestamp;
         let mut state = &mut ctx.accounts.state.load_mut()?;
 
-        // Adjust staking round
-        adjust_staking_rounds(&mut state, slot);
+        // Accrue staking rewards
+        adjust_staking_rewards(&mut state, slot);
 
         let exchange_account = &mut ctx.accounts.exchange_account.load_mut()?;
-        // adjust current staking points for exchange account
-        adjust_staking_account(exchange_account, &state.staking);
+        // move accrued staking rewards to exchange account
+        adjust_staking_account(exchange_account, state);
 
         let assets_list = &mut ctx.accounts.assets_list.load_mut()?;
         // calculate debt also validate if oracles are up-to-date
@@ -676,25 +994,14 @@ pub mod exchange {
                 .debt_shares
                 .checked_sub(exchange_account.debt_shares)
                 .unwrap();
-
-            state.staking.next_round.all_points = state.debt_shares;
-            // Should be fine used checked math just in case
-            state.staking.current_round.all_points = state
-                .staking
-                .current_round
-                .all_points
-                .checked_sub(exchange_account.user_staking_data.current_round_points)
-                .unwrap();
-
             exchange_account.debt_shares = 0;
-            // Change points for next staking round
-            exchange_account.user_staking_data.next_round_points = 0;
-            // Change points for current staking round
-            exchange_account.user_staking_data.current_round_points = 0;
 
             // Change supply
             burn_synthetic.set_supply_safely(burn_synthetic.supply.sub(user_debt).unwrap())?;
 
+            // Without debt shares account gets no SNY rewards
+            adjust_staking_boost(state, exchange_account, assets_list, Decimal::from_usd(0));
+
             // Burn token
             // We do not use full allowance maybe its better to burn full allowance
             // and mint matching amount
@@ -708,68 +1015,83 @@ pub mod exchange {
                 .checked_sub(burned_shares)
                 .unwrap();
             state.debt_shares = state.debt_shares.checked_sub(burned_shares).unwrap();
-            state.staking.next_round.all_points = state.debt_shares;
-
-            // Change points for next staking round
-            exchange_account.user_staking_data.next_round_points = exchange_account.debt_shares;
-            // Change points for current staking round
-            if exchange_account.user_staking_data.current_round_points >= burned_shares {
-                exchange_account.user_staking_data.current_round_points = exchange_account
-                    .user_staking_data
-                    .current_round_points
-                    .checked_sub(burned_shares)
-                    .unwrap();
-                state.staking.current_round.all_points = state
-                    .staking
-                    .current_round
-                    .all_points
-                    .checked_sub(burned_shares)
-                    .unwrap();
-            } else {
-                state.staking.current_round.all_points = state
-                    .staking
-                    .current_round
-                    .all_points
-                    .checked_sub(exchange_account.user_staking_data.current_round_points)
-                    .unwrap();
-                exchange_account.user_staking_data.current_round_points = 0;
-            }
 
             // Change supply
             burn_synthetic.set_supply_safely(burn_synthetic.supply.sub(amount_decimal).unwrap())?;
 
+            // Lower debt raises staking boost
+            let debt_after_burn = user_debt.sub(amount_decimal).unwrap();
+            adjust_staking_boost(state, exchange_account, assets_list, debt_after_burn);
+
             // Burn token
             let cpi_ctx = CpiContext::from(&*ctx.accounts).with_signer(signer);
             token::burn(cpi_ctx, amount)?;
             Ok(())
         }
     }
-    #[access_control(halted(&ctx.accounts.state))]
+    #[access_control(halted(&ctx.accounts.state) exchange_account_migrated(&ctx.accounts.exchange_account))]
     pub fn liquidate(ctx: Context<Liquidate>, amount: u64) -> Result<()> {
         msg!("Synthetify: LIQUIDATE");
+        liquidate_exchange_account(ctx.accounts, amount, false)?;
+        Ok(())
+    }
+    // Liquidator receives collateral up front and has to return repaid xUSD
+    // with repay_flash_liquidation later in the same transaction (e.g. after selling collateral)
+    #[access_control(halted(&ctx.accounts.liquidate.state) exchange_account_migrated(&ctx.accounts.liquidate.exchange_account))]
+    pub fn flash_liquidate(ctx: Context<FlashLiquidate>, amount: u64) -> Result<()> {
+        msg!("Synthetify: FLASH LIQUIDATE");
+
+        // Only one flash liquidation can be outstanding
+        require!(
+            ctx.accounts.liquidate.state.load()?.flash_liquidation_debt == 0,
+            FlashLiquidationInProgress
+        );
+        let repay_instruction = instruction::RepayFlashLiquidation {}.data();
+        require!(
+            is_followed_by_instruction(&ctx.accounts.instructions, &repay_instruction)?,
+            FlashLiquidationNotRepaid
+        );
+
+        let repay_amount = liquidate_exchange_account(&ctx.accounts.liquidate, amount, true)?;
+        let state = &mut ctx.accounts.liquidate.state.load_mut()?;
+        state.flash_liquidation_debt = repay_amount;
+
+        Ok(())
+    }
+    pub fn repay_flash_liquidation(ctx: Context<RepayFlashLiquidation>) -> Result<()> {
+        msg!("Synthetify: REPAY FLASH LIQUIDATION");
+
+        let state = &mut ctx.accounts.state.load_mut()?;
+        let amount = state.flash_liquidation_debt;
+        require!(amount > 0, NoFlashLiquidation);
+        state.flash_liquidation_debt = 0;
+
+        let seeds = &[SYNTHETIFY_EXCHANGE_SEED.as_bytes(), &[state.nonce]];
+        let signer = &[&seeds[..]];
+        let cpi_ctx = CpiContext::from(&*ctx.accounts).with_signer(signer);
+        token::burn(cpi_ctx, amount)?;
+
+        Ok(())
+    }
+    #[access_control(halted(&ctx.accounts.state) exchange_account_migrated(&ctx.accounts.exchange_account))]
+    pub fn liquidate_multi<'info>(
+        ctx: Context<'_, '_, '_, 'info, LiquidateMulti<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        msg!("Synthetify: LIQUIDATE MULTI");
 
         let slot = Clock::get()?.slot;
         let timestamp = Clock::get()?.unix_timestamp;
-        let mut state = &mut ctx.accounts.state.load_mut()?;
+        let state = &mut ctx.accounts.state.load_mut()?;
 
-        // Adjust staking round
-        adjust_staking_rounds(&mut state, slot);
+        // Accrue staking rewards
+        adjust_staking_rewards(state, slot);
 
         let exchange_account = &mut ctx.accounts.exchange_account.load_mut()?;
-        // adjust current staking points for exchange account
-        adjust_staking_account(exchange_account, &state.staking);
+        // move accrued staking rewards to exchange account
+        adjust_staking_account(exchange_account, state);
 
         let assets_list = &mut ctx.accounts.assets_list.load_mut()?;
-        let signer = ctx.accounts.signer.key;
-        let reserve_account = &ctx.accounts.reserve_account;
-        let liquidation_fund = &ctx.accounts.liquidation_fund;
-        let liquidator_collateral_account = &ctx.accounts.liquidator_collateral_account;
-        let liquidator_usd_account = &ctx.accounts.liquidator_usd_account;
-
-        // Signer need to be owner of source amount
-        if !signer.eq(&liquidator_usd_account.owner) {
-            return Err(ErrorCode::InvalidSigner.into());
-        }
 
         // Time given user to adjust collateral ratio passed
         if exchange_account.liquidation_deadline > slot {
@@ -786,224 +1108,367 @@ pub mod exchange {
             return Err(ErrorCode::InvalidLiquidation.into());
         }
 
-        // Cannot payback more than liquidation_rate of user debt
-        // If user debt is below 1 USD we can liquidate entire debt
-
-        let max_repay = match user_debt.lte(Decimal::from_integer(1).to_usd())? {
-            true => user_debt.to_usd().to_u64(),
-            false => user_debt.mul(state.liquidation_rate).to_usd().to_u64(),
-        };
-
+        // Cannot payback more than close factor of user debt, which rises as health drops
+        // If user debt is below 1 USD max_repay covers entire debt
+        let close_factor = calculate_close_factor(
+            user_debt,
+            max_debt,
+            state.liquidation_rate,
+            state.full_liquidation_health,
+        );
+        let max_repay = calculate_max_repay(user_debt, close_factor);
         let amount: u64 = match amount {
             u64::MAX => max_repay,
             _ => amount,
         };
-        // Amount to repay must be less or equal max_repay
         require!(amount.le(&max_repay), InvalidLiquidation);
 
-        // Fail if liquidator wants to liquidate more than allowed number
+        // Collaterals are given as triples of accounts
+        let remaining_accounts = ctx.remaining_accounts;
         require!(
-            amount.le(&max_repay) || user_debt.lte(Decimal::from_integer(1).to_usd())?,
-            InvalidLiquidation
+            !remaining_accounts.is_empty() && remaining_accounts.len() % 3 == 0,
+            InvalidAccount
         );
 
-        let (assets, collaterals, _) = assets_list.split_borrow();
-
-        // finding collateral also validate reserve_account.mint, liquidation_fund.mint, liquidator_collateral_account.mint
-        let liquidated_collateral = match collaterals.iter_mut().find(|x| {
-            x.collateral_address.eq(&reserve_account.mint)
-                && x.collateral_address.eq(&liquidation_fund.mint)
-                && x.collateral_address.eq(&liquidator_collateral_account.mint)
-        }) {
-            Some(v) => v,
-            None => return Err(ErrorCode::NoAssetFound.into()),
-        };
-
-        let liquidated_asset = &assets[liquidated_collateral.asset_index as usize];
         let liquidation_amount_preflight = Decimal {
             val: amount.into(),
             scale: XUSD_SCALE,
         };
 
-        let seized_collateral_in_usd_preflight = liquidation_amount_preflight
-            .mul_up(
-                state
-                    .penalty_to_liquidator
-                    .add(state.penalty_to_exchange)
-                    .unwrap(),
-            )
-            .add(liquidation_amount_preflight)
-            .unwrap();
+        let seeds = &[SYNTHETIFY_EXCHANGE_SEED.as_bytes(), &[state.nonce]];
+        let signer_seeds = &[&seeds[..]];
 
-        let seized_collateral_in_token_preflight = usd_to_token_amount(
-            liquidated_asset,
-            seized_collateral_in_usd_preflight,
-            liquidated_collateral.reserve_balance.scale,
-        );
-        let exchange_account_collateral_index =
-            match exchange_account.collaterals.iter().position(|x| {
-                x.collateral_address
-                    .eq(&liquidated_collateral.collateral_address)
+        // Collaterals can override global penalties, so repaid debt is tracked per collateral
+        let mut remaining_to_repay = liquidation_amount_preflight;
+        let mut insurance_amount = Decimal::from_usd(0);
+        let (assets, collaterals, _) = assets_list.split_borrow();
+
+        for accounts in remaining_accounts.chunks(3) {
+            if remaining_to_repay.val == 0 {
+                break;
+            }
+            let reserve_account = Account::<token::TokenAccount>::try_from(&accounts[0])?;
+            let liquidation_fund = Account::<token::TokenAccount>::try_from(&accounts[1])?;
+            let liquidator_collateral_account =
+                Account::<token::TokenAccount>::try_from(&accounts[2])?;
+
+            // finding collateral also validate reserve_account, liquidation_fund and liquidator_collateral_account
+            let liquidated_collateral = match collaterals.iter_mut().find(|x| {
+                x.reserve_address.eq(accounts[0].key)
+                    && x.liquidation_fund.eq(accounts[1].key)
+                    && x.collateral_address.eq(&reserve_account.mint)
+                    && x.collateral_address.eq(&liquidation_fund.mint)
+                    && x.collateral_address.eq(&liquidator_collateral_account.mint)
             }) {
                 Some(v) => v,
                 None => return Err(ErrorCode::NoAssetFound.into()),
             };
+            // liquidated collateral can be send to any account except liquidation_fund and reserve_account
+            require!(
+                accounts[2].key.ne(accounts[0].key) && accounts[2].key.ne(accounts[1].key),
+                InvalidAccount
+            );
 
-        let preflight_check = seized_collateral_in_token_preflight.val
-            <= exchange_account.collaterals[exchange_account_collateral_index]
-                .amount
-                .into();
-
-        let (seized_collateral_in_token, liquidation_amount) = match preflight_check {
-            true => (
-                seized_collateral_in_token_preflight,
-                liquidation_amount_preflight,
-            ),
-            false => {
-                let seized_collateral_in_token = Decimal {
-                    val: exchange_account.collaterals[exchange_account_collateral_index]
-                        .amount
-                        .into(),
-                    scale: liquidated_collateral.reserve_balance.scale,
+            let exchange_account_collateral_index =
+                match exchange_account.collaterals.iter().position(|x| {
+                    x.collateral_address
+                        .eq(&liquidated_collateral.collateral_address)
+                }) {
+                    Some(v) => v,
+                    None => return Err(ErrorCode::NoAssetFound.into()),
                 };
-                (
-                    seized_collateral_in_token,
-                    calculate_value_in_usd(liquidated_asset.price, seized_collateral_in_token),
-                )
-            }
-        };
-        // Rounding down - debt is burned in favor of the system
-
-        let burned_debt_shares = amount_to_shares_by_rounding_down(
-            state.debt_shares,
-            total_debt.to_u64(),
-            liquidation_amount.to_u64(),
-        );
-        state.debt_shares = state.debt_shares.checked_sub(burned_debt_shares).unwrap();
-
-        exchange_account.debt_shares = exchange_account
-            .debt_shares
-            .checked_sub(burned_debt_shares)
-            .unwrap();
-
-        let exchange_account_collateral =
-            &mut exchange_account.collaterals[exchange_account_collateral_index];
-
-        exchange_account_collateral.amount = exchange_account_collateral
-            .amount
-            .checked_sub(seized_collateral_in_token.to_u64())
-            .unwrap();
-        liquidated_collateral.reserve_balance = liquidated_collateral
-            .reserve_balance
-            .sub(seized_collateral_in_token)
-            .unwrap();
+            let liquidated_asset = &assets[liquidated_collateral.asset_index as usize];
+            let user_collateral = Decimal {
+                val: exchange_account.collaterals[exchange_account_collateral_index]
+                    .amount
+                    .into(),
+                scale: liquidated_collateral.reserve_balance.scale,
+            };
 
-        let collateral_to_exchange = seized_collateral_in_token
-            .mul(state.penalty_to_exchange)
-            .div_up(
-                Decimal::from_percent(100)
-                    .add(state.penalty_to_liquidator)
-                    .unwrap()
-                    .add(state.penalty_to_exchange)
-                    .unwrap(),
+            let (penalty_to_liquidator, penalty_to_exchange) =
+                liquidated_collateral.liquidation_penalties(state);
+            let penalty_to_liquidator = calculate_liquidator_penalty(
+                penalty_to_liquidator,
+                state.max_penalty_to_liquidator,
+                state.liquidation_auction_duration,
+                exchange_account.liquidation_deadline,
+                slot,
             );
+            let penalty = penalty_to_liquidator.add(penalty_to_exchange).unwrap();
 
-        let collateral_to_liquidator = seized_collateral_in_token
-            .sub(collateral_to_exchange)
-            .unwrap();
+            // Seize until remaining repay amount with penalties of collateral is covered
+            let to_seize_in_usd = remaining_to_repay
+                .mul_up(penalty)
+                .add(remaining_to_repay)
+                .unwrap();
+            let to_seize_in_token = usd_to_token_amount(
+                liquidated_asset,
+                to_seize_in_usd,
+                liquidated_collateral.reserve_balance.scale,
+            );
+            let (seized_collateral_in_token, repaid) =
+                match to_seize_in_token.gt(user_collateral)? {
+                    // Repaid amount is proportional to seized collateral
+                    true => {
+                        let seized_collateral_in_usd =
+                            calculate_value_in_usd(liquidated_asset.price, user_collateral);
+                        let repaid = remaining_to_repay
+                            .mul(seized_collateral_in_usd)
+                            .div_up(to_seize_in_usd);
+                        match repaid.gt(remaining_to_repay)? {
+                            true => (user_collateral, remaining_to_repay),
+                            false => (user_collateral, repaid),
+                        }
+                    }
+                    false => (to_seize_in_token, remaining_to_repay),
+                };
+            remaining_to_repay = remaining_to_repay.sub(repaid).unwrap();
 
-        // Remove staking for liquidation
-        state.staking.next_round.all_points = state.debt_shares;
-        state.staking.current_round.all_points = state
-            .staking
-            .current_round
-            .all_points
-            .checked_sub(exchange_account.user_staking_data.current_round_points)
-            .unwrap();
-        state.staking.finished_round.all_points = state
-            .staking
-            .finished_round
-            .all_points
-            .checked_sub(exchange_account.user_staking_data.finished_round_points)
-            .unwrap();
-        exchange_account.user_staking_data.finished_round_points = 0u64;
-        exchange_account.user_staking_data.current_round_points = 0u64;
-        exchange_account.user_staking_data.next_round_points = exchange_account.debt_shares;
+            let exchange_account_collateral =
+                &mut exchange_account.collaterals[exchange_account_collateral_index];
+            exchange_account_collateral.amount = exchange_account_collateral
+                .amount
+                .checked_sub(seized_collateral_in_token.to_u64())
+                .unwrap();
+            liquidated_collateral.reserve_balance = liquidated_collateral
+                .reserve_balance
+                .sub(seized_collateral_in_token)
+                .unwrap();
+            // Clean user collateral if empty
+            if exchange_account.collaterals[exchange_account_collateral_index].amount == 0 {
+                exchange_account.remove(exchange_account_collateral_index);
+            }
 
-        let seeds = &[SYNTHETIFY_EXCHANGE_SEED.as_bytes(), &[state.nonce]];
-        let signer_seeds = &[&seeds[..]];
+            let (collateral_to_liquidator, collateral_to_exchange) =
+                calculate_liquidation_penalty_split(
+                    seized_collateral_in_token,
+                    penalty_to_liquidator,
+                    penalty_to_exchange,
+                );
+            // Part of exchange penalty is sold to liquidator for xUSD feeding insurance fund
+            let collateral_to_insurance = collateral_to_exchange.mul(state.insurance_fund_ratio);
+            let collateral_to_exchange =
+                collateral_to_exchange.sub(collateral_to_insurance).unwrap();
+            insurance_amount = insurance_amount
+                .add(calculate_value_in_usd(
+                    liquidated_asset.price,
+                    collateral_to_insurance,
+                ))
+                .unwrap();
 
-        {
             // transfer collateral to liquidator
             let liquidator_accounts = Transfer {
-                from: ctx.accounts.reserve_account.to_account_info(),
-                to: ctx.accounts.liquidator_collateral_account.to_account_info(),
+                from: accounts[0].clone(),
+                to: accounts[2].clone(),
                 authority: ctx.accounts.exchange_authority.to_account_info(),
             };
             let token_program = ctx.accounts.token_program.to_account_info();
             let transfer =
                 CpiContext::new(token_program, liquidator_accounts).with_signer(signer_seeds);
-            token::transfer(transfer, collateral_to_liquidator.to_u64())?;
-        }
-        {
-            if !ctx
-                .accounts
-                .liquidation_fund
-                .to_account_info()
-                .key
-                .eq(&liquidated_collateral.liquidation_fund)
-            {
-                return Err(ErrorCode::ExchangeLiquidationAccount.into());
-            }
+            token::transfer(
+                transfer,
+                collateral_to_liquidator
+                    .add(collateral_to_insurance)
+                    .unwrap()
+                    .to_u64(),
+            )?;
+
             // transfer collateral to liquidation_account
             let exchange_accounts = Transfer {
-                from: ctx.accounts.reserve_account.to_account_info(),
-                to: ctx.accounts.liquidation_fund.to_account_info(),
+                from: accounts[0].clone(),
+                to: accounts[1].clone(),
                 authority: ctx.accounts.exchange_authority.to_account_info(),
             };
             let token_program = ctx.accounts.token_program.to_account_info();
             let transfer =
                 CpiContext::new(token_program, exchange_accounts).with_signer(signer_seeds);
-            token::transfer(transfer, collateral_to_exchange.try_into().unwrap())?;
+            token::transfer(transfer, collateral_to_exchange.to_u64())?;
         }
-        {
-            // burn xUSD
-            let new_supply = assets_list.synthetics[0]
-                .supply
-                .sub(liquidation_amount)
-                .unwrap();
-            assets_list.synthetics[0].set_supply_safely(new_supply)?;
 
-            let burn_accounts = Burn {
-                mint: ctx.accounts.usd_token.to_account_info(),
-                to: ctx.accounts.liquidator_usd_account.to_account_info(),
-                authority: ctx.accounts.exchange_authority.to_account_info(),
+        let liquidation_amount = liquidation_amount_preflight
+            .sub(remaining_to_repay)
+            .unwrap();
+        state.insurance_fund = state.insurance_fund.add(insurance_amount).unwrap();
+
+        // Rounding down - debt is burned in favor of the system
+        let burned_debt_shares = amount_to_shares_by_rounding_down(
+            state.debt_shares,
+            total_debt.to_u64(),
+            liquidation_amount.to_u64(),
+        );
+        state.debt_shares = state.debt_shares.checked_sub(burned_debt_shares).unwrap();
+        exchange_account.debt_shares = exchange_account
+            .debt_shares
+            .checked_sub(burned_debt_shares)
+            .unwrap();
+
+        // Seized SNY and repaid debt change staking boost
+        let debt_after_liquidation = user_debt.sub(liquidation_amount).unwrap();
+        adjust_staking_boost(state, exchange_account, assets_list, debt_after_liquidation);
+
+        // burn xUSD, insurance part stays in supply as insurance fund
+        let new_supply = assets_list.synthetics[0]
+            .supply
+            .sub(liquidation_amount)
+            .unwrap();
+        assets_list.synthetics[0].set_supply_safely(new_supply)?;
+
+        let burn_accounts = Burn {
+            mint: ctx.accounts.usd_token.to_account_info(),
+            to: ctx.accounts.liquidator_usd_account.to_account_info(),
+            authority: ctx.accounts.exchange_authority.to_account_info(),
+        };
+        let token_program = ctx.accounts.token_program.to_account_info();
+        let burn = CpiContext::new(token_program, burn_accounts).with_signer(signer_seeds);
+        token::burn(
+            burn,
+            liquidation_amount.add(insurance_amount).unwrap().to_u64(),
+        )?;
+
+        Ok(())
+    }
+    // Read only, interest not adjusted yet is included in debt without changing accounts
+    #[access_control(halted(&ctx.accounts.state) exchange_account_migrated(&ctx.accounts.exchange_account))]
+    pub fn preview_liquidation(
+        ctx: Context<PreviewLiquidation>,
+        collateral_address: Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+        msg!("Synthetify: PREVIEW LIQUIDATION");
+
+        let slot = Clock::get()?.slot;
+        let timestamp = Clock::get()?.unix_timestamp;
+        let state = &ctx.accounts.state.load()?;
+        let exchange_account = &ctx.accounts.exchange_account.load()?;
+        let assets_list = &ctx.accounts.assets_list.load()?;
+
+        // calculate debt also validate if oracles are up-to-date
+        let total_debt = calculate_debt_with_interest(state, assets_list, slot, timestamp)?;
+        let user_debt = calculate_user_debt_in_usd(exchange_account, total_debt, state.debt_shares);
+        let max_debt = calculate_max_debt_in_usd(exchange_account, assets_list);
+        let liquidatable =
+            exchange_account.liquidation_deadline <= slot && max_debt.lte(user_debt)?;
+
+        let close_factor = calculate_close_factor(
+            user_debt,
+            max_debt,
+            state.liquidation_rate,
+            state.full_liquidation_health,
+        );
+        let max_repay = calculate_max_repay(user_debt, close_factor);
+        let amount = amount.min(max_repay);
+
+        let liquidated_collateral = match assets_list
+            .collaterals
+            .iter()
+            .find(|x| x.collateral_address.eq(&collateral_address))
+        {
+            Some(v) => v,
+            None => return Err(ErrorCode::NoAssetFound.into()),
+        };
+        let liquidated_asset = &assets_list.assets[liquidated_collateral.asset_index as usize];
+        let exchange_account_collateral_index =
+            match exchange_account.collaterals.iter().position(|x| {
+                x.collateral_address
+                    .eq(&liquidated_collateral.collateral_address)
+            }) {
+                Some(v) => v,
+                None => return Err(ErrorCode::NoAssetFound.into()),
             };
-            let token_program = ctx.accounts.token_program.to_account_info();
-            let burn = CpiContext::new(token_program, burn_accounts).with_signer(signer_seeds);
-            token::burn(burn, liquidation_amount.to_u64())?;
-        }
-        // Clean user collateral if empty
-        if exchange_account.collaterals[exchange_account_collateral_index].amount == 0 {
-            exchange_account.remove(exchange_account_collateral_index);
-        }
 
+        // Collateral can override global penalties
+        let (penalty_to_liquidator, penalty_to_exchange) =
+            liquidated_collateral.liquidation_penalties(state);
+        let penalty_to_liquidator = calculate_liquidator_penalty(
+            penalty_to_liquidator,
+            state.max_penalty_to_liquidator,
+            state.liquidation_auction_duration,
+            exchange_account.liquidation_deadline,
+            slot,
+        );
+        let (seized_collateral_in_token, liquidation_amount) = calculate_seized_collateral(
+            Decimal {
+                val: amount.into(),
+                scale: XUSD_SCALE,
+            },
+            penalty_to_liquidator.add(penalty_to_exchange).unwrap(),
+            liquidated_asset,
+            Decimal {
+                val: exchange_account.collaterals[exchange_account_collateral_index]
+                    .amount
+                    .into(),
+                scale: liquidated_collateral.reserve_balance.scale,
+            },
+        );
+        let (collateral_to_liquidator, collateral_to_exchange) =
+            calculate_liquidation_penalty_split(
+                seized_collateral_in_token,
+                penalty_to_liquidator,
+                penalty_to_exchange,
+            );
+        let collateral_to_insurance = collateral_to_exchange.mul(state.insurance_fund_ratio);
+        let insurance_amount =
+            calculate_value_in_usd(liquidated_asset.price, collateral_to_insurance);
+
+        // Resulting health of account
+        let burned_debt_shares = amount_to_shares_by_rounding_down(
+            state.debt_shares,
+            total_debt.to_u64(),
+            liquidation_amount.to_u64(),
+        );
+        let mut account_after = Box::new(**exchange_account);
+        account_after.debt_shares = account_after
+            .debt_shares
+            .checked_sub(burned_debt_shares)
+            .unwrap();
+        account_after.collaterals[exchange_account_collateral_index].amount = account_after
+            .collaterals[exchange_account_collateral_index]
+            .amount
+            .checked_sub(seized_collateral_in_token.to_u64())
+            .unwrap();
+        let debt_after = calculate_user_debt_in_usd(
+            &account_after,
+            total_debt.sub(liquidation_amount).unwrap(),
+            state.debt_shares.checked_sub(burned_debt_shares).unwrap(),
+        );
+        let max_debt_after = calculate_max_debt_in_usd(&account_after, assets_list);
+
+        let preview = LiquidationPreview {
+            liquidatable,
+            max_repay,
+            repay_amount: liquidation_amount.add(insurance_amount).unwrap().to_u64(),
+            seized_collateral: seized_collateral_in_token.to_u64(),
+            collateral_to_liquidator: collateral_to_liquidator
+                .add(collateral_to_insurance)
+                .unwrap()
+                .to_u64(),
+            collateral_to_exchange: collateral_to_exchange
+                .sub(collateral_to_insurance)
+                .unwrap()
+                .to_u64(),
+            debt_after: debt_after.to_u64(),
+            max_debt_after: max_debt_after.to_u64(),
+        };
+        set_return_data(&preview.try_to_vec().unwrap());
         Ok(())
     }
-    #[access_control(halted(&ctx.accounts.state))]
-    pub fn check_account_collateralization(ctx: Context<CheckCollateralization>) -> Result<()> {
+    // Keeper bounty is paid only when accounts of KeeperBounty are given as remaining accounts
+    #[access_control(halted(&ctx.accounts.state) exchange_account_migrated(&ctx.accounts.exchange_account))]
+    pub fn check_account_collateralization<'info>(
+        ctx: Context<'_, '_, '_, 'info, CheckCollateralization<'info>>,
+    ) -> Result<()> {
         msg!("Synthetify: CHECK ACCOUNT COLLATERALIZATION");
 
         let slot = Clock::get()?.slot;
         let timestamp = Clock::get()?.unix_timestamp;
         let mut state = &mut ctx.accounts.state.load_mut()?;
 
-        // Adjust staking round
-        adjust_staking_rounds(&mut state, slot);
+        // Accrue staking rewards
+        adjust_staking_rewards(&mut state, slot);
 
         let exchange_account = &mut ctx.accounts.exchange_account.load_mut()?;
-        // adjust current staking points for exchange account
-        adjust_staking_account(exchange_account, &state.staking);
+        // move accrued staking rewards to exchange account
+        adjust_staking_account(exchange_account, state);
 
         let assets_list = &mut ctx.accounts.assets_list.load_mut()?;
 
@@ -1014,88 +1479,256 @@ pub mod exchange {
         let user_debt = calculate_user_debt_in_usd(exchange_account, total_debt, state.debt_shares);
         let max_debt = calculate_max_debt_in_usd(exchange_account, assets_list);
 
-        // If account is undercollateralized set liquidation_deadline
-        // After liquidation_deadline slot account can be liquidated
-        if max_debt.gt(user_debt)? {
-            exchange_account.liquidation_deadline = u64::MAX;
-        } else {
-            if exchange_account.liquidation_deadline == u64::MAX {
-                exchange_account.liquidation_deadline =
-                    slot.checked_add(state.liquidation_buffer.into()).unwrap();
-            }
+        // Boost set by owner goes stale with SNY price and debt, anyone can refresh it here
+        adjust_staking_boost(state, exchange_account, assets_list, user_debt);
+
+        let deadline_set = update_liquidation_deadline(
+            exchange_account,
+            user_debt,
+            max_debt,
+            slot,
+            state.liquidation_buffer,
+        )?;
+
+        if ctx.remaining_accounts.is_empty() {
+            return Ok(());
+        }
+        let mut remaining_accounts = ctx.remaining_accounts;
+        let keeper_bounty =
+            KeeperBounty::try_accounts(ctx.program_id, &mut remaining_accounts, &[])?;
+        require!(
+            keeper_bounty
+                .exchange_authority
+                .key
+                .eq(&state.exchange_authority),
+            InvalidAccount
+        );
+        require!(
+            keeper_bounty
+                .usd_token
+                .to_account_info()
+                .key
+                .eq(&assets_list.synthetics[0].asset_address),
+            InvalidAccount
+        );
+
+        // Bounty only for new deadline, limited per account
+        let cooldown_passed = slot
+            >= exchange_account
+                .last_keeper_bounty
+                .saturating_add(state.keeper_bounty_cooldown.into());
+        if !deadline_set || !cooldown_passed || state.keeper_bounty.val == 0 {
+            return Ok(());
+        }
+
+        // Bounty is paid from swap tax reserve
+        let bounty = match state.keeper_bounty.gt(state.swap_tax_reserve)? {
+            true => state.swap_tax_reserve,
+            false => state.keeper_bounty,
+        };
+        if bounty.val == 0 {
+            return Ok(());
         }
+        state.swap_tax_reserve = state.swap_tax_reserve.sub(bounty).unwrap();
+        exchange_account.last_keeper_bounty = slot;
 
+        // Mint xUSD to keeper
+        let seeds = &[SYNTHETIFY_EXCHANGE_SEED.as_bytes(), &[state.nonce]];
+        let signer = &[&seeds[..]];
+        let mint_cpi_ctx = CpiContext::from(&keeper_bounty).with_signer(signer);
+        token::mint_to(mint_cpi_ctx, bounty.to_u64())?;
         Ok(())
     }
+    #[access_control(halted(&ctx.accounts.state) exchange_account_migrated(&ctx.accounts.exchange_account))]
+    pub fn write_off_bad_debt(ctx: Context<WriteOffBadDebt>) -> Result<()> {
+        msg!("Synthetify: WRITE OFF BAD DEBT");
 
-    #[access_control(halted(&ctx.accounts.state))]
+        let slot = Clock::get()?.slot;
+        let timestamp = Clock::get()?.unix_timestamp;
+        let state = &mut ctx.accounts.state.load_mut()?;
+
+        // Accrue staking rewards
+        adjust_staking_rewards(state, slot);
+
+        let exchange_account = &mut ctx.accounts.exchange_account.load_mut()?;
+        // move accrued staking rewards to exchange account
+        adjust_staking_account(exchange_account, state);
+
+        // Only debt without any collateral left can be written off
+        require!(exchange_account.is_insolvent(), AccountSolvent);
+
+        let assets_list = &mut ctx.accounts.assets_list.load_mut()?;
+        // calculate debt also validate if oracles are up-to-date
+        let total_debt =
+            calculate_debt_with_adjustment(state, assets_list, slot, timestamp).unwrap();
+        let user_debt = calculate_user_debt_in_usd(exchange_account, total_debt, state.debt_shares);
+
+        // Insurance fund absorbs shortfall first
+        let covered_by_insurance = match user_debt.gt(state.insurance_fund)? {
+            true => state.insurance_fund,
+            false => user_debt,
+        };
+        state.insurance_fund = state.insurance_fund.sub(covered_by_insurance).unwrap();
+        let new_supply = assets_list.synthetics[0]
+            .supply
+            .sub(covered_by_insurance)
+            .unwrap();
+        assets_list.synthetics[0].set_supply_safely(new_supply)?;
+
+        // Remaining debt is socialized across the pool by burning shares of account
+        let socialized = user_debt.sub(covered_by_insurance).unwrap();
+        let written_off_shares = exchange_account.debt_shares;
+        state.debt_shares = state.debt_shares.checked_sub(written_off_shares).unwrap();
+        exchange_account.debt_shares = 0;
+        exchange_account.liquidation_deadline = u64::MAX;
+        adjust_staking_boost(state, exchange_account, assets_list, Decimal::from_usd(0));
+
+        emit!(BadDebtWriteOff {
+            exchange_account: *ctx.accounts.exchange_account.to_account_info().key,
+            owner: exchange_account.owner,
+            debt: user_debt.to_u64(),
+            covered_by_insurance: covered_by_insurance.to_u64(),
+            socialized: socialized.to_u64(),
+            debt_shares: written_off_shares,
+        });
+        Ok(())
+    }
+
+    #[access_control(halted(&ctx.accounts.state) exchange_account_migrated(&ctx.accounts.exchange_account))]
     pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
         msg!("Synthetify: CLAIM REWARDS");
 
         let slot = Clock::get()?.slot;
         let mut state = &mut ctx.accounts.state.load_mut()?;
 
-        // Adjust staking round
-        adjust_staking_rounds(&mut state, slot);
+        // Accrue staking rewards
+        adjust_staking_rewards(&mut state, slot);
         let exchange_account = &mut ctx.accounts.exchange_account.load_mut()?;
 
-        // adjust current staking points for exchange account
-        adjust_staking_account(exchange_account, &state.staking);
+        // Rewards accrued up to current slot become withdrawable
+        adjust_staking_account(exchange_account, state);
+        Ok(())
+    }
+    #[access_control(halted(&ctx.accounts.state) exchange_account_migrated(&ctx.accounts.exchange_account)
+    authorized(&ctx.accounts.exchange_account, &ctx.accounts.signer, DELEGATE_CLAIM_REWARDS))]
+    pub fn withdraw_rewards<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawRewards<'info>>,
+    ) -> Result<()> {
+        msg!("Synthetify: WITHDRAW REWARDS");
 
-        if state
-            .staking
-            .finished_round
-            .amount
-            .gt(Decimal::from_sny(0))?
-        {
-            let reward_amount: u64 = state
-                .staking
-                .finished_round
-                .amount
-                .val
-                .checked_mul(
-                    exchange_account
-                        .user_staking_data
-                        .finished_round_points
-                        .into(),
-                )
-                .unwrap()
-                .checked_div(state.staking.finished_round.all_points.into())
-                .unwrap()
-                .try_into()
-                .unwrap();
+        let slot = Clock::get()?.slot;
+        let mut state = &mut ctx.accounts.state.load_mut()?;
+
+        // Accrue staking rewards
+        adjust_staking_rewards(&mut state, slot);
+
+        let exchange_account = &mut ctx.accounts.exchange_account.load_mut()?;
+        // move accrued staking rewards to exchange account
+        adjust_staking_account(exchange_account, state);
 
-            exchange_account.user_staking_data.amount_to_claim = exchange_account
-                .user_staking_data
-                .amount_to_claim
-                .add(Decimal::from_sny(reward_amount.into()))?;
+        // Reward streams are given as pairs of fund_account and user_token_account
+        let remaining_accounts = ctx.remaining_accounts;
+        require!(remaining_accounts.len() % 2 == 0, InvalidAccount);
 
-            exchange_account.user_staking_data.finished_round_points = 0;
+        // Delegate can withdraw only to accounts controlled by owner
+        let signer_is_owner = ctx.accounts.signer.key.eq(&exchange_account.owner);
+        require!(
+            signer_is_owner || ctx.accounts.user_token_account.owner == exchange_account.owner,
+            Unauthorized
+        );
+        let seeds = &[SYNTHETIFY_EXCHANGE_SEED.as_bytes(), &[state.nonce]];
+        let signer_seeds = &[&seeds[..]];
+        let mut withdrawn = false;
+
+        // Transfer vested SNY rewards
+        let released =
+            release_vested_rewards(exchange_account, state.rewards_vesting_duration, slot);
+        if released.val > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.staking_fund_account.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.exchange_authority.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts).with_signer(signer_seeds);
+            token::transfer(cpi_ctx, released.to_u64())?;
+            withdrawn = true;
         }
 
+        // Transfer rewards of streams
+        for accounts in remaining_accounts.chunks(2) {
+            let user_token_account = Account::<token::TokenAccount>::try_from(&accounts[1])?;
+
+            // finding stream also validate fund_account and user_token_account
+            let index = match state.reward_streams[..state.head_reward_streams as usize]
+                .iter()
+                .position(|x| {
+                    x.fund_account.eq(accounts[0].key) && x.mint.eq(&user_token_account.mint)
+                }) {
+                Some(v) => v,
+                None => return Err(ErrorCode::NoAssetFound.into()),
+            };
+            require!(accounts[1].key.ne(accounts[0].key), InvalidAccount);
+            require!(
+                signer_is_owner || user_token_account.owner == exchange_account.owner,
+                Unauthorized
+            );
+
+            let amount_to_claim = exchange_account.reward_streams[index].amount_to_claim;
+            if amount_to_claim.val == 0 {
+                continue;
+            }
+            let cpi_accounts = Transfer {
+                from: accounts[0].clone(),
+                to: accounts[1].clone(),
+                authority: ctx.accounts.exchange_authority.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts).with_signer(signer_seeds);
+            token::transfer(cpi_ctx, amount_to_claim.to_u64())?;
+            exchange_account.reward_streams[index].amount_to_claim =
+                Decimal::new(0, amount_to_claim.scale);
+            withdrawn = true;
+        }
+        require!(withdrawn, NoRewards);
         Ok(())
     }
-    #[access_control(halted(&ctx.accounts.state))]
-    pub fn withdraw_rewards(ctx: Context<WithdrawRewards>) -> Result<()> {
-        msg!("Synthetify: WITHDRAW REWARDS");
+    #[access_control(halted(&ctx.accounts.state) exchange_account_migrated(&ctx.accounts.exchange_account)
+    authorized(&ctx.accounts.exchange_account, &ctx.accounts.signer, DELEGATE_CLAIM_REWARDS))]
+    pub fn withdraw_rewards_early(ctx: Context<WithdrawRewards>) -> Result<()> {
+        msg!("Synthetify: WITHDRAW REWARDS EARLY");
 
         let slot = Clock::get()?.slot;
-        let mut state = &mut ctx.accounts.state.load_mut()?;
+        let state = &mut ctx.accounts.state.load_mut()?;
 
-        // Adjust staking round
-        adjust_staking_rounds(&mut state, slot);
+        // Accrue staking rewards
+        adjust_staking_rewards(state, slot);
 
         let exchange_account = &mut ctx.accounts.exchange_account.load_mut()?;
-        // adjust current staking points for exchange account
-        adjust_staking_account(exchange_account, &state.staking);
+        // move accrued staking rewards to exchange account
+        adjust_staking_account(exchange_account, state);
+
+        // Delegate can withdraw only to accounts controlled by owner
+        require!(
+            ctx.accounts.signer.key.eq(&exchange_account.owner)
+                || ctx.accounts.user_token_account.owner == exchange_account.owner,
+            Unauthorized
+        );
+
+        // Unvested rewards are released with penalty
+        let (released, penalty) = exit_rewards_vesting(
+            exchange_account,
+            state.rewards_vesting_duration,
+            state.early_exit_penalty,
+            slot,
+        );
+        require!(released.val > 0, NoRewards);
+
+        // Penalty stays in staking fund and goes to other stakers
+        distribute_early_exit_penalty(state, exchange_account, penalty);
 
-        if exchange_account.user_staking_data.amount_to_claim.val == 0u128 {
-            return Err(ErrorCode::NoRewards.into());
-        }
         let seeds = &[SYNTHETIFY_EXCHANGE_SEED.as_bytes(), &[state.nonce]];
         let signer_seeds = &[&seeds[..]];
-
-        // Transfer rewards
         let cpi_accounts = Transfer {
             from: ctx.accounts.staking_fund_account.to_account_info(),
             to: ctx.accounts.user_token_account.to_account_info(),
@@ -1103,12 +1736,82 @@ pub mod exchange {
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
         let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts).with_signer(signer_seeds);
-        token::transfer(
-            cpi_ctx,
-            exchange_account.user_staking_data.amount_to_claim.to_u64(),
-        )?;
-        // Reset rewards amount
-        exchange_account.user_staking_data.amount_to_claim = Decimal::from_sny(0);
+        token::transfer(cpi_ctx, released.to_u64())?;
+        Ok(())
+    }
+    #[access_control(halted(&ctx.accounts.state) exchange_account_migrated(&ctx.accounts.exchange_account)
+    authorized(&ctx.accounts.exchange_account, &ctx.accounts.signer, DELEGATE_CLAIM_REWARDS))]
+    pub fn compound_rewards(ctx: Context<CompoundRewards>) -> Result<()> {
+        msg!("Synthetify: COMPOUND REWARDS");
+
+        let slot = Clock::get()?.slot;
+        let state = &mut ctx.accounts.state.load_mut()?;
+
+        // Accrue staking rewards
+        adjust_staking_rewards(state, slot);
+
+        let exchange_account = &mut ctx.accounts.exchange_account.load_mut()?;
+        let assets_list = &mut ctx.accounts.assets_list.load_mut()?;
+        // move accrued staking rewards to exchange account
+        adjust_staking_account(exchange_account, state);
+
+        let released =
+            release_vested_rewards(exchange_account, state.rewards_vesting_duration, slot);
+        require!(released.val > 0, NoRewards);
+        let amount = released.to_u64();
+
+        // SNY is always first collateral
+        let collateral = &mut assets_list.collaterals[0];
+        require!(
+            collateral
+                .reserve_address
+                .eq(ctx.accounts.reserve_address.to_account_info().key),
+            InvalidAccount
+        );
+        require!(
+            collateral
+                .collateral_address
+                .eq(&ctx.accounts.staking_fund_account.mint),
+            InvalidAccount
+        );
+
+        let amount_decimal = Decimal {
+            val: amount.into(),
+            scale: collateral.reserve_balance.scale,
+        };
+        let new_reserve_balance = collateral.reserve_balance.add(amount_decimal).unwrap();
+        if new_reserve_balance.gt(collateral.max_collateral)? {
+            return Err(ErrorCode::CollateralLimitExceeded.into());
+        }
+        collateral.reserve_balance = new_reserve_balance;
+
+        let exchange_account_collateral = exchange_account
+            .collaterals
+            .iter_mut()
+            .find(|x| x.collateral_address.eq(&collateral.collateral_address));
+
+        match exchange_account_collateral {
+            Some(entry) => entry.amount = entry.amount.checked_add(amount).unwrap(),
+            None => exchange_account.append(CollateralEntry {
+                amount,
+                collateral_address: collateral.collateral_address,
+                index: 0,
+            }),
+        }
+
+        // Compounded SNY raises staking boost, skipped like in deposit when prices are outdated
+        if let Ok(total_debt) = calculate_debt(assets_list, slot, state.max_delay, false) {
+            let user_debt =
+                calculate_user_debt_in_usd(exchange_account, total_debt, state.debt_shares);
+            adjust_staking_boost(state, exchange_account, assets_list, user_debt);
+        }
+
+        // Move rewards from staking fund to reserve
+        let seeds = &[SYNTHETIFY_EXCHANGE_SEED.as_bytes(), &[state.nonce]];
+        let signer = &[&seeds[..]];
+        let cpi_ctx = CpiContext::from(&*ctx.accounts).with_signer(signer);
+
+        token::transfer(cpi_ctx, amount)?;
         Ok(())
     }
     #[access_control(halted(&ctx.accounts.state)
@@ -1194,14 +1897,47 @@ pub mod exchange {
         token::mint_to(mint_cpi_ctx, actual_amount.to_usd().to_u64())?;
         Ok(())
     }
-    #[access_control(admin(&ctx.accounts.state, &ctx.accounts.admin))]
-    pub fn withdraw_accumulated_debt_interest(
-        ctx: Context<WithdrawAccumulatedDebtInterest>,
-        amount: u64,
-    ) -> Result<()> {
-        msg!("Synthetify: WITHDRAW ACCUMULATED DEBT INTEREST");
+    #[access_control(halted(&ctx.accounts.state) exchange_account_migrated(&ctx.accounts.exchange_account)
+    authorized(&ctx.accounts.exchange_account, &ctx.accounts.signer, DELEGATE_CLAIM_REWARDS))]
+    pub fn withdraw_swap_tax_rewards(ctx: Context<WithdrawSwapTaxRewards>) -> Result<()> {
+        msg!("Synthetify: WITHDRAW SWAP TAX REWARDS");
+
         let slot = Clock::get()?.slot;
-        let timestamp = Clock::get()?.unix_timestamp;
+        let state = &mut ctx.accounts.state.load_mut()?;
+
+        // Accrue staking rewards
+        adjust_staking_rewards(state, slot);
+
+        let exchange_account = &mut ctx.accounts.exchange_account.load_mut()?;
+        // move accrued staking rewards to exchange account
+        adjust_staking_account(exchange_account, state);
+
+        // Delegate can withdraw only to accounts controlled by owner
+        require!(
+            ctx.accounts.signer.key.eq(&exchange_account.owner)
+                || ctx.accounts.to.owner == exchange_account.owner,
+            Unauthorized
+        );
+
+        let amount_to_claim = exchange_account.swap_tax_rewards.amount_to_claim;
+        require!(amount_to_claim.val > 0, NoRewards);
+        exchange_account.swap_tax_rewards.amount_to_claim = Decimal::from_usd(0);
+
+        // xUSD of swap tax is already included in supply
+        let seeds = &[SYNTHETIFY_EXCHANGE_SEED.as_bytes(), &[state.nonce]];
+        let signer = &[&seeds[..]];
+        let mint_cpi_ctx = CpiContext::from(&*ctx.accounts).with_signer(signer);
+        token::mint_to(mint_cpi_ctx, amount_to_claim.to_u64())?;
+        Ok(())
+    }
+    #[access_control(admin(&ctx.accounts.state, &ctx.accounts.admin))]
+    pub fn withdraw_accumulated_debt_interest(
+        ctx: Context<WithdrawAccumulatedDebtInterest>,
+        amount: u64,
+    ) -> Result<()> {
+        msg!("Synthetify: WITHDRAW ACCUMULATED DEBT INTEREST");
+        let slot = Clock::get()?.slot;
+        let timestamp = Clock::get()?.unix_timestamp;
         let state = &mut ctx.accounts.state.load_mut()?;
         let assets_list = &mut ctx.accounts.assets_list.load_mut()?;
 
@@ -1247,6 +1983,73 @@ pub mod exchange {
         Ok(())
     }
     #[access_control(admin(&ctx.accounts.state, &ctx.accounts.admin))]
+    pub fn set_swap_tax_reward_ratio(
+        ctx: Context<AdminAction>,
+        swap_tax_reward_ratio: Decimal,
+    ) -> Result<()> {
+        msg!("Synthetify:Admin: SET SWAP TAX REWARD RATIO");
+        let state = &mut ctx.accounts.state.load_mut()?;
+
+        // swap_tax_reward_ratio should be less or equals 100%
+        let same_scale = swap_tax_reward_ratio.scale == state.swap_tax_reward_ratio.scale;
+        let in_range = swap_tax_reward_ratio.lte(Decimal::from_percent(100))?;
+        require!(same_scale && in_range, ParameterOutOfRange);
+
+        state.swap_tax_reward_ratio = swap_tax_reward_ratio;
+        Ok(())
+    }
+    #[access_control(admin(&ctx.accounts.state, &ctx.accounts.admin))]
+    pub fn set_max_staking_boost(
+        ctx: Context<AdminAction>,
+        max_staking_boost: Decimal,
+    ) -> Result<()> {
+        msg!("Synthetify:Admin:Staking: SET MAX STAKING BOOST");
+        let state = &mut ctx.accounts.state.load_mut()?;
+
+        // max_staking_boost should be less or equals 100%
+        let same_scale = max_staking_boost.scale == state.max_staking_boost.scale;
+        let in_range = max_staking_boost.lte(Decimal::from_percent(100))?;
+        require!(same_scale && in_range, ParameterOutOfRange);
+
+        // Boost of accounts is updated on their next SNY collateral or debt change
+        state.max_staking_boost = max_staking_boost;
+        Ok(())
+    }
+    #[access_control(admin(&ctx.accounts.state, &ctx.accounts.admin))]
+    pub fn set_insurance_fund_ratio(
+        ctx: Context<AdminAction>,
+        insurance_fund_ratio: Decimal,
+    ) -> Result<()> {
+        msg!("Synthetify:Admin: SET INSURANCE FUND RATIO");
+        let state = &mut ctx.accounts.state.load_mut()?;
+
+        // insurance_fund_ratio must be less or equals 100%
+        let same_scale = insurance_fund_ratio.scale == state.insurance_fund_ratio.scale;
+        let in_range = insurance_fund_ratio.lte(Decimal::from_percent(100))?;
+        require!(same_scale && in_range, ParameterOutOfRange);
+
+        state.insurance_fund_ratio = insurance_fund_ratio;
+        Ok(())
+    }
+    #[access_control(admin(&ctx.accounts.state, &ctx.accounts.admin))]
+    pub fn set_keeper_bounty(
+        ctx: Context<AdminAction>,
+        keeper_bounty: Decimal,
+        keeper_bounty_cooldown: u32,
+    ) -> Result<()> {
+        msg!("Synthetify:Admin: SET KEEPER BOUNTY");
+        let state = &mut ctx.accounts.state.load_mut()?;
+
+        // keeper_bounty must be less or equals 100 USD
+        let same_scale = keeper_bounty.scale == XUSD_SCALE;
+        let in_range = keeper_bounty.lte(Decimal::from_integer(100).to_usd())?;
+        require!(same_scale && in_range, ParameterOutOfRange);
+
+        state.keeper_bounty = keeper_bounty;
+        state.keeper_bounty_cooldown = keeper_bounty_cooldown;
+        Ok(())
+    }
+    #[access_control(admin(&ctx.accounts.state, &ctx.accounts.admin))]
     pub fn set_debt_interest_rate(
         ctx: Context<AdminAction>,
         debt_interest_rate: Decimal,
@@ -1290,6 +2093,22 @@ pub mod exchange {
         state.liquidation_rate = liquidation_rate;
         Ok(())
     }
+    #[access_control(admin(&ctx.accounts.state, &ctx.accounts.admin))]
+    pub fn set_full_liquidation_health(
+        ctx: Context<AdminAction>,
+        full_liquidation_health: Decimal,
+    ) -> Result<()> {
+        msg!("Synthetify:Admin: SET FULL LIQUIDATION HEALTH");
+        let state = &mut ctx.accounts.state.load_mut()?;
+
+        // full_liquidation_health should be less or equals 100%
+        let same_scale = full_liquidation_health.scale == state.full_liquidation_health.scale;
+        let in_range = full_liquidation_health.lte(Decimal::from_percent(100))?;
+        require!(same_scale && in_range, ParameterOutOfRange);
+
+        state.full_liquidation_health = full_liquidation_health;
+        Ok(())
+    }
 
     #[access_control(admin(&ctx.accounts.state, &ctx.accounts.admin))]
     pub fn set_fee(ctx: Context<AdminAction>, fee: Decimal) -> Result<()> {
@@ -1339,7 +2158,14 @@ pub mod exchange {
     ) -> Result<()> {
         msg!("Synthetify:Admin:Staking: SET AMOUNT PER ROUND");
         let state = &mut ctx.accounts.state.load_mut()?;
+        let slot = Clock::get()?.slot;
 
+        require!(
+            amount_per_round.scale == state.staking.amount_per_round.scale,
+            DifferentScale
+        );
+        // Rewards up to now are accrued with previous rate
+        adjust_staking_rewards(state, slot);
         state.staking.amount_per_round = amount_per_round;
         Ok(())
     }
@@ -1347,10 +2173,93 @@ pub mod exchange {
     pub fn set_staking_round_length(ctx: Context<AdminAction>, round_length: u32) -> Result<()> {
         msg!("Synthetify:Admin:Staking: SET ROUND LENGTH");
         let state = &mut ctx.accounts.state.load_mut()?;
+        let slot = Clock::get()?.slot;
 
+        // Rewards up to now are accrued with previous rate
+        adjust_staking_rewards(state, slot);
         state.staking.round_length = round_length;
         Ok(())
     }
+    #[access_control(admin(&ctx.accounts.state, &ctx.accounts.admin))]
+    pub fn add_reward_stream(
+        ctx: Context<AddRewardStream>,
+        amount_per_slot: u64,
+        end_slot: u64,
+    ) -> Result<()> {
+        msg!("Synthetify:Admin:Staking: ADD REWARD STREAM");
+        let state = &mut ctx.accounts.state.load_mut()?;
+        let slot = Clock::get()?.slot;
+
+        let head_reward_streams = state.head_reward_streams as usize;
+        require!(
+            head_reward_streams < state.reward_streams.len(),
+            ParameterOutOfRange
+        );
+        // Streams are identified by fund account on withdrawal
+        let fund_account = *ctx.accounts.fund_account.to_account_info().key;
+        require!(
+            state.reward_streams[..head_reward_streams]
+                .iter()
+                .all(|x| x.fund_account.ne(&fund_account)),
+            InvalidAccount
+        );
+
+        state.append_reward_stream(RewardStream {
+            mint: *ctx.accounts.mint.to_account_info().key,
+            fund_account,
+            amount_per_slot: Decimal::new(amount_per_slot.into(), ctx.accounts.mint.decimals),
+            end_slot,
+            reward_per_share: Decimal::new(0, REWARD_PER_SHARE_SCALE),
+            last_update: slot,
+        });
+        Ok(())
+    }
+    #[access_control(admin(&ctx.accounts.state, &ctx.accounts.admin))]
+    pub fn set_reward_stream(
+        ctx: Context<AdminAction>,
+        index: u8,
+        amount_per_slot: Decimal,
+        end_slot: u64,
+    ) -> Result<()> {
+        msg!("Synthetify:Admin:Staking: SET REWARD STREAM");
+        let state = &mut ctx.accounts.state.load_mut()?;
+        let slot = Clock::get()?.slot;
+
+        require!(index < state.head_reward_streams, ParameterOutOfRange);
+        let reward_stream = state.reward_streams[index as usize];
+        require!(
+            amount_per_slot.scale == reward_stream.amount_per_slot.scale,
+            DifferentScale
+        );
+
+        // Rewards up to now are accrued with previous rate
+        adjust_staking_rewards(state, slot);
+        let reward_stream = &mut state.reward_streams[index as usize];
+        reward_stream.amount_per_slot = amount_per_slot;
+        reward_stream.end_slot = end_slot;
+        // Ended stream is not accrued for slots before restart
+        reward_stream.last_update = slot;
+        Ok(())
+    }
+    #[access_control(admin(&ctx.accounts.state, &ctx.accounts.admin))]
+    pub fn set_rewards_vesting(
+        ctx: Context<AdminAction>,
+        vesting_duration: u32,
+        early_exit_penalty: Decimal,
+    ) -> Result<()> {
+        msg!("Synthetify:Admin:Staking: SET REWARDS VESTING");
+        let state = &mut ctx.accounts.state.load_mut()?;
+
+        // early_exit_penalty should be less or equals 100%
+        let same_scale = early_exit_penalty.scale == state.early_exit_penalty.scale;
+        let in_range = early_exit_penalty.lte(Decimal::from_percent(100))?;
+        require!(same_scale && in_range, ParameterOutOfRange);
+
+        // Already vesting rewards keep their schedule
+        state.rewards_vesting_duration = vesting_duration;
+        state.early_exit_penalty = early_exit_penalty;
+        Ok(())
+    }
 
     #[access_control(admin(&ctx.accounts.state, &ctx.accounts.signer))]
     pub fn set_max_supply(
@@ -1410,6 +2319,26 @@ pub mod exchange {
         Ok(())
     }
     #[access_control(admin(&ctx.accounts.state, &ctx.accounts.admin))]
+    pub fn set_liquidation_auction(
+        ctx: Context<AdminAction>,
+        liquidation_auction_duration: u32,
+        max_penalty_to_liquidator: Decimal,
+    ) -> Result<()> {
+        msg!("Synthetify:Admin: SET LIQUIDATION AUCTION");
+        let state = &mut ctx.accounts.state.load_mut()?;
+
+        // max_penalty_to_liquidator must be between penalty_to_liquidator and 25%
+        let same_scale = max_penalty_to_liquidator.scale == state.penalty_to_liquidator.scale;
+        require!(same_scale, ParameterOutOfRange);
+        let in_range = max_penalty_to_liquidator.lte(Decimal::from_percent(25))?
+            && max_penalty_to_liquidator.gte(state.penalty_to_liquidator)?;
+        require!(in_range, ParameterOutOfRange);
+
+        state.liquidation_auction_duration = liquidation_auction_duration;
+        state.max_penalty_to_liquidator = max_penalty_to_liquidator;
+        Ok(())
+    }
+    #[access_control(admin(&ctx.accounts.state, &ctx.accounts.admin))]
     pub fn add_collateral(
         ctx: Context<AddCollateral>,
         reserve_balance: Decimal,
@@ -1445,6 +2374,9 @@ pub mod exchange {
             collateral_ratio,
             reserve_balance,
             max_collateral,
+            // global liquidation penalties until set_collateral_liquidation_penalties
+            penalty_to_liquidator: Decimal::default(),
+            penalty_to_exchange: Decimal::default(),
         };
         assets_list.append_collateral(new_collateral);
         Ok(())
@@ -1495,6 +2427,41 @@ pub mod exchange {
         Ok(())
     }
     #[access_control(admin(&ctx.accounts.state, &ctx.accounts.admin))]
+    pub fn set_collateral_liquidation_penalties(
+        ctx: Context<SetCollateralLiquidationPenalties>,
+        penalty_to_exchange: Decimal,
+        penalty_to_liquidator: Decimal,
+    ) -> Result<()> {
+        msg!("Synthetify:Admin: SET COLLATERAL LIQUIDATION PENALTIES");
+        let state = ctx.accounts.state.load()?;
+        let mut assets_list = ctx.accounts.assets_list.load_mut()?;
+
+        let collateral = match assets_list.collaterals.iter_mut().find(|x| {
+            x.collateral_address == *ctx.accounts.collateral_address.to_account_info().key
+        }) {
+            Some(asset) => asset,
+            None => return Err(ErrorCode::NoAssetFound.into()),
+        };
+
+        // Default (zero scale) penalties remove override
+        let is_override = penalty_to_exchange != Decimal::default()
+            || penalty_to_liquidator != Decimal::default();
+        if is_override {
+            // penalty_to_exchange and penalty_to_liquidator must be less or equals 25%
+            let same_scale = penalty_to_exchange.scale == state.penalty_to_exchange.scale;
+            let in_range = penalty_to_exchange.lte(Decimal::from_percent(25))?;
+            require!(same_scale && in_range, ParameterOutOfRange);
+
+            let same_scale = penalty_to_liquidator.scale == state.penalty_to_liquidator.scale;
+            let in_range = penalty_to_liquidator.lte(Decimal::from_percent(25))?;
+            require!(same_scale && in_range, ParameterOutOfRange);
+        }
+
+        collateral.penalty_to_exchange = penalty_to_exchange;
+        collateral.penalty_to_liquidator = penalty_to_liquidator;
+        Ok(())
+    }
+    #[access_control(admin(&ctx.accounts.state, &ctx.accounts.admin))]
     pub fn set_admin(ctx: Context<SetAdmin>) -> Result<()> {
         msg!("Synthetify:Admin: SET ADMIN");
         let mut state = ctx.accounts.state.load_mut()?;
@@ -1865,6 +2832,7 @@ pub mod exchange {
         ctx: Context<CreateVault>,
         bump: u8,
         vault_type: u8,
+        kind: u8,
         open_fee: Decimal,
         debt_interest_rate: Decimal,
         collateral_ratio: Decimal,
@@ -1900,6 +2868,21 @@ pub mod exchange {
             open_fee.scale == UNIFIED_PERCENT_SCALE && open_fee.lte(Decimal::from_percent(100))?,
             ParameterOutOfRange
         );
+        // Kind cannot be changed later, switching it would reprice open entries
+        match to_vault_type(kind)? {
+            VaultType::Standard => {}
+            VaultType::Stablecoin => {
+                // Depegged collateral is liquidated without penalty
+                require!(
+                    penalty_to_liquidator.val == 0 && penalty_to_exchange.val == 0,
+                    ParameterOutOfRange
+                );
+            }
+            VaultType::InterestFree => {
+                // Only open_fee is charged
+                require!(debt_interest_rate.val == 0, ParameterOutOfRange);
+            }
+        }
 
         // Init vault struct
         {
@@ -1911,6 +2894,8 @@ pub mod exchange {
             vault.oracle_type = oracle_type;
             vault.open_fee = open_fee;
             vault.debt_interest_rate = debt_interest_rate;
+            vault.current_interest_rate = debt_interest_rate;
+            vault.deleverage_fee = Decimal::from_percent(0);
             vault.collateral_ratio = collateral_ratio;
             vault.accumulated_interest = Decimal::new(0, synthetic.max_supply.scale);
             vault.accumulated_interest_rate = Decimal::from_integer(1).to_interest_rate();
@@ -1926,6 +2911,7 @@ pub mod exchange {
             vault.liquidation_penalty_liquidator = penalty_to_liquidator;
             vault.liquidation_penalty_exchange = penalty_to_exchange;
             vault.vault_type = vault_type;
+            vault.kind = kind;
         }
 
         Ok(())
@@ -2028,18 +3014,30 @@ pub mod exchange {
         }
         check_value_collateral_price_feed(&ctx.accounts.collateral_price_feed, vault.oracle_type)?;
 
-        let collateral_price = load_price_from_feed(
+        let oracle_price = load_price_from_feed(
             &ctx.accounts.collateral_price_feed,
             vault.oracle_type,
             assets,
         )?;
+        let collateral_price = vault_collateral_price(vault, oracle_price)?;
+        let basket_prices = load_vault_basket_prices(vault, ctx.remaining_accounts, assets)?;
+        let basket_borrow_limit = calculate_vault_basket_borrow_limit(
+            vault,
+            vault_entry,
+            &basket_prices,
+            synthetic_asset,
+            *synthetic,
+            false,
+            None,
+        );
         let amount_borrow_limit = calculate_vault_borrow_limit(
             collateral_price,
             synthetic_asset,
             *synthetic,
             vault_entry.collateral_amount,
             vault.collateral_ratio,
-        );
+        )
+        .add(basket_borrow_limit)?;
 
         let mint_amount = match amount {
             u64::MAX => {
@@ -2069,6 +3067,75 @@ pub mod exchange {
         Ok(())
     }
 
+    #[access_control(halted(&ctx.accounts.state) vault_halted(&ctx.accounts.vault) swapline_halted(&ctx.accounts.swapline))]
+    pub fn leverage_vault(ctx: Context<LeverageVault>, target_ratio: Decimal) -> Result<()> {
+        msg!("Synthetify: LEVERAGE VAULT");
+        let timestamp = Clock::get()?.unix_timestamp;
+        let slot = Clock::get()?.slot;
+
+        let state = ctx.accounts.state.load()?;
+        let assets_list = &mut ctx.accounts.assets_list.load_mut()?;
+        let vault_entry = &mut ctx.accounts.vault_entry.load_mut()?;
+        let vault = &mut ctx.accounts.vault.load_mut()?;
+        let swapline = &mut ctx.accounts.swapline.load_mut()?;
+        let (assets, _, synthetics) = assets_list.split_borrow();
+
+        // Target cannot exceed regular borrow limit
+        require!(
+            target_ratio.scale == UNIFIED_PERCENT_SCALE
+                && target_ratio.val > 0
+                && target_ratio.lte(vault.collateral_ratio)?,
+            ParameterOutOfRange
+        );
+        // Levering past own deleverage threshold would let keeper sell collateral right away
+        let deleverage_threshold = vault_entry.deleverage_threshold;
+        require!(
+            deleverage_threshold.val == 0 || target_ratio.lt(deleverage_threshold)?,
+            ParameterOutOfRange
+        );
+
+        let synthetic = match synthetics.iter_mut().find(|x| {
+            x.asset_address
+                .eq(ctx.accounts.synthetic.to_account_info().key)
+        }) {
+            Some(s) => s,
+            None => return Err(ErrorCode::NoAssetFound.into()),
+        };
+        let synthetic_asset = assets[synthetic.asset_index as usize];
+
+        adjust_vault_entry_interest_debt(vault, vault_entry, synthetic, timestamp);
+
+        if synthetic_asset.last_update < slot.checked_sub(state.max_delay.into()).unwrap() {
+            return Err(ErrorCode::OutdatedOracle.into());
+        }
+        check_value_collateral_price_feed(&ctx.accounts.collateral_price_feed, vault.oracle_type)?;
+
+        let oracle_price = load_price_from_feed(
+            &ctx.accounts.collateral_price_feed,
+            vault.oracle_type,
+            assets,
+        )?;
+        let collateral_price = vault_collateral_price(vault, oracle_price)?;
+
+        // Synthetic is never minted, swapline only releases collateral to vault
+        let collateral_out = leverage_vault_entry(
+            vault,
+            vault_entry,
+            synthetic,
+            synthetic_asset,
+            swapline,
+            collateral_price,
+            target_ratio,
+        )?;
+
+        let seeds = &[SYNTHETIFY_EXCHANGE_SEED.as_bytes(), &[state.nonce]];
+        let signer = &[&seeds[..]];
+        let cpi_ctx = CpiContext::from(&*ctx.accounts).with_signer(signer);
+        token::transfer(cpi_ctx, collateral_out.to_u64())?;
+
+        Ok(())
+    }
+
     #[access_control(halted(&ctx.accounts.state) vault_halted(&ctx.accounts.vault))]
     pub fn withdraw_vault(ctx: Context<WithdrawVault>, amount: u64) -> Result<()> {
         msg!("Synthetify: WITHDRAW_VAULT");
@@ -2099,16 +3166,30 @@ pub mod exchange {
         }
         check_value_collateral_price_feed(&ctx.accounts.collateral_price_feed, vault.oracle_type)?;
 
-        let collateral_price = load_price_from_feed(
+        let oracle_price = load_price_from_feed(
             &ctx.accounts.collateral_price_feed,
             vault.oracle_type,
             assets,
         )?;
+        let collateral_price = vault_collateral_price(vault, oracle_price)?;
+        // Debt is covered by basket collaterals first
+        let basket_prices = load_vault_basket_prices(vault, ctx.remaining_accounts, assets)?;
+        let basket_borrow_limit = calculate_vault_basket_borrow_limit(
+            vault,
+            vault_entry,
+            &basket_prices,
+            synthetic_asset,
+            *synthetic,
+            false,
+            None,
+        );
+        let uncovered_debt =
+            calculate_vault_uncovered_debt(vault_entry.synthetic_amount, basket_borrow_limit);
         let vault_withdraw_limit = calculate_vault_withdraw_limit(
             collateral_price,
             synthetic_asset,
             vault_entry.collateral_amount,
-            vault_entry.synthetic_amount,
+            uncovered_debt,
             vault.collateral_ratio,
         )
         .unwrap();
@@ -2129,26 +3210,586 @@ pub mod exchange {
             .sub(amount_to_withdraw)
             .unwrap();
 
-        // Send withdrawn collateral to user
+        // Send withdrawn collateral to user
+        let seeds = &[SYNTHETIFY_EXCHANGE_SEED.as_bytes(), &[state.nonce]];
+        let signer = &[&seeds[..]];
+        let cpi_ctx = CpiContext::from(&*ctx.accounts).with_signer(signer);
+        token::transfer(cpi_ctx, amount_to_withdraw.val.try_into().unwrap())?;
+
+        Ok(())
+    }
+
+    #[access_control(halted(&ctx.accounts.state) vault_halted(&ctx.accounts.vault))]
+    pub fn repay_vault(ctx: Context<RepayVault>, amount: u64) -> Result<()> {
+        msg!("Synthetify: REPAY_VAULT");
+
+        let timestamp = Clock::get()?.unix_timestamp;
+
+        let state = ctx.accounts.state.load()?;
+        let assets_list = &mut ctx.accounts.assets_list.load_mut()?;
+        let vault_entry = &mut ctx.accounts.vault_entry.load_mut()?;
+        let vault = &mut ctx.accounts.vault.load_mut()?;
+        let (_, _, synthetics) = assets_list.split_borrow();
+
+        let synthetic = match synthetics.iter_mut().find(|x| {
+            x.asset_address
+                .eq(ctx.accounts.synthetic.to_account_info().key)
+        }) {
+            Some(s) => s,
+            None => return Err(ErrorCode::NoAssetFound.into()),
+        };
+
+        adjust_vault_entry_interest_debt(vault, vault_entry, synthetic, timestamp);
+
+        // determine repay_amount
+        let mut repay_amount = match amount {
+            u64::MAX => vault_entry.synthetic_amount,
+            _ => Decimal::new(amount.into(), vault_entry.synthetic_amount.scale),
+        };
+
+        if repay_amount.gt(vault_entry.synthetic_amount)? {
+            repay_amount = vault_entry.synthetic_amount;
+        };
+        vault_entry.decrease_supply_cascade(vault, synthetic, repay_amount)?;
+
+        // burn tokens
+        let seeds = &[SYNTHETIFY_EXCHANGE_SEED.as_bytes(), &[state.nonce]];
+        let signer = &[&seeds[..]];
+        let cpi_ctx = CpiContext::from(&*ctx.accounts).with_signer(signer);
+        token::burn(cpi_ctx, repay_amount.to_u64())?;
+
+        Ok(())
+    }
+    // Read only, interest is accrued on copies of vault, vault entry and synthetic
+    #[access_control(halted(&ctx.accounts.state) vault_halted(&ctx.accounts.vault))]
+    pub fn preview_vault_liquidation(
+        ctx: Context<PreviewVaultLiquidation>,
+        amount: u64,
+    ) -> Result<()> {
+        msg!("Synthetify: PREVIEW VAULT LIQUIDATION");
+
+        let timestamp = Clock::get()?.unix_timestamp;
+        let slot = Clock::get()?.slot;
+
+        let state = ctx.accounts.state.load()?;
+        let assets_list = &ctx.accounts.assets_list.load()?;
+        let vault_entry = &mut Box::new(*ctx.accounts.vault_entry.load()?);
+        let vault = &mut Box::new(*ctx.accounts.vault.load()?);
+        let assets = &assets_list.assets;
+
+        let synthetic = &mut match assets_list.synthetics.iter().find(|x| {
+            x.asset_address
+                .eq(ctx.accounts.synthetic.to_account_info().key)
+        }) {
+            Some(s) => *s,
+            None => return Err(ErrorCode::NoAssetFound.into()),
+        };
+        let synthetic_asset = &assets[synthetic.asset_index as usize];
+
+        adjust_vault_entry_interest_debt(vault, vault_entry, synthetic, timestamp);
+
+        if synthetic_asset.last_update < slot.checked_sub(state.max_delay.into()).unwrap() {
+            return Err(ErrorCode::OutdatedOracle.into());
+        }
+        check_value_collateral_price_feed(&ctx.accounts.collateral_price_feed, vault.oracle_type)?;
+
+        let oracle_price = load_price_from_feed(
+            &ctx.accounts.collateral_price_feed,
+            vault.oracle_type,
+            assets,
+        )?;
+        let collateral_price = vault_collateral_price(vault, oracle_price)?;
+
+        let basket_prices = load_vault_basket_prices(vault, ctx.remaining_accounts, assets)?;
+        let basket_liquidation_limit = calculate_vault_basket_borrow_limit(
+            vault,
+            vault_entry,
+            &basket_prices,
+            *synthetic_asset,
+            *synthetic,
+            true,
+            None,
+        );
+
+        // Amount of synthetic safely collateralized
+        let amount_liquidation_limit = calculate_vault_borrow_limit(
+            collateral_price,
+            *synthetic_asset,
+            *synthetic,
+            vault_entry.collateral_amount,
+            vault.liquidation_threshold,
+        )
+        .add(basket_liquidation_limit)?;
+        let liquidatable = amount_liquidation_limit.lt(vault_entry.synthetic_amount)?;
+
+        // Same limits as in liquidate_vault
+        let amount_in_usd =
+            calculate_value_in_usd(synthetic_asset.price, vault_entry.synthetic_amount);
+        let max_repay = match amount_in_usd.lte(Decimal::from_integer(1).to_usd())? {
+            true => vault_entry.synthetic_amount,
+            false => vault_entry.synthetic_amount.mul(vault.liquidation_ratio),
+        };
+        let liquidation_amount = match amount {
+            u64::MAX => max_repay,
+            _ => Decimal::new(amount.into(), vault_entry.synthetic_amount.scale),
+        };
+        let liquidation_amount = match liquidation_amount.gt(max_repay)? {
+            true => max_repay,
+            false => liquidation_amount,
+        };
+
+        let seized_collateral_in_token = calculate_vault_seized_collateral(
+            liquidation_amount,
+            vault
+                .liquidation_penalty_liquidator
+                .add(vault.liquidation_penalty_exchange)
+                .unwrap(),
+            synthetic_asset.price,
+            collateral_price,
+            ctx.accounts.collateral.decimals,
+        );
+        let (collateral_to_liquidator, collateral_to_exchange) =
+            calculate_liquidation_penalty_split(
+                seized_collateral_in_token,
+                vault.liquidation_penalty_liquidator,
+                vault.liquidation_penalty_exchange,
+            );
+
+        // Resulting health of vault entry
+        let collateral_after = match seized_collateral_in_token.gt(vault_entry.collateral_amount)? {
+            true => Decimal::new(0, vault_entry.collateral_amount.scale),
+            false => vault_entry
+                .collateral_amount
+                .sub(seized_collateral_in_token)
+                .unwrap(),
+        };
+        let debt_after = vault_entry
+            .synthetic_amount
+            .sub(liquidation_amount)
+            .unwrap();
+        let max_debt_after = calculate_vault_borrow_limit(
+            collateral_price,
+            *synthetic_asset,
+            *synthetic,
+            collateral_after,
+            vault.liquidation_threshold,
+        )
+        .add(basket_liquidation_limit)?;
+
+        let preview = LiquidationPreview {
+            liquidatable,
+            max_repay: max_repay.to_u64(),
+            repay_amount: liquidation_amount.to_u64(),
+            seized_collateral: seized_collateral_in_token.to_u64(),
+            collateral_to_liquidator: collateral_to_liquidator.to_u64(),
+            collateral_to_exchange: collateral_to_exchange.to_u64(),
+            debt_after: debt_after.to_u64(),
+            max_debt_after: max_debt_after.to_u64(),
+        };
+        set_return_data(&preview.try_to_vec().unwrap());
+        Ok(())
+    }
+    #[access_control(halted(&ctx.accounts.state) vault_halted(&ctx.accounts.vault))]
+    pub fn liquidate_vault(ctx: Context<LiquidateVault>, amount: u64) -> Result<()> {
+        msg!("Synthetify: LIQUIDATE VAULT");
+
+        let timestamp = Clock::get()?.unix_timestamp;
+        let slot = Clock::get()?.slot;
+
+        let state = ctx.accounts.state.load()?;
+        let assets_list = &mut ctx.accounts.assets_list.load_mut()?;
+        let vault_entry = &mut ctx.accounts.vault_entry.load_mut()?;
+        let vault = &mut ctx.accounts.vault.load_mut()?;
+        let (assets, _, synthetics) = assets_list.split_borrow();
+
+        let synthetic_position = match synthetics.iter_mut().position(|x| {
+            x.asset_address
+                .eq(ctx.accounts.synthetic.to_account_info().key)
+        }) {
+            Some(i) => i,
+            None => return Err(ErrorCode::NoAssetFound.into()),
+        };
+
+        let synthetic = &mut synthetics[synthetic_position];
+        let synthetic_asset = &assets[synthetic.asset_index as usize];
+
+        adjust_vault_entry_interest_debt(vault, vault_entry, synthetic, timestamp);
+
+        if synthetic_asset.last_update < slot.checked_sub(state.max_delay.into()).unwrap() {
+            return Err(ErrorCode::OutdatedOracle.into());
+        }
+        check_value_collateral_price_feed(&ctx.accounts.collateral_price_feed, vault.oracle_type)?;
+
+        let oracle_price = load_price_from_feed(
+            &ctx.accounts.collateral_price_feed,
+            vault.oracle_type,
+            assets,
+        )?;
+        let collateral_price = vault_collateral_price(vault, oracle_price)?;
+
+        let basket_prices = load_vault_basket_prices(vault, ctx.remaining_accounts, assets)?;
+        let basket_liquidation_limit = calculate_vault_basket_borrow_limit(
+            vault,
+            vault_entry,
+            &basket_prices,
+            *synthetic_asset,
+            *synthetic,
+            true,
+            None,
+        );
+
+        // Amount of synthetic safely collateralized
+        let amount_liquidation_limit = calculate_vault_borrow_limit(
+            collateral_price,
+            *synthetic_asset,
+            *synthetic,
+            vault_entry.collateral_amount,
+            vault.liquidation_threshold,
+        )
+        .add(basket_liquidation_limit)?;
+        // Fail if user is safe
+        require!(
+            amount_liquidation_limit.lt(vault_entry.synthetic_amount)?,
+            InvalidLiquidation
+        );
+
+        // Amount of synthetic to repay
+        // U64::MAX mean debt * liquidation_ratio (percent of position able to liquidate in single liquidation)
+        // If user debt is below 1 USD we can liquidate entire debt
+        let amount_in_usd =
+            calculate_value_in_usd(synthetic_asset.price, vault_entry.synthetic_amount);
+        let liquidation_amount = match amount {
+            u64::MAX => {
+                if amount_in_usd.lte(Decimal::from_integer(1).to_usd())? {
+                    vault_entry.synthetic_amount
+                } else {
+                    vault_entry.synthetic_amount.mul(vault.liquidation_ratio)
+                }
+            }
+            _ => Decimal::new(amount.into(), vault_entry.synthetic_amount.scale),
+        };
+        // Fail if liquidator wants to liquidate more than allowed number
+
+        require!(
+            liquidation_amount.lte(vault_entry.synthetic_amount)?,
+            InvalidLiquidation
+        );
+        // Fail if liquidator wants to liquidate more than allowed number
+        require!(
+            liquidation_amount.lte(vault_entry.synthetic_amount.mul(vault.liquidation_ratio))?
+                || amount_in_usd.lte(Decimal::from_integer(1).to_usd())?,
+            InvalidLiquidation
+        );
+        // Amount seized in token
+        let seized_collateral_in_token = calculate_vault_seized_collateral(
+            liquidation_amount,
+            vault
+                .liquidation_penalty_liquidator
+                .add(vault.liquidation_penalty_exchange)
+                .unwrap(),
+            synthetic_asset.price,
+            collateral_price,
+            ctx.accounts.collateral.decimals,
+        );
+
+        let (collateral_to_liquidator, collateral_to_exchange) =
+            calculate_liquidation_penalty_split(
+                seized_collateral_in_token,
+                vault.liquidation_penalty_liquidator,
+                vault.liquidation_penalty_exchange,
+            );
+        // Rest of debt can be liquidated with basket collaterals
+        require!(
+            seized_collateral_in_token.lte(vault_entry.collateral_amount)?,
+            InvalidLiquidation
+        );
+
+        // Adjust vault_entry variables
+        vault_entry.collateral_amount = vault_entry
+            .collateral_amount
+            .sub(seized_collateral_in_token)
+            .unwrap();
+
+        vault.collateral_amount = vault
+            .collateral_amount
+            .sub(seized_collateral_in_token)
+            .unwrap();
+
+        vault_entry.decrease_supply_cascade(vault, synthetic, liquidation_amount)?;
+
+        let seeds = &[SYNTHETIFY_EXCHANGE_SEED.as_bytes(), &[state.nonce]];
+        let signer_seeds = &[&seeds[..]];
+        {
+            // Transfer collateral to liquidator
+            let liquidator_accounts = Transfer {
+                from: ctx.accounts.collateral_reserve.to_account_info(),
+                to: ctx.accounts.liquidator_collateral_account.to_account_info(),
+                authority: ctx.accounts.exchange_authority.to_account_info(),
+            };
+            let token_program = ctx.accounts.token_program.to_account_info();
+            let transfer =
+                CpiContext::new(token_program, liquidator_accounts).with_signer(signer_seeds);
+            token::transfer(transfer, collateral_to_liquidator.to_u64())?;
+        }
+        {
+            // Transfer collateral to liquidation_fund
+            let exchange_accounts = Transfer {
+                from: ctx.accounts.collateral_reserve.to_account_info(),
+                to: ctx.accounts.liquidation_fund.to_account_info(),
+                authority: ctx.accounts.exchange_authority.to_account_info(),
+            };
+            let token_program = ctx.accounts.token_program.to_account_info();
+            let transfer =
+                CpiContext::new(token_program, exchange_accounts).with_signer(signer_seeds);
+            token::transfer(transfer, collateral_to_exchange.try_into().unwrap())?;
+        }
+        {
+            // Burn repaid synthetic
+            let exchange_accounts = Burn {
+                mint: ctx.accounts.synthetic.to_account_info(),
+                to: ctx.accounts.liquidator_synthetic_account.to_account_info(),
+                authority: ctx.accounts.exchange_authority.to_account_info(),
+            };
+            let token_program = ctx.accounts.token_program.to_account_info();
+            let burn = CpiContext::new(token_program, exchange_accounts).with_signer(signer_seeds);
+            token::burn(burn, liquidation_amount.to_u64())?;
+        }
+        Ok(())
+    }
+
+    #[access_control(halted(&ctx.accounts.state))]
+    pub fn set_vault_entry_deleverage(
+        ctx: Context<SetVaultEntryDeleverage>,
+        threshold: Decimal,
+        target: Decimal,
+    ) -> Result<()> {
+        msg!("Synthetify: SET VAULT ENTRY DELEVERAGE");
+        let vault = ctx.accounts.vault.load()?;
+        let vault_entry = &mut ctx.accounts.vault_entry.load_mut()?;
+
+        // Zero threshold disables deleverage
+        if threshold.val != 0 {
+            // Deleverage values primary collateral only
+            require!(vault.head_collaterals == 0, InvalidVaultType);
+            let same_scale =
+                threshold.scale == UNIFIED_PERCENT_SCALE && target.scale == UNIFIED_PERCENT_SCALE;
+            require!(same_scale, ParameterOutOfRange);
+            // Deleverage has to be triggered before liquidation
+            let in_range = target.val > 0
+                && target.lt(threshold)?
+                && threshold.lt(vault.liquidation_threshold)?;
+            require!(in_range, ParameterOutOfRange);
+        }
+
+        vault_entry.deleverage_threshold = threshold;
+        vault_entry.deleverage_target = target;
+        Ok(())
+    }
+    #[access_control(halted(&ctx.accounts.state) vault_halted(&ctx.accounts.vault) swapline_halted(&ctx.accounts.swapline))]
+    pub fn deleverage_vault(ctx: Context<DeleverageVault>) -> Result<()> {
+        msg!("Synthetify: DELEVERAGE VAULT");
+        let timestamp = Clock::get()?.unix_timestamp;
+        let slot = Clock::get()?.slot;
+
+        let state = ctx.accounts.state.load()?;
+        let assets_list = &mut ctx.accounts.assets_list.load_mut()?;
+        let vault_entry = &mut ctx.accounts.vault_entry.load_mut()?;
+        let vault = &mut ctx.accounts.vault.load_mut()?;
+        let swapline = &mut ctx.accounts.swapline.load_mut()?;
+        let (assets, _, synthetics) = assets_list.split_borrow();
+
+        let synthetic = match synthetics.iter_mut().find(|x| {
+            x.asset_address
+                .eq(ctx.accounts.synthetic.to_account_info().key)
+        }) {
+            Some(s) => s,
+            None => return Err(ErrorCode::NoAssetFound.into()),
+        };
+        let synthetic_asset = assets[synthetic.asset_index as usize];
+
+        adjust_vault_entry_interest_debt(vault, vault_entry, synthetic, timestamp);
+
+        if synthetic_asset.last_update < slot.checked_sub(state.max_delay.into()).unwrap() {
+            return Err(ErrorCode::OutdatedOracle.into());
+        }
+        check_value_collateral_price_feed(&ctx.accounts.collateral_price_feed, vault.oracle_type)?;
+
+        let oracle_price = load_price_from_feed(
+            &ctx.accounts.collateral_price_feed,
+            vault.oracle_type,
+            assets,
+        )?;
+        let collateral_price = vault_collateral_price(vault, oracle_price)?;
+
+        let (sold_collateral, keeper_fee) = deleverage_vault_entry(
+            vault,
+            vault_entry,
+            synthetic,
+            synthetic_asset,
+            swapline,
+            collateral_price,
+        )?;
+
+        let seeds = &[SYNTHETIFY_EXCHANGE_SEED.as_bytes(), &[state.nonce]];
+        let signer_seeds = &[&seeds[..]];
+        {
+            // Transfer sold collateral to swapline
+            let swapline_accounts = Transfer {
+                from: ctx.accounts.vault_collateral_reserve.to_account_info(),
+                to: ctx.accounts.swapline_collateral_reserve.to_account_info(),
+                authority: ctx.accounts.exchange_authority.to_account_info(),
+            };
+            let token_program = ctx.accounts.token_program.to_account_info();
+            let transfer =
+                CpiContext::new(token_program, swapline_accounts).with_signer(signer_seeds);
+            token::transfer(transfer, sold_collateral.to_u64())?;
+        }
+        {
+            // Transfer fee to keeper
+            let keeper_accounts = Transfer {
+                from: ctx.accounts.vault_collateral_reserve.to_account_info(),
+                to: ctx.accounts.keeper_collateral_account.to_account_info(),
+                authority: ctx.accounts.exchange_authority.to_account_info(),
+            };
+            let token_program = ctx.accounts.token_program.to_account_info();
+            let transfer =
+                CpiContext::new(token_program, keeper_accounts).with_signer(signer_seeds);
+            token::transfer(transfer, keeper_fee.to_u64())?;
+        }
+        Ok(())
+    }
+    #[access_control(halted(&ctx.accounts.state) vault_halted(&ctx.accounts.vault))]
+    pub fn close_vault_entry(ctx: Context<CloseVaultEntry>) -> Result<()> {
+        msg!("Synthetify: CLOSE VAULT ENTRY");
+        let timestamp = Clock::get()?.unix_timestamp;
+
+        let assets_list = &mut ctx.accounts.assets_list.load_mut()?;
+        let vault_entry = &mut ctx.accounts.vault_entry.load_mut()?;
+        let vault = &mut ctx.accounts.vault.load_mut()?;
+
+        let synthetic = match assets_list.synthetics.iter_mut().find(|x| {
+            x.asset_address
+                .eq(ctx.accounts.synthetic.to_account_info().key)
+        }) {
+            Some(s) => s,
+            None => return Err(ErrorCode::NoAssetFound.into()),
+        };
+
+        // Interest accrued since last action has to be repaid as well
+        adjust_vault_entry_interest_debt(vault, vault_entry, synthetic, timestamp);
+
+        require!(vault_entry.is_empty(), VaultEntryNotEmpty);
+
+        // Rent is returned to owner on exit
+        Ok(())
+    }
+    #[access_control(halted(&ctx.accounts.state) vault_halted(&ctx.accounts.vault))]
+    pub fn transfer_vault_entry(ctx: Context<TransferVaultEntry>, bump: u8) -> Result<()> {
+        msg!("Synthetify: TRANSFER VAULT ENTRY");
+        let timestamp = Clock::get()?.unix_timestamp;
+
+        let assets_list = &mut ctx.accounts.assets_list.load_mut()?;
+        let vault_entry = &mut ctx.accounts.vault_entry.load_mut()?;
+        let vault = &mut ctx.accounts.vault.load_mut()?;
+
+        let synthetic = match assets_list.synthetics.iter_mut().find(|x| {
+            x.asset_address
+                .eq(ctx.accounts.synthetic.to_account_info().key)
+        }) {
+            Some(s) => s,
+            None => return Err(ErrorCode::NoAssetFound.into()),
+        };
+
+        adjust_vault_entry_interest_debt(vault, vault_entry, synthetic, timestamp);
+
+        let recipient_vault_entry = &mut match ctx.accounts.recipient_vault_entry.load_init() {
+            // Init recipient entry created by this instruction
+            Ok(mut recipient_vault_entry) => {
+                recipient_vault_entry.bump = bump;
+                recipient_vault_entry.owner = *ctx.accounts.recipient.key;
+                recipient_vault_entry.vault = *ctx.accounts.vault.to_account_info().key;
+                recipient_vault_entry.last_accumulated_interest_rate =
+                    vault.accumulated_interest_rate;
+                recipient_vault_entry.synthetic_amount =
+                    Decimal::new(0, vault_entry.synthetic_amount.scale);
+                recipient_vault_entry.collateral_amount =
+                    Decimal::new(0, ctx.accounts.collateral.decimals);
+                recipient_vault_entry
+            }
+            Err(_) => ctx.accounts.recipient_vault_entry.load_mut()?,
+        };
+        adjust_vault_entry_interest_debt(vault, recipient_vault_entry, synthetic, timestamp);
+
+        move_vault_entry(vault_entry, recipient_vault_entry)?;
+        Ok(())
+    }
+    #[access_control(halted(&ctx.accounts.state) vault_halted(&ctx.accounts.vault))]
+    pub fn deposit_vault_collateral(
+        ctx: Context<DepositVaultCollateral>,
+        index: u8,
+        amount: u64,
+    ) -> Result<()> {
+        msg!("Synthetify: DEPOSIT VAULT COLLATERAL");
+
+        let state = &ctx.accounts.state.load()?;
+        let vault_entry = &mut ctx.accounts.vault_entry.load_mut()?;
+        let vault = &mut ctx.accounts.vault.load_mut()?;
+
+        let index = index as usize;
+        require!(index < vault.head_collaterals as usize, NoAssetFound);
+        let vault_collateral = vault.collaterals[index];
+        require!(
+            ctx.accounts
+                .reserve_address
+                .key()
+                .eq(&vault_collateral.collateral_reserve),
+            InvalidAccount
+        );
+        require!(
+            ctx.accounts
+                .user_collateral_account
+                .mint
+                .eq(&vault_collateral.collateral),
+            InvalidAccount
+        );
+
+        let amount_decimal = Decimal::new(amount.into(), vault_collateral.collateral_amount.scale);
+        vault_entry.increase_collateral_cascade(vault, index, amount_decimal)?;
+
+        // Transfer token
         let seeds = &[SYNTHETIFY_EXCHANGE_SEED.as_bytes(), &[state.nonce]];
         let signer = &[&seeds[..]];
         let cpi_ctx = CpiContext::from(&*ctx.accounts).with_signer(signer);
-        token::transfer(cpi_ctx, amount_to_withdraw.val.try_into().unwrap())?;
+        token::transfer(cpi_ctx, amount)?;
 
         Ok(())
     }
-
     #[access_control(halted(&ctx.accounts.state) vault_halted(&ctx.accounts.vault))]
-    pub fn repay_vault(ctx: Context<RepayVault>, amount: u64) -> Result<()> {
-        msg!("Synthetify: REPAY_VAULT");
-
+    pub fn withdraw_vault_collateral(
+        ctx: Context<WithdrawVaultCollateral>,
+        index: u8,
+        amount: u64,
+    ) -> Result<()> {
+        msg!("Synthetify: WITHDRAW VAULT COLLATERAL");
         let timestamp = Clock::get()?.unix_timestamp;
+        let slot = Clock::get()?.slot;
 
         let state = ctx.accounts.state.load()?;
         let assets_list = &mut ctx.accounts.assets_list.load_mut()?;
         let vault_entry = &mut ctx.accounts.vault_entry.load_mut()?;
         let vault = &mut ctx.accounts.vault.load_mut()?;
-        let (_, _, synthetics) = assets_list.split_borrow();
+        let (assets, _, synthetics) = assets_list.split_borrow();
+
+        let index = index as usize;
+        require!(index < vault.head_collaterals as usize, NoAssetFound);
+        let vault_collateral = vault.collaterals[index];
+        require!(
+            ctx.accounts
+                .reserve_address
+                .key()
+                .eq(&vault_collateral.collateral_reserve),
+            InvalidAccount
+        );
 
         let synthetic = match synthetics.iter_mut().find(|x| {
             x.asset_address
@@ -2157,31 +3798,75 @@ pub mod exchange {
             Some(s) => s,
             None => return Err(ErrorCode::NoAssetFound.into()),
         };
+        let synthetic_asset = assets[synthetic.asset_index as usize];
 
         adjust_vault_entry_interest_debt(vault, vault_entry, synthetic, timestamp);
 
-        // determine repay_amount
-        let mut repay_amount = match amount {
-            u64::MAX => vault_entry.synthetic_amount,
-            _ => Decimal::new(amount.into(), vault_entry.synthetic_amount.scale),
-        };
+        if synthetic_asset.last_update < slot.checked_sub(state.max_delay.into()).unwrap() {
+            return Err(ErrorCode::OutdatedOracle.into());
+        }
+        check_value_collateral_price_feed(&ctx.accounts.collateral_price_feed, vault.oracle_type)?;
 
-        if repay_amount.gt(vault_entry.synthetic_amount)? {
-            repay_amount = vault_entry.synthetic_amount;
+        let oracle_price = load_price_from_feed(
+            &ctx.accounts.collateral_price_feed,
+            vault.oracle_type,
+            assets,
+        )?;
+        let collateral_price = vault_collateral_price(vault, oracle_price)?;
+        let basket_prices = load_vault_basket_prices(vault, ctx.remaining_accounts, assets)?;
+
+        // Debt which is not covered by rest of collaterals
+        let other_borrow_limit = calculate_vault_borrow_limit(
+            collateral_price,
+            synthetic_asset,
+            *synthetic,
+            vault_entry.collateral_amount,
+            vault.collateral_ratio,
+        )
+        .add(calculate_vault_basket_borrow_limit(
+            vault,
+            vault_entry,
+            &basket_prices,
+            synthetic_asset,
+            *synthetic,
+            false,
+            Some(index),
+        ))?;
+        let uncovered_debt =
+            calculate_vault_uncovered_debt(vault_entry.synthetic_amount, other_borrow_limit);
+        let vault_withdraw_limit = calculate_vault_withdraw_limit(
+            basket_prices[index],
+            synthetic_asset,
+            vault_entry.collateral_amounts[index],
+            uncovered_debt,
+            vault_collateral.collateral_ratio,
+        )?;
+
+        let amount_to_withdraw = match amount {
+            u64::MAX => vault_withdraw_limit,
+            _ => Decimal::new(amount.into(), vault_collateral.collateral_amount.scale),
         };
-        vault_entry.decrease_supply_cascade(vault, synthetic, repay_amount)?;
+        require!(
+            amount_to_withdraw.lte(vault_withdraw_limit)?,
+            VaultWithdrawLimit
+        );
+        vault_entry.decrease_collateral_cascade(vault, index, amount_to_withdraw)?;
 
-        // burn tokens
+        // Send withdrawn collateral to user
         let seeds = &[SYNTHETIFY_EXCHANGE_SEED.as_bytes(), &[state.nonce]];
         let signer = &[&seeds[..]];
         let cpi_ctx = CpiContext::from(&*ctx.accounts).with_signer(signer);
-        token::burn(cpi_ctx, repay_amount.to_u64())?;
+        token::transfer(cpi_ctx, amount_to_withdraw.to_u64())?;
 
         Ok(())
     }
     #[access_control(halted(&ctx.accounts.state) vault_halted(&ctx.accounts.vault))]
-    pub fn liquidate_vault(ctx: Context<LiquidateVault>, amount: u64) -> Result<()> {
-        msg!("Synthetify: LIQUIDATE VAULT");
+    pub fn liquidate_vault_collateral(
+        ctx: Context<LiquidateVaultCollateral>,
+        index: u8,
+        amount: u64,
+    ) -> Result<()> {
+        msg!("Synthetify: LIQUIDATE VAULT COLLATERAL");
 
         let timestamp = Clock::get()?.unix_timestamp;
         let slot = Clock::get()?.slot;
@@ -2192,15 +3877,31 @@ pub mod exchange {
         let vault = &mut ctx.accounts.vault.load_mut()?;
         let (assets, _, synthetics) = assets_list.split_borrow();
 
-        let synthetic_position = match synthetics.iter_mut().position(|x| {
+        let index = index as usize;
+        require!(index < vault.head_collaterals as usize, NoAssetFound);
+        let vault_collateral = vault.collaterals[index];
+        require!(
+            ctx.accounts
+                .collateral_reserve
+                .key()
+                .eq(&vault_collateral.collateral_reserve),
+            InvalidAccount
+        );
+        require!(
+            ctx.accounts
+                .liquidation_fund
+                .key()
+                .eq(&vault_collateral.liquidation_fund),
+            InvalidAccount
+        );
+
+        let synthetic = match synthetics.iter_mut().find(|x| {
             x.asset_address
                 .eq(ctx.accounts.synthetic.to_account_info().key)
         }) {
-            Some(i) => i,
+            Some(s) => s,
             None => return Err(ErrorCode::NoAssetFound.into()),
         };
-
-        let synthetic = &mut synthetics[synthetic_position];
         let synthetic_asset = &assets[synthetic.asset_index as usize];
 
         adjust_vault_entry_interest_debt(vault, vault_entry, synthetic, timestamp);
@@ -2210,29 +3911,37 @@ pub mod exchange {
         }
         check_value_collateral_price_feed(&ctx.accounts.collateral_price_feed, vault.oracle_type)?;
 
-        let collateral_price = load_price_from_feed(
+        let oracle_price = load_price_from_feed(
             &ctx.accounts.collateral_price_feed,
             vault.oracle_type,
             assets,
         )?;
+        let collateral_price = vault_collateral_price(vault, oracle_price)?;
+        let basket_prices = load_vault_basket_prices(vault, ctx.remaining_accounts, assets)?;
 
-        // Amount of synthetic safely collateralized
+        // Health is checked for whole basket
         let amount_liquidation_limit = calculate_vault_borrow_limit(
             collateral_price,
             *synthetic_asset,
             *synthetic,
             vault_entry.collateral_amount,
             vault.liquidation_threshold,
-        );
-        // Fail if user is safe
+        )
+        .add(calculate_vault_basket_borrow_limit(
+            vault,
+            vault_entry,
+            &basket_prices,
+            *synthetic_asset,
+            *synthetic,
+            true,
+            None,
+        ))?;
         require!(
             amount_liquidation_limit.lt(vault_entry.synthetic_amount)?,
             InvalidLiquidation
         );
 
-        // Amount of synthetic to repay
-        // U64::MAX mean debt * liquidation_ratio (percent of position able to liquidate in single liquidation)
-        // If user debt is below 1 USD we can liquidate entire debt
+        // Same limits as in liquidate_vault
         let amount_in_usd =
             calculate_value_in_usd(synthetic_asset.price, vault_entry.synthetic_amount);
         let liquidation_amount = match amount {
@@ -2245,59 +3954,35 @@ pub mod exchange {
             }
             _ => Decimal::new(amount.into(), vault_entry.synthetic_amount.scale),
         };
-        // Fail if liquidator wants to liquidate more than allowed number
-
         require!(
             liquidation_amount.lte(vault_entry.synthetic_amount)?,
             InvalidLiquidation
         );
-        // Fail if liquidator wants to liquidate more than allowed number
         require!(
             liquidation_amount.lte(vault_entry.synthetic_amount.mul(vault.liquidation_ratio))?
                 || amount_in_usd.lte(Decimal::from_integer(1).to_usd())?,
             InvalidLiquidation
         );
-        // Amount seized in usd
-        let seized_collateral_in_usd = liquidation_amount
-            .mul_up(
-                vault
-                    .liquidation_penalty_liquidator
-                    .add(vault.liquidation_penalty_exchange)
-                    .unwrap(),
-            )
-            .add(liquidation_amount)
-            .unwrap()
-            .mul(synthetic_asset.price);
 
-        // Amount seized in token
-        let seized_collateral_in_token = seized_collateral_in_usd
-            .div_to_scale(collateral_price, ctx.accounts.collateral.decimals);
-
-        let collateral_to_exchange = seized_collateral_in_token
-            .mul(vault.liquidation_penalty_exchange)
-            .div_up(
-                Decimal::from_percent(100)
-                    .add(vault.liquidation_penalty_liquidator)
-                    .unwrap()
-                    .add(vault.liquidation_penalty_exchange)
-                    .unwrap(),
+        let seized_collateral_in_token = calculate_vault_seized_collateral(
+            liquidation_amount,
+            vault
+                .liquidation_penalty_liquidator
+                .add(vault.liquidation_penalty_exchange)
+                .unwrap(),
+            synthetic_asset.price,
+            basket_prices[index],
+            vault_collateral.collateral_amount.scale,
+        );
+        let (collateral_to_liquidator, collateral_to_exchange) =
+            calculate_liquidation_penalty_split(
+                seized_collateral_in_token,
+                vault.liquidation_penalty_liquidator,
+                vault.liquidation_penalty_exchange,
             );
 
-        let collateral_to_liquidator = seized_collateral_in_token
-            .sub(collateral_to_exchange)
-            .unwrap();
-
-        // Adjust vault_entry variables
-        vault_entry.collateral_amount = vault_entry
-            .collateral_amount
-            .sub(seized_collateral_in_token)
-            .unwrap();
-
-        vault.collateral_amount = vault
-            .collateral_amount
-            .sub(seized_collateral_in_token)
-            .unwrap();
-
+        // Fails if entry holds less of seized collateral
+        vault_entry.decrease_collateral_cascade(vault, index, seized_collateral_in_token)?;
         vault_entry.decrease_supply_cascade(vault, synthetic, liquidation_amount)?;
 
         let seeds = &[SYNTHETIFY_EXCHANGE_SEED.as_bytes(), &[state.nonce]];
@@ -2324,7 +4009,7 @@ pub mod exchange {
             let token_program = ctx.accounts.token_program.to_account_info();
             let transfer =
                 CpiContext::new(token_program, exchange_accounts).with_signer(signer_seeds);
-            token::transfer(transfer, collateral_to_exchange.try_into().unwrap())?;
+            token::transfer(transfer, collateral_to_exchange.to_u64())?;
         }
         {
             // Burn repaid synthetic
@@ -2406,11 +4091,82 @@ pub mod exchange {
         let same_scale = vault.debt_interest_rate.scale == debt_interest_rate.scale;
         let in_range = debt_interest_rate.lte(Decimal::from_percent(200).to_interest_rate())?;
         require!(same_scale && in_range, ParameterOutOfRange);
+        require!(
+            debt_interest_rate.val == 0 || to_vault_type(vault.kind)? != VaultType::InterestFree,
+            ParameterOutOfRange
+        );
+        // Debt interest rate is base of utilization curve
+        require!(
+            !vault.interest_curve_enabled
+                || debt_interest_rate.lte(vault.interest_rate_at_optimal)?,
+            ParameterOutOfRange
+        );
 
         vault.debt_interest_rate = debt_interest_rate;
         Ok(())
     }
 
+    #[access_control(admin(&ctx.accounts.state, &ctx.accounts.admin))]
+    pub fn set_vault_interest_rate_curve(
+        ctx: Context<SetVaultParameter>,
+        enabled: bool,
+        optimal_utilization: Decimal,
+        interest_rate_at_optimal: Decimal,
+        max_interest_rate: Decimal,
+    ) -> Result<()> {
+        msg!("Synthetify:Admin: SET VAULT INTEREST RATE CURVE");
+        let timestamp = Clock::get()?.unix_timestamp;
+        let vault = &mut ctx.accounts.vault.load_mut()?;
+
+        require!(
+            !enabled || to_vault_type(vault.kind)? != VaultType::InterestFree,
+            InvalidVaultType
+        );
+        // optimal utilization must be between 0% and 100%
+        // rates must grow from debt_interest_rate up to at most 200%
+        let same_scale = optimal_utilization.scale == UNIFIED_PERCENT_SCALE
+            && interest_rate_at_optimal.scale == vault.debt_interest_rate.scale
+            && max_interest_rate.scale == vault.debt_interest_rate.scale;
+        require!(same_scale, ParameterOutOfRange);
+        let in_range = optimal_utilization.val > 0
+            && optimal_utilization.lt(Decimal::from_percent(100))?
+            && max_interest_rate.lte(Decimal::from_percent(200).to_interest_rate())?;
+        let increasing = vault.debt_interest_rate.lte(interest_rate_at_optimal)?
+            && interest_rate_at_optimal.lte(max_interest_rate)?;
+        require!(in_range && increasing, ParameterOutOfRange);
+
+        // Interest until now accrues with previous rate
+        adjust_vault_interest_rate(vault, timestamp);
+
+        vault.interest_curve_enabled = enabled;
+        vault.optimal_utilization = optimal_utilization;
+        vault.interest_rate_at_optimal = interest_rate_at_optimal;
+        vault.max_interest_rate = max_interest_rate;
+        // Refresh current_interest_rate with new curve
+        adjust_vault_interest_rate(vault, timestamp);
+        Ok(())
+    }
+
+    #[access_control(admin(&ctx.accounts.state, &ctx.accounts.admin))]
+    pub fn set_vault_deleverage_fee(
+        ctx: Context<SetVaultParameter>,
+        deleverage_fee: Decimal,
+    ) -> Result<()> {
+        msg!("Synthetify:Admin: SET VAULT DELEVERAGE FEE");
+        let vault = &mut ctx.accounts.vault.load_mut()?;
+
+        // deleverage fee must be at most 1% and lower than liquidation penalty liquidator
+        let same_scale = deleverage_fee.scale == UNIFIED_PERCENT_SCALE;
+        require!(same_scale, ParameterOutOfRange);
+        let in_range = deleverage_fee.lte(Decimal::from_percent(1))?;
+        let below_penalty =
+            deleverage_fee.val == 0 || deleverage_fee.lt(vault.liquidation_penalty_liquidator)?;
+        require!(in_range && below_penalty, ParameterOutOfRange);
+
+        vault.deleverage_fee = deleverage_fee;
+        Ok(())
+    }
+
     #[access_control(admin(&ctx.accounts.state, &ctx.accounts.admin))]
     pub fn set_vault_liquidation_threshold(
         ctx: Context<SetVaultParameter>,
@@ -2462,6 +4218,11 @@ pub mod exchange {
             vault.liquidation_penalty_liquidator.scale == liquidation_penalty_liquidator.scale;
         let in_range = liquidation_penalty_liquidator.lte(Decimal::from_percent(20))?;
         require!(same_scale && in_range, ParameterOutOfRange);
+        require!(
+            liquidation_penalty_liquidator.val == 0
+                || to_vault_type(vault.kind)? != VaultType::Stablecoin,
+            ParameterOutOfRange
+        );
 
         vault.liquidation_penalty_liquidator = liquidation_penalty_liquidator;
         Ok(())
@@ -2480,6 +4241,11 @@ pub mod exchange {
             vault.liquidation_penalty_exchange.scale == liquidation_penalty_exchange.scale;
         let in_range = liquidation_penalty_exchange.lte(Decimal::from_percent(20))?;
         require!(same_scale && in_range, ParameterOutOfRange);
+        require!(
+            liquidation_penalty_exchange.val == 0
+                || to_vault_type(vault.kind)? != VaultType::Stablecoin,
+            ParameterOutOfRange
+        );
 
         vault.liquidation_penalty_exchange = liquidation_penalty_exchange;
         Ok(())
@@ -2503,6 +4269,58 @@ pub mod exchange {
         Ok(())
     }
 
+    #[access_control(admin(&ctx.accounts.state, &ctx.accounts.admin))]
+    pub fn add_vault_collateral(
+        ctx: Context<AddVaultCollateral>,
+        collateral_ratio: Decimal,
+        liquidation_threshold: Decimal,
+        oracle_type: u8,
+    ) -> Result<()> {
+        msg!("Synthetify:Admin: ADD VAULT COLLATERAL");
+        let vault = &mut ctx.accounts.vault.load_mut()?;
+        let collateral = ctx.accounts.collateral.key();
+
+        check_value_collateral_price_feed(&ctx.accounts.collateral_price_feed, oracle_type)?;
+        // Stablecoin vaults are isolated to single collateral
+        require!(
+            to_vault_type(vault.kind)? != VaultType::Stablecoin,
+            InvalidVaultType
+        );
+
+        // collateral_ratio must be less than liquidation_threshold, both at most 100%
+        let same_scale = collateral_ratio.scale == UNIFIED_PERCENT_SCALE
+            && liquidation_threshold.scale == UNIFIED_PERCENT_SCALE;
+        require!(same_scale, ParameterOutOfRange);
+        let in_range = liquidation_threshold.lte(Decimal::from_percent(100))?;
+        let less_than_liquidation_threshold = collateral_ratio.lt(liquidation_threshold)?;
+        require!(
+            in_range && less_than_liquidation_threshold,
+            ParameterOutOfRange
+        );
+
+        require!(
+            (vault.head_collaterals as usize) < vault.collaterals.len(),
+            ParameterOutOfRange
+        );
+        let already_collateral = vault.collateral.eq(&collateral)
+            || vault.collaterals[..vault.head_collaterals as usize]
+                .iter()
+                .any(|x| x.collateral.eq(&collateral));
+        require!(!already_collateral, AlreadyACollateral);
+
+        vault.append_collateral(VaultCollateral {
+            collateral,
+            collateral_price_feed: ctx.accounts.collateral_price_feed.key(),
+            oracle_type,
+            collateral_ratio,
+            liquidation_threshold,
+            collateral_reserve: ctx.accounts.collateral_reserve.key(),
+            liquidation_fund: ctx.accounts.liquidation_fund.key(),
+            collateral_amount: Decimal::new(0, ctx.accounts.collateral.decimals),
+        });
+        Ok(())
+    }
+
     #[access_control(admin(&ctx.accounts.state, &ctx.accounts.admin))]
     pub fn withdraw_vault_accumulated_interest(
         ctx: Context<WithdrawVaultAccumulatedInterest>,
@@ -2565,6 +4383,254 @@ pub mod exchange {
     }
 }
 
+// Shared by liquidate and flash_liquidate, returns amount of xUSD repaid by liquidator
+fn liquidate_exchange_account(accounts: &Liquidate, amount: u64, flash: bool) -> Result<u64> {
+    use crate::decimal::{Add, Compare, Mul, Sub, XUSD_SCALE};
+    use crate::math::{
+        amount_to_shares_by_rounding_down, calculate_close_factor,
+        calculate_liquidation_penalty_split, calculate_liquidator_penalty,
+        calculate_max_debt_in_usd, calculate_max_repay, calculate_seized_collateral,
+        calculate_user_debt_in_usd, calculate_value_in_usd,
+    };
+    use std::convert::TryInto;
+
+    let slot = Clock::get()?.slot;
+    let timestamp = Clock::get()?.unix_timestamp;
+    let mut state = &mut accounts.state.load_mut()?;
+
+    // Accrue staking rewards
+    adjust_staking_rewards(&mut state, slot);
+
+    let exchange_account = &mut accounts.exchange_account.load_mut()?;
+    // move accrued staking rewards to exchange account
+    adjust_staking_account(exchange_account, state);
+
+    let assets_list = &mut accounts.assets_list.load_mut()?;
+    let signer = accounts.signer.key;
+    let reserve_account = &accounts.reserve_account;
+    let liquidation_fund = &accounts.liquidation_fund;
+    let liquidator_collateral_account = &accounts.liquidator_collateral_account;
+    let liquidator_usd_account = &accounts.liquidator_usd_account;
+
+    // Signer need to be owner of source amount
+    if !signer.eq(&liquidator_usd_account.owner) {
+        return Err(ErrorCode::InvalidSigner.into());
+    }
+
+    // Time given user to adjust collateral ratio passed
+    if exchange_account.liquidation_deadline > slot {
+        return Err(ErrorCode::LiquidationDeadline.into());
+    }
+    // calculate debt also validate if oracles are up-to-date
+    let total_debt = calculate_debt_with_adjustment(state, assets_list, slot, timestamp).unwrap();
+    let user_debt = calculate_user_debt_in_usd(exchange_account, total_debt, state.debt_shares);
+    let max_debt = calculate_max_debt_in_usd(exchange_account, assets_list);
+
+    // Check collateral ratio
+    if max_debt.gt(user_debt)? {
+        return Err(ErrorCode::InvalidLiquidation.into());
+    }
+
+    // Cannot payback more than close factor of user debt, which rises as health drops
+    // If user debt is below 1 USD max_repay covers entire debt
+    let close_factor = calculate_close_factor(
+        user_debt,
+        max_debt,
+        state.liquidation_rate,
+        state.full_liquidation_health,
+    );
+    let max_repay = calculate_max_repay(user_debt, close_factor);
+
+    let amount: u64 = match amount {
+        u64::MAX => max_repay,
+        _ => amount,
+    };
+    // Amount to repay must be less or equal max_repay
+    require!(amount.le(&max_repay), InvalidLiquidation);
+
+    let (assets, collaterals, _) = assets_list.split_borrow();
+
+    // finding collateral also validate reserve_account.mint, liquidation_fund.mint, liquidator_collateral_account.mint
+    let liquidated_collateral = match collaterals.iter_mut().find(|x| {
+        x.collateral_address.eq(&reserve_account.mint)
+            && x.collateral_address.eq(&liquidation_fund.mint)
+            && x.collateral_address.eq(&liquidator_collateral_account.mint)
+    }) {
+        Some(v) => v,
+        None => return Err(ErrorCode::NoAssetFound.into()),
+    };
+
+    let liquidated_asset = &assets[liquidated_collateral.asset_index as usize];
+    let liquidation_amount_preflight = Decimal {
+        val: amount.into(),
+        scale: XUSD_SCALE,
+    };
+
+    // Collateral can override global penalties
+    let (penalty_to_liquidator, penalty_to_exchange) =
+        liquidated_collateral.liquidation_penalties(state);
+    // Liquidator penalty rises with slots passed since liquidation_deadline
+    let penalty_to_liquidator = calculate_liquidator_penalty(
+        penalty_to_liquidator,
+        state.max_penalty_to_liquidator,
+        state.liquidation_auction_duration,
+        exchange_account.liquidation_deadline,
+        slot,
+    );
+
+    let exchange_account_collateral_index =
+        match exchange_account.collaterals.iter().position(|x| {
+            x.collateral_address
+                .eq(&liquidated_collateral.collateral_address)
+        }) {
+            Some(v) => v,
+            None => return Err(ErrorCode::NoAssetFound.into()),
+        };
+
+    let (seized_collateral_in_token, liquidation_amount) = calculate_seized_collateral(
+        liquidation_amount_preflight,
+        penalty_to_liquidator.add(penalty_to_exchange).unwrap(),
+        liquidated_asset,
+        Decimal {
+            val: exchange_account.collaterals[exchange_account_collateral_index]
+                .amount
+                .into(),
+            scale: liquidated_collateral.reserve_balance.scale,
+        },
+    );
+    // Rounding down - debt is burned in favor of the system
+
+    let burned_debt_shares = amount_to_shares_by_rounding_down(
+        state.debt_shares,
+        total_debt.to_u64(),
+        liquidation_amount.to_u64(),
+    );
+    state.debt_shares = state.debt_shares.checked_sub(burned_debt_shares).unwrap();
+
+    exchange_account.debt_shares = exchange_account
+        .debt_shares
+        .checked_sub(burned_debt_shares)
+        .unwrap();
+
+    let exchange_account_collateral =
+        &mut exchange_account.collaterals[exchange_account_collateral_index];
+
+    exchange_account_collateral.amount = exchange_account_collateral
+        .amount
+        .checked_sub(seized_collateral_in_token.to_u64())
+        .unwrap();
+    liquidated_collateral.reserve_balance = liquidated_collateral
+        .reserve_balance
+        .sub(seized_collateral_in_token)
+        .unwrap();
+
+    let (collateral_to_liquidator, collateral_to_exchange) = calculate_liquidation_penalty_split(
+        seized_collateral_in_token,
+        penalty_to_liquidator,
+        penalty_to_exchange,
+    );
+
+    // Part of exchange penalty is sold to liquidator for xUSD feeding insurance fund
+    let collateral_to_insurance = collateral_to_exchange.mul(state.insurance_fund_ratio);
+    let collateral_to_exchange = collateral_to_exchange.sub(collateral_to_insurance).unwrap();
+    let insurance_amount = calculate_value_in_usd(liquidated_asset.price, collateral_to_insurance);
+    state.insurance_fund = state.insurance_fund.add(insurance_amount).unwrap();
+
+    let seeds = &[SYNTHETIFY_EXCHANGE_SEED.as_bytes(), &[state.nonce]];
+    let signer_seeds = &[&seeds[..]];
+
+    {
+        // transfer collateral to liquidator
+        let liquidator_accounts = Transfer {
+            from: accounts.reserve_account.to_account_info(),
+            to: accounts.liquidator_collateral_account.to_account_info(),
+            authority: accounts.exchange_authority.to_account_info(),
+        };
+        let token_program = accounts.token_program.to_account_info();
+        let transfer =
+            CpiContext::new(token_program, liquidator_accounts).with_signer(signer_seeds);
+        token::transfer(
+            transfer,
+            collateral_to_liquidator
+                .add(collateral_to_insurance)
+                .unwrap()
+                .to_u64(),
+        )?;
+    }
+    {
+        if !accounts
+            .liquidation_fund
+            .to_account_info()
+            .key
+            .eq(&liquidated_collateral.liquidation_fund)
+        {
+            return Err(ErrorCode::ExchangeLiquidationAccount.into());
+        }
+        // transfer collateral to liquidation_account
+        let exchange_accounts = Transfer {
+            from: accounts.reserve_account.to_account_info(),
+            to: accounts.liquidation_fund.to_account_info(),
+            authority: accounts.exchange_authority.to_account_info(),
+        };
+        let token_program = accounts.token_program.to_account_info();
+        let transfer = CpiContext::new(token_program, exchange_accounts).with_signer(signer_seeds);
+        token::transfer(transfer, collateral_to_exchange.try_into().unwrap())?;
+    }
+    // burn xUSD, insurance part stays in supply as insurance fund
+    let new_supply = assets_list.synthetics[0]
+        .supply
+        .sub(liquidation_amount)
+        .unwrap();
+    assets_list.synthetics[0].set_supply_safely(new_supply)?;
+    let repay_amount = liquidation_amount.add(insurance_amount).unwrap().to_u64();
+
+    // Flash liquidator burns xUSD later in transaction
+    if !flash {
+        let burn_accounts = Burn {
+            mint: accounts.usd_token.to_account_info(),
+            to: accounts.liquidator_usd_account.to_account_info(),
+            authority: accounts.exchange_authority.to_account_info(),
+        };
+        let token_program = accounts.token_program.to_account_info();
+        let burn = CpiContext::new(token_program, burn_accounts).with_signer(signer_seeds);
+        token::burn(burn, repay_amount)?;
+    }
+    // Clean user collateral if empty
+    if exchange_account.collaterals[exchange_account_collateral_index].amount == 0 {
+        exchange_account.remove(exchange_account_collateral_index);
+    }
+
+    // Seized SNY and repaid debt change staking boost
+    let debt_after_liquidation = user_debt.sub(liquidation_amount).unwrap();
+    adjust_staking_boost(state, exchange_account, assets_list, debt_after_liquidation);
+
+    Ok(repay_amount)
+}
+
+// Returned by preview instructions, amounts in smallest units of tokens
+// (xUSD and collateral for debt pool, synthetic and collateral for vaults)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct LiquidationPreview {
+    pub liquidatable: bool,
+    pub max_repay: u64,         // max amount of debt repaid in single liquidation
+    pub repay_amount: u64,      // amount burned from liquidator
+    pub seized_collateral: u64, // collateral taken from position
+    pub collateral_to_liquidator: u64, // collateral received by liquidator
+    pub collateral_to_exchange: u64, // collateral sent to liquidation fund
+    pub debt_after: u64,        // debt of position after liquidation
+    pub max_debt_after: u64,    // debt limit of position after liquidation
+}
+
+#[event]
+pub struct BadDebtWriteOff {
+    pub exchange_account: Pubkey,
+    pub owner: Pubkey,
+    pub debt: u64,                 // xUSD debt of account at write off
+    pub covered_by_insurance: u64, // xUSD taken from insurance fund
+    pub socialized: u64,           // xUSD spread across debt pool
+    pub debt_shares: u64,          // burned debt shares of account
+}
+
 // some error code may be unused (future use)
 #[error]
 pub enum ErrorCode {
@@ -2650,6 +4716,26 @@ pub enum ErrorCode {
     InvalidExchangeAccount = 39,
     #[msg("Invalid oracle type")]
     InvalidOracleType = 40,
+    #[msg("Account is not migrated to current version")]
+    AccountNotMigrated = 41,
+    #[msg("Account is already migrated")]
+    AccountAlreadyMigrated = 42,
+    #[msg("Delegate has no permission")]
+    DelegatePermission = 43,
+    #[msg("Account still has collateral")]
+    AccountSolvent = 44,
+    #[msg("Flash liquidation already in progress")]
+    FlashLiquidationInProgress = 45,
+    #[msg("Flash liquidation is not repaid in transaction")]
+    FlashLiquidationNotRepaid = 46,
+    #[msg("No flash liquidation to repay")]
+    NoFlashLiquidation = 47,
+    #[msg("Invalid vault type")]
+    InvalidVaultType = 48,
+    #[msg("Vault entry still has debt or collateral")]
+    VaultEntryNotEmpty = 49,
+    #[msg("Deleverage is not triggered")]
+    DeleverageNotTriggered = 50,
 }
 
 // Access control modifiers.
@@ -2657,12 +4743,39 @@ pub enum ErrorCode {
 fn admin(state_loader: &Loader<State>, signer: &AccountInfo) -> Result<()> {
     let state = state_loader.load()?;
     require!(signer.key.eq(&state.admin), Unauthorized);
+    require!(state.version == STATE_VERSION, AccountNotMigrated);
     Ok(())
 }
-// Check if program is halted
+// Check if program is halted or state awaits migration
 fn halted<'info>(state_loader: &Loader<State>) -> Result<()> {
     let state = state_loader.load()?;
     require!(!state.halted, Halted);
+    require!(state.version == STATE_VERSION, AccountNotMigrated);
+    Ok(())
+}
+// Only owner or delegate with permission
+fn authorized(
+    exchange_account_loader: &Loader<ExchangeAccount>,
+    signer: &AccountInfo,
+    permission: u8,
+) -> Result<()> {
+    let exchange_account = exchange_account_loader.load()?;
+    require!(
+        exchange_account.owner.eq(signer.key) || exchange_account.delegate.eq(signer.key),
+        InvalidSigner
+    );
+    require!(
+        exchange_account.is_authorized(signer.key, permission),
+        DelegatePermission
+    );
+    Ok(())
+}
+// Check if exchange account layout is up to date
+fn exchange_account_migrated(exchange_account_loader: &Loader<ExchangeAccount>) -> Result<()> {
+    require!(
+        is_exchange_account_migrated(&exchange_account_loader.to_account_info())?,
+        AccountNotMigrated
+    );
     Ok(())
 }
 // Vault containers
@@ -2746,6 +4859,74 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_exchange_account_is_insolvent() {
+        // No debt
+        {
+            let exchange_account = ExchangeAccount {
+                ..Default::default()
+            };
+            assert!(!exchange_account.is_insolvent());
+        }
+        // Debt without collateral
+        {
+            let mut exchange_account = ExchangeAccount {
+                debt_shares: 10,
+                ..Default::default()
+            };
+            assert!(exchange_account.is_insolvent());
+            // Only emptied entries left
+            exchange_account.append(CollateralEntry {
+                amount: 0,
+                ..Default::default()
+            });
+            assert!(exchange_account.is_insolvent());
+        }
+        // Debt with collateral
+        {
+            let mut exchange_account = ExchangeAccount {
+                debt_shares: 10,
+                ..Default::default()
+            };
+            exchange_account.append(CollateralEntry {
+                amount: 1,
+                ..Default::default()
+            });
+            assert!(!exchange_account.is_insolvent());
+        }
+    }
+
+    #[test]
+    fn test_exchange_account_is_authorized() {
+        let owner = Pubkey::new_unique();
+        let delegate = Pubkey::new_unique();
+        // No delegate
+        {
+            let exchange_account = ExchangeAccount {
+                owner,
+                ..Default::default()
+            };
+            assert!(exchange_account.is_authorized(&owner, DELEGATE_ALL));
+            assert!(!exchange_account.is_authorized(&delegate, DELEGATE_DEPOSIT));
+            assert!(!exchange_account.is_authorized(&Pubkey::default(), 0));
+        }
+        // Delegate with partial permissions
+        {
+            let exchange_account = ExchangeAccount {
+                owner,
+                delegate,
+                delegate_permissions: DELEGATE_DEPOSIT | DELEGATE_BURN,
+                ..Default::default()
+            };
+            assert!(exchange_account.is_authorized(&delegate, DELEGATE_DEPOSIT));
+            assert!(exchange_account.is_authorized(&delegate, DELEGATE_BURN));
+            assert!(!exchange_account.is_authorized(&delegate, DELEGATE_MINT));
+            assert!(!exchange_account.is_authorized(&delegate, DELEGATE_WITHDRAW));
+            assert!(!exchange_account.is_authorized(&delegate, DELEGATE_CLAIM_REWARDS));
+            assert!(exchange_account.is_authorized(&owner, DELEGATE_WITHDRAW));
+        }
+    }
+
     #[test]
     fn test_assets_list_appending() {
         // Freshly created
@@ -1,8 +1,9 @@
+use std::convert::TryInto;
 use std::ops::Neg;
-use std::{cell::RefMut, convert::TryInto};
 
 use crate::decimal::{
-    Add, Compare, Div, DivScale, DivUp, Mul, MulUp, PowAccuracy, Sub, XUSD_SCALE,
+    Add, Compare, Div, DivScale, DivUp, Mul, MulUp, PowAccuracy, Sub, REWARD_PER_SHARE_SCALE,
+    UNIFIED_PERCENT_SCALE, XUSD_SCALE,
 };
 use crate::*;
 
@@ -13,7 +14,7 @@ pub const MIN_SWAP_USD_VALUE: Decimal = Decimal {
 };
 
 pub fn calculate_debt(
-    assets_list: &RefMut<AssetsList>,
+    assets_list: &AssetsList,
     slot: u64,
     max_delay: u32,
     twap: bool,
@@ -259,6 +260,65 @@ pub fn calculate_debt_interest_rate(debt_interest_rate: u16) -> Decimal {
 pub fn calculate_minute_interest_rate(apr: Decimal) -> Decimal {
     Decimal::from_interest_rate(apr.val.checked_div(MINUTES_IN_YEAR.into()).unwrap())
 }
+// Share of vault max_borrow already minted, capped at 100%
+pub fn calculate_vault_utilization(mint_amount: Decimal, max_borrow: Decimal) -> Decimal {
+    let full = Decimal::from_percent(100);
+    if max_borrow.val == 0 {
+        return full;
+    }
+    let utilization = mint_amount.div_to_scale(max_borrow, full.scale);
+    match utilization.gt(full).unwrap() {
+        true => full,
+        false => utilization,
+    }
+}
+// Rate grows linearly from base_rate to rate_at_optimal at optimal_utilization
+// and then steeper up to max_rate at full utilization
+pub fn calculate_utilization_interest_rate(
+    utilization: Decimal,
+    base_rate: Decimal,
+    optimal_utilization: Decimal,
+    rate_at_optimal: Decimal,
+    max_rate: Decimal,
+) -> Decimal {
+    if utilization.lte(optimal_utilization).unwrap() {
+        let increase = rate_at_optimal
+            .sub(base_rate)
+            .unwrap()
+            .mul(utilization)
+            .div(optimal_utilization);
+        base_rate.add(increase).unwrap()
+    } else {
+        let excess_utilization = utilization.sub(optimal_utilization).unwrap();
+        let excess_range = Decimal::from_percent(100).sub(optimal_utilization).unwrap();
+        let increase = max_rate
+            .sub(rate_at_optimal)
+            .unwrap()
+            .mul(excess_utilization)
+            .div(excess_range);
+        rate_at_optimal.add(increase).unwrap()
+    }
+}
+// Value of collateral to sell through swapline so debt drops to target ratio of collateral value
+// Sold value repays debt reduced by swap_fee and keeper_fee is taken from collateral on top of it
+pub fn calculate_deleverage_value(
+    debt_value: Decimal,
+    collateral_value: Decimal,
+    target_ratio: Decimal,
+    swap_fee: Decimal,
+    keeper_fee: Decimal,
+) -> Result<Decimal> {
+    // (debt - target * collateral) / (1 - swap_fee - target * (1 + keeper_fee))
+    let one = Decimal::from_percent(100);
+    let target_debt_value = collateral_value.mul(target_ratio);
+    if debt_value.lte(target_debt_value)? {
+        return Ok(Decimal::new(0, debt_value.scale));
+    }
+    let excess_debt_value = debt_value.sub(target_debt_value)?;
+    let lost_ratio = target_ratio.mul(one.add(keeper_fee)?).add(swap_fee)?;
+    require!(lost_ratio.lt(one)?, ParameterOutOfRange);
+    Ok(excess_debt_value.div(one.sub(lost_ratio)?))
+}
 pub fn calculate_vault_max_borrow_based_max_debt(max_debt: Decimal, open_fee: Decimal) -> Decimal {
     let open_factor = open_fee.add(Decimal::from_percent(100)).unwrap();
     max_debt.div(open_factor)
@@ -299,10 +359,312 @@ pub fn calculate_vault_withdraw_limit(
         max_withdraw_value.div_to_scale(collateral_price, collateral_amount.scale);
     return Ok(max_withdraw_amount);
 }
+// Summed limits of basket collaterals of vault entry, prices are given in order of vault collaterals
+// Collateral of skipped index is not counted
+pub fn calculate_vault_basket_borrow_limit(
+    vault: &Vault,
+    vault_entry: &VaultEntry,
+    prices: &[Decimal],
+    synthetic_asset: Asset,
+    synthetic: Synthetic,
+    liquidation: bool,
+    skip: Option<usize>,
+) -> Decimal {
+    let mut borrow_limit = Decimal::new(0, synthetic.supply.scale);
+    for (index, vault_collateral) in vault.collaterals[..vault.head_collaterals as usize]
+        .iter()
+        .enumerate()
+    {
+        if skip == Some(index) {
+            continue;
+        }
+        let collateral_ratio = match liquidation {
+            true => vault_collateral.liquidation_threshold,
+            false => vault_collateral.collateral_ratio,
+        };
+        let collateral_limit = calculate_vault_borrow_limit(
+            prices[index],
+            synthetic_asset,
+            synthetic,
+            vault_entry.collateral_amounts[index],
+            collateral_ratio,
+        );
+        borrow_limit = borrow_limit.add(collateral_limit).unwrap();
+    }
+    borrow_limit
+}
+// Part of debt which has to be covered by single collateral of basket
+pub fn calculate_vault_uncovered_debt(synthetic_amount: Decimal, other_limit: Decimal) -> Decimal {
+    match other_limit.gte(synthetic_amount).unwrap() {
+        true => Decimal::new(0, synthetic_amount.scale),
+        false => synthetic_amount.sub(other_limit).unwrap(),
+    }
+}
+// Share of debt repaid in single liquidation, rises linearly from liquidation_rate
+// at health of 100% (max_debt equal to user_debt) to 100% at full_liquidation_health
+pub fn calculate_close_factor(
+    user_debt: Decimal,
+    max_debt: Decimal,
+    liquidation_rate: Decimal,
+    full_liquidation_health: Decimal,
+) -> Decimal {
+    // Zero full_liquidation_health keeps fixed liquidation_rate
+    if full_liquidation_health.val == 0 || user_debt.val == 0 {
+        return liquidation_rate;
+    }
+    let full = Decimal::from_percent(100);
+    let health = max_debt.div_to_scale(user_debt, UNIFIED_PERCENT_SCALE);
+    if health.gte(full).unwrap() {
+        return liquidation_rate;
+    }
+    if health.lte(full_liquidation_health).unwrap() {
+        return full;
+    }
+    let increase = full
+        .sub(liquidation_rate)
+        .unwrap()
+        .mul(full.sub(health).unwrap())
+        .div(full.sub(full_liquidation_health).unwrap());
+    liquidation_rate.add(increase).unwrap()
+}
+// Cannot payback more than liquidation_rate of user debt
+// If user debt is below 1 USD we can liquidate entire debt
+pub fn calculate_max_repay(user_debt: Decimal, liquidation_rate: Decimal) -> u64 {
+    match user_debt.lte(Decimal::from_integer(1).to_usd()).unwrap() {
+        true => user_debt.to_usd().to_u64(),
+        false => user_debt.mul(liquidation_rate).to_usd().to_u64(),
+    }
+}
+// Growth of reward per debt share over given slots
+// amount_per_round is spread evenly across slots of round
+pub fn calculate_reward_per_share_increase(
+    amount_per_round: Decimal,
+    round_length: u32,
+    slots: u64,
+    debt_shares: u64,
+) -> Decimal {
+    // Nothing is distributed without stakers
+    if round_length == 0 || debt_shares == 0 {
+        return Decimal::new(0, REWARD_PER_SHARE_SCALE);
+    }
+    let scale_diff = REWARD_PER_SHARE_SCALE
+        .checked_sub(amount_per_round.scale)
+        .unwrap();
+    let increase = amount_per_round
+        .val
+        .checked_mul(slots.into())
+        .unwrap()
+        .checked_mul(10u128.pow(scale_diff.into()))
+        .unwrap()
+        .checked_div(
+            u128::from(round_length)
+                .checked_mul(debt_shares.into())
+                .unwrap(),
+        )
+        .unwrap();
+    Decimal::new(increase, REWARD_PER_SHARE_SCALE)
+}
+// Rounding down - rewards are paid in favor of the system
+pub fn calculate_staking_reward(
+    debt_shares: u64,
+    reward_per_share: Decimal,
+    last_reward_per_share: Decimal,
+    scale: u8,
+) -> Decimal {
+    let reward_per_share_diff = reward_per_share.sub(last_reward_per_share).unwrap();
+    Decimal::new(
+        reward_per_share_diff
+            .val
+            .checked_mul(debt_shares.into())
+            .unwrap(),
+        REWARD_PER_SHARE_SCALE,
+    )
+    .to_scale(scale)
+}
+// Boost grows linearly with value of SNY collateral up to max_staking_boost
+// reached when SNY collateral covers whole debt
+pub fn calculate_staking_boost(
+    sny_collateral_value: Decimal,
+    user_debt: Decimal,
+    max_staking_boost: Decimal,
+) -> Decimal {
+    if user_debt.val == 0 || sny_collateral_value.gte(user_debt).unwrap() {
+        return max_staking_boost;
+    }
+    max_staking_boost.mul(sny_collateral_value).div(user_debt)
+}
+// SNY rewards are split by boosted shares, so boost changes share of account
+// without changing amount distributed
+pub fn calculate_boosted_debt_shares(debt_shares: u64, staking_boost: Decimal) -> u64 {
+    let extra_shares: u64 = Decimal::from_integer(debt_shares)
+        .mul(staking_boost)
+        .val
+        .try_into()
+        .unwrap();
+    debt_shares.checked_add(extra_shares).unwrap()
+}
+// Part of vesting_amount unlocked linearly between vesting_start and vesting_end
+pub fn calculate_vested_amount(
+    vesting_amount: u64,
+    vesting_start: u64,
+    vesting_end: u64,
+    slot: u64,
+) -> u64 {
+    if slot >= vesting_end {
+        return vesting_amount;
+    }
+    if slot <= vesting_start {
+        return 0;
+    }
+    u128::from(vesting_amount)
+        .checked_mul(slot.checked_sub(vesting_start).unwrap().into())
+        .unwrap()
+        .checked_div(vesting_end.checked_sub(vesting_start).unwrap().into())
+        .unwrap()
+        .try_into()
+        .unwrap()
+}
+// Merged schedule ends after average of remaining vesting times weighted by amounts
+pub fn calculate_vesting_end(
+    remaining_amount: u64,
+    vesting_end: u64,
+    new_amount: u64,
+    vesting_duration: u32,
+    slot: u64,
+) -> u64 {
+    let total_amount = u128::from(remaining_amount)
+        .checked_add(new_amount.into())
+        .unwrap();
+    if total_amount == 0 {
+        return slot;
+    }
+    let remaining_time = vesting_end.saturating_sub(slot);
+    let weighted_time = u128::from(remaining_amount)
+        .checked_mul(remaining_time.into())
+        .unwrap()
+        .checked_add(
+            u128::from(new_amount)
+                .checked_mul(vesting_duration.into())
+                .unwrap(),
+        )
+        .unwrap()
+        .checked_div(total_amount)
+        .unwrap();
+    slot.checked_add(weighted_time.try_into().unwrap()).unwrap()
+}
+// Start of linear schedule ending at vesting_end with vested_amount already vested at slot
+pub fn calculate_vesting_start(
+    vested_amount: u64,
+    remaining_amount: u64,
+    vesting_end: u64,
+    slot: u64,
+) -> u64 {
+    if remaining_amount == 0 {
+        return slot;
+    }
+    // Rounding down - later start vests less in favor of the system
+    let elapsed = u128::from(vested_amount)
+        .checked_mul(vesting_end.saturating_sub(slot).into())
+        .unwrap()
+        .checked_div(remaining_amount.into())
+        .unwrap();
+    slot.saturating_sub(elapsed.try_into().unwrap_or(u64::MAX))
+}
+// Returns (seized_collateral_in_token, liquidation_amount)
+// If user collateral is insufficient whole collateral is seized
+pub fn calculate_seized_collateral(
+    liquidation_amount: Decimal,
+    penalty: Decimal,
+    liquidated_asset: &Asset,
+    user_collateral: Decimal,
+) -> (Decimal, Decimal) {
+    let seized_collateral_in_usd = liquidation_amount
+        .mul_up(penalty)
+        .add(liquidation_amount)
+        .unwrap();
+    let seized_collateral_in_token = usd_to_token_amount(
+        liquidated_asset,
+        seized_collateral_in_usd,
+        user_collateral.scale,
+    );
+
+    match seized_collateral_in_token.lte(user_collateral).unwrap() {
+        true => (seized_collateral_in_token, liquidation_amount),
+        false => (
+            user_collateral,
+            calculate_value_in_usd(liquidated_asset.price, user_collateral),
+        ),
+    }
+}
+pub fn calculate_vault_seized_collateral(
+    liquidation_amount: Decimal,
+    penalty: Decimal,
+    synthetic_price: Decimal,
+    collateral_price: Decimal,
+    collateral_decimals: u8,
+) -> Decimal {
+    let seized_collateral_in_usd = liquidation_amount
+        .mul_up(penalty)
+        .add(liquidation_amount)
+        .unwrap()
+        .mul(synthetic_price);
+    seized_collateral_in_usd.div_to_scale(collateral_price, collateral_decimals)
+}
+// Split seized collateral into (to_liquidator, to_exchange)
+pub fn calculate_liquidation_penalty_split(
+    seized_collateral: Decimal,
+    penalty_to_liquidator: Decimal,
+    penalty_to_exchange: Decimal,
+) -> (Decimal, Decimal) {
+    let collateral_to_exchange = seized_collateral.mul(penalty_to_exchange).div_up(
+        Decimal::from_percent(100)
+            .add(penalty_to_liquidator)
+            .unwrap()
+            .add(penalty_to_exchange)
+            .unwrap(),
+    );
+    let collateral_to_liquidator = seized_collateral.sub(collateral_to_exchange).unwrap();
+    (collateral_to_liquidator, collateral_to_exchange)
+}
+pub fn calculate_liquidator_penalty(
+    penalty_to_liquidator: Decimal,
+    max_penalty_to_liquidator: Decimal,
+    auction_duration: u32,
+    liquidation_deadline: u64,
+    slot: u64,
+) -> Decimal {
+    // Auction disabled, fixed penalty
+    if auction_duration == 0
+        || max_penalty_to_liquidator
+            .lte(penalty_to_liquidator)
+            .unwrap()
+    {
+        return penalty_to_liquidator;
+    }
+    // Penalty rises linearly from penalty_to_liquidator to max_penalty_to_liquidator
+    let elapsed = slot
+        .saturating_sub(liquidation_deadline)
+        .min(auction_duration.into());
+    let increase = max_penalty_to_liquidator
+        .sub(penalty_to_liquidator)
+        .unwrap()
+        .val
+        .checked_mul(elapsed.into())
+        .unwrap()
+        .checked_div(auction_duration.into())
+        .unwrap();
+    penalty_to_liquidator
+        .add(Decimal {
+            val: increase,
+            scale: penalty_to_liquidator.scale,
+        })
+        .unwrap()
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::decimal::SNY_SCALE;
     use std::{cell::RefCell, ops::Div};
 
     #[test]
@@ -1564,6 +1926,219 @@ mod tests {
         assert_eq!(borrow_limit, expected_borrow_limit);
     }
     #[test]
+    fn test_calculate_vault_basket_borrow_limit() {
+        let xusd_asset = Asset {
+            price: Decimal::from_integer(1).to_price(),
+            ..Default::default()
+        };
+        let xusd_synthetic = Synthetic {
+            supply: Decimal::from_integer(100).to_usd(),
+            ..Default::default()
+        };
+        let mut vault = Vault {
+            head_collaterals: 2,
+            ..Default::default()
+        };
+        vault.collaterals[0] = VaultCollateral {
+            collateral_ratio: Decimal::from_percent(70),
+            liquidation_threshold: Decimal::from_percent(80),
+            ..Default::default()
+        };
+        vault.collaterals[1] = VaultCollateral {
+            collateral_ratio: Decimal::from_percent(50),
+            liquidation_threshold: Decimal::from_percent(60),
+            ..Default::default()
+        };
+        let mut vault_entry = VaultEntry {
+            ..Default::default()
+        };
+        vault_entry.collateral_amounts[0] = Decimal::from_integer(2).to_scale(8);
+        vault_entry.collateral_amounts[1] = Decimal::from_integer(100).to_scale(9);
+        let prices = [
+            Decimal::from_integer(50).to_price(),
+            Decimal::from_integer(2).to_price(),
+        ];
+
+        // 100 * 70% + 200 * 50%
+        let borrow_limit = calculate_vault_basket_borrow_limit(
+            &vault,
+            &vault_entry,
+            &prices,
+            xusd_asset,
+            xusd_synthetic,
+            false,
+            None,
+        );
+        assert_eq!(borrow_limit, Decimal::from_integer(170).to_usd());
+
+        // 100 * 80% + 200 * 60%
+        let liquidation_limit = calculate_vault_basket_borrow_limit(
+            &vault,
+            &vault_entry,
+            &prices,
+            xusd_asset,
+            xusd_synthetic,
+            true,
+            None,
+        );
+        assert_eq!(liquidation_limit, Decimal::from_integer(200).to_usd());
+
+        // Without second collateral
+        let other_limit = calculate_vault_basket_borrow_limit(
+            &vault,
+            &vault_entry,
+            &prices,
+            xusd_asset,
+            xusd_synthetic,
+            false,
+            Some(1),
+        );
+        assert_eq!(other_limit, Decimal::from_integer(70).to_usd());
+
+        // Zeroed entry of collateral added later
+        vault_entry.collateral_amounts[1] = Decimal::default();
+        let borrow_limit = calculate_vault_basket_borrow_limit(
+            &vault,
+            &vault_entry,
+            &prices,
+            xusd_asset,
+            xusd_synthetic,
+            false,
+            None,
+        );
+        assert_eq!(borrow_limit, Decimal::from_integer(70).to_usd());
+
+        // Standard vault
+        let borrow_limit = calculate_vault_basket_borrow_limit(
+            &Vault::default(),
+            &vault_entry,
+            &[],
+            xusd_asset,
+            xusd_synthetic,
+            false,
+            None,
+        );
+        assert_eq!(borrow_limit, Decimal::from_usd(0));
+    }
+    #[test]
+    fn test_calculate_vault_uncovered_debt() {
+        let synthetic_amount = Decimal::from_integer(100).to_usd();
+        assert_eq!(
+            calculate_vault_uncovered_debt(synthetic_amount, Decimal::from_integer(30).to_usd()),
+            Decimal::from_integer(70).to_usd()
+        );
+        assert_eq!(
+            calculate_vault_uncovered_debt(synthetic_amount, Decimal::from_integer(100).to_usd()),
+            Decimal::from_usd(0)
+        );
+        assert_eq!(
+            calculate_vault_uncovered_debt(synthetic_amount, Decimal::from_integer(130).to_usd()),
+            Decimal::from_usd(0)
+        );
+    }
+    #[test]
+    fn test_calculate_vault_utilization() {
+        let max_borrow = Decimal::from_integer(200).to_usd();
+        assert_eq!(
+            calculate_vault_utilization(Decimal::from_integer(50).to_usd(), max_borrow),
+            Decimal::from_percent(25)
+        );
+        assert_eq!(
+            calculate_vault_utilization(Decimal::from_usd(0), max_borrow),
+            Decimal::from_percent(0)
+        );
+        // Max borrow lowered below minted amount
+        assert_eq!(
+            calculate_vault_utilization(Decimal::from_integer(300).to_usd(), max_borrow),
+            Decimal::from_percent(100)
+        );
+        assert_eq!(
+            calculate_vault_utilization(Decimal::from_usd(0), Decimal::from_usd(0)),
+            Decimal::from_percent(100)
+        );
+    }
+    #[test]
+    fn test_calculate_utilization_interest_rate() {
+        let base_rate = Decimal::from_percent(1).to_interest_rate();
+        let optimal_utilization = Decimal::from_percent(80);
+        let rate_at_optimal = Decimal::from_percent(5).to_interest_rate();
+        let max_rate = Decimal::from_percent(25).to_interest_rate();
+        let rate = |utilization| {
+            calculate_utilization_interest_rate(
+                utilization,
+                base_rate,
+                optimal_utilization,
+                rate_at_optimal,
+                max_rate,
+            )
+        };
+
+        assert_eq!(rate(Decimal::from_percent(0)), base_rate);
+        // 1% + 4% * 40 / 80
+        assert_eq!(
+            rate(Decimal::from_percent(40)),
+            Decimal::from_percent(3).to_interest_rate()
+        );
+        assert_eq!(rate(Decimal::from_percent(80)), rate_at_optimal);
+        // 5% + 20% * 10 / 20
+        assert_eq!(
+            rate(Decimal::from_percent(90)),
+            Decimal::from_percent(15).to_interest_rate()
+        );
+        assert_eq!(rate(Decimal::from_percent(100)), max_rate);
+    }
+    #[test]
+    fn test_calculate_deleverage_value() {
+        let collateral_value = Decimal::from_integer(100).to_usd();
+        let target_ratio = Decimal::from_percent(50);
+        let no_fee = Decimal::from_percent(0);
+        // (70 - 50) / (1 - 0.5)
+        assert_eq!(
+            calculate_deleverage_value(
+                Decimal::from_integer(70).to_usd(),
+                collateral_value,
+                target_ratio,
+                no_fee,
+                no_fee
+            )
+            .unwrap(),
+            Decimal::from_integer(40).to_usd()
+        );
+        // (70 - 50) / (1 - 0.5 * 1.2 - 0.2)
+        assert_eq!(
+            calculate_deleverage_value(
+                Decimal::from_integer(70).to_usd(),
+                collateral_value,
+                target_ratio,
+                Decimal::from_percent(20),
+                Decimal::from_percent(20)
+            )
+            .unwrap(),
+            Decimal::from_integer(100).to_usd()
+        );
+        // Already below target
+        assert_eq!(
+            calculate_deleverage_value(
+                Decimal::from_integer(40).to_usd(),
+                collateral_value,
+                target_ratio,
+                no_fee,
+                no_fee
+            )
+            .unwrap(),
+            Decimal::from_usd(0)
+        );
+        // Fees exceed what is repaid
+        assert!(calculate_deleverage_value(
+            Decimal::from_integer(70).to_usd(),
+            collateral_value,
+            target_ratio,
+            Decimal::from_percent(50),
+            no_fee
+        )
+        .is_err());
+    }
+    #[test]
     fn test_calculate_vault_max_borrow_based_max_debt() {
         // accuracy trunc
         {
@@ -1678,4 +2253,397 @@ mod tests {
         assert_eq!({ actual.val }, { expected.val });
         assert_eq!(actual.scale, expected.scale);
     }
+
+    #[test]
+    fn test_calculate_liquidator_penalty() {
+        let penalty = Decimal::from_percent(5);
+        let max_penalty = Decimal::from_percent(15);
+        let deadline = 1000;
+        // Auction disabled
+        {
+            let result = calculate_liquidator_penalty(penalty, max_penalty, 0, deadline, 5000);
+            assert_eq!(result, penalty);
+        }
+        // Max penalty below base penalty
+        {
+            let result = calculate_liquidator_penalty(
+                penalty,
+                Decimal::from_percent(2),
+                100,
+                deadline,
+                5000,
+            );
+            assert_eq!(result, penalty);
+        }
+        // Start of auction
+        {
+            let result = calculate_liquidator_penalty(penalty, max_penalty, 100, deadline, 1000);
+            assert_eq!(result, penalty);
+        }
+        // Middle of auction
+        {
+            let result = calculate_liquidator_penalty(penalty, max_penalty, 100, deadline, 1050);
+            assert_eq!(result, Decimal::from_percent(10));
+        }
+        {
+            let result = calculate_liquidator_penalty(penalty, max_penalty, 300, deadline, 1100);
+            assert_eq!(result, Decimal::from_unified_percent(8333));
+        }
+        // Capped after auction
+        {
+            let result = calculate_liquidator_penalty(penalty, max_penalty, 100, deadline, 9999);
+            assert_eq!(result, max_penalty);
+        }
+    }
+
+    #[test]
+    fn test_calculate_liquidation_penalty_split() {
+        // 100 + 5% + 5%
+        {
+            let seized = Decimal::from_integer(110).to_scale(6);
+            let (to_liquidator, to_exchange) = calculate_liquidation_penalty_split(
+                seized,
+                Decimal::from_percent(5),
+                Decimal::from_percent(5),
+            );
+            assert_eq!(to_exchange, Decimal::from_integer(5).to_scale(6));
+            assert_eq!(to_liquidator, Decimal::from_integer(105).to_scale(6));
+        }
+        // No exchange penalty
+        {
+            let seized = Decimal::from_integer(110).to_scale(6);
+            let (to_liquidator, to_exchange) = calculate_liquidation_penalty_split(
+                seized,
+                Decimal::from_percent(10),
+                Decimal::from_percent(0),
+            );
+            assert_eq!(to_exchange, Decimal::from_integer(0).to_scale(6));
+            assert_eq!(to_liquidator, seized);
+        }
+        // Rounding in favor of exchange
+        {
+            let seized = Decimal { val: 111, scale: 6 };
+            let (to_liquidator, to_exchange) = calculate_liquidation_penalty_split(
+                seized,
+                Decimal::from_percent(5),
+                Decimal::from_percent(5),
+            );
+            assert_eq!(to_exchange, Decimal { val: 5, scale: 6 });
+            assert_eq!(to_liquidator, Decimal { val: 106, scale: 6 });
+        }
+    }
+
+    #[test]
+    fn test_calculate_max_repay() {
+        let liquidation_rate = Decimal::from_percent(20);
+        // Debt below 1 USD can be repaid entirely
+        {
+            let user_debt = Decimal::from_usd(999_999);
+            assert_eq!(calculate_max_repay(user_debt, liquidation_rate), 999_999);
+        }
+        {
+            let user_debt = Decimal::from_integer(1000).to_usd();
+            assert_eq!(
+                calculate_max_repay(user_debt, liquidation_rate),
+                200_000_000
+            );
+        }
+    }
+
+    #[test]
+    fn test_calculate_reward_per_share_increase() {
+        let amount_per_round = Decimal::from_integer(100).to_sny();
+        // Half of round
+        {
+            let result = calculate_reward_per_share_increase(amount_per_round, 100, 50, 1_000);
+            // 50 SNY / 1000 shares
+            assert_eq!(result, Decimal::new(5, 2).to_scale(REWARD_PER_SHARE_SCALE));
+        }
+        // Many rounds
+        {
+            let result =
+                calculate_reward_per_share_increase(amount_per_round, 100, 1_000, 10u64.pow(12));
+            // 1000 SNY / 10^12 shares
+            assert_eq!(result, Decimal::new(1_000_000_000, REWARD_PER_SHARE_SCALE));
+        }
+        // Rounding down
+        {
+            let result = calculate_reward_per_share_increase(amount_per_round, 100, 1, 3);
+            assert_eq!(
+                result,
+                Decimal::new(333_333_333_333_333_333, REWARD_PER_SHARE_SCALE)
+            );
+        }
+        // No stakers
+        {
+            let result = calculate_reward_per_share_increase(amount_per_round, 100, 50, 0);
+            assert_eq!(result, Decimal::new(0, REWARD_PER_SHARE_SCALE));
+        }
+        // Zero round length
+        {
+            let result = calculate_reward_per_share_increase(amount_per_round, 0, 50, 1_000);
+            assert_eq!(result, Decimal::new(0, REWARD_PER_SHARE_SCALE));
+        }
+    }
+    #[test]
+    fn test_calculate_staking_reward() {
+        let last_reward_per_share = Decimal::new(5, 2).to_scale(REWARD_PER_SHARE_SCALE);
+        let reward_per_share = Decimal::new(15, 2).to_scale(REWARD_PER_SHARE_SCALE);
+        // 0.1 SNY per share
+        {
+            let result =
+                calculate_staking_reward(300, reward_per_share, last_reward_per_share, SNY_SCALE);
+            assert_eq!(result, Decimal::from_integer(30).to_sny());
+        }
+        // Nothing accrued
+        {
+            let result =
+                calculate_staking_reward(300, reward_per_share, reward_per_share, SNY_SCALE);
+            assert_eq!(result, Decimal::from_sny(0));
+        }
+        // No shares
+        {
+            let result =
+                calculate_staking_reward(0, reward_per_share, last_reward_per_share, SNY_SCALE);
+            assert_eq!(result, Decimal::from_sny(0));
+        }
+        // Rounding down
+        {
+            let reward_per_share = Decimal::new(333_333_333_333_333_333, REWARD_PER_SHARE_SCALE);
+            let result = calculate_staking_reward(
+                2,
+                reward_per_share,
+                Decimal::new(0, REWARD_PER_SHARE_SCALE),
+                SNY_SCALE,
+            );
+            assert_eq!(result, Decimal::from_sny(666_666));
+        }
+        // Token with different decimals
+        {
+            let result = calculate_staking_reward(300, reward_per_share, last_reward_per_share, 9);
+            assert_eq!(result, Decimal::from_integer(30).to_scale(9));
+        }
+    }
+    #[test]
+    fn test_calculate_vested_amount() {
+        // Before start
+        assert_eq!(calculate_vested_amount(1_000, 100, 200, 50), 0);
+        assert_eq!(calculate_vested_amount(1_000, 100, 200, 100), 0);
+        // Linear vesting
+        assert_eq!(calculate_vested_amount(1_000, 100, 200, 125), 250);
+        assert_eq!(calculate_vested_amount(1_000, 100, 200, 199), 990);
+        // Rounding down
+        assert_eq!(calculate_vested_amount(10, 100, 103, 101), 3);
+        // After end
+        assert_eq!(calculate_vested_amount(1_000, 100, 200, 200), 1_000);
+        assert_eq!(calculate_vested_amount(1_000, 100, 200, 1_000), 1_000);
+        // Empty schedule
+        assert_eq!(calculate_vested_amount(0, 100, 100, 100), 0);
+    }
+    #[test]
+    fn test_calculate_vesting_end() {
+        // New schedule
+        assert_eq!(calculate_vesting_end(0, 0, 1_000, 100, 500), 600);
+        // Equal amounts, 20 and 100 slots remaining
+        assert_eq!(calculate_vesting_end(1_000, 520, 1_000, 100, 500), 560);
+        // Nothing new, schedule unchanged
+        assert_eq!(calculate_vesting_end(1_000, 520, 0, 100, 500), 520);
+        // Schedule already ended
+        assert_eq!(calculate_vesting_end(0, 400, 0, 100, 500), 500);
+        assert_eq!(calculate_vesting_end(0, 400, 3_000, 100, 500), 600);
+    }
+    #[test]
+    fn test_calculate_vesting_start() {
+        // Nothing remaining
+        assert_eq!(calculate_vesting_start(1_000, 0, 500, 500), 500);
+        // A quarter vested
+        let start = calculate_vesting_start(100, 300, 800, 500);
+        assert_eq!(start, 400);
+        assert_eq!(calculate_vested_amount(400, start, 800, 500), 100);
+        // Rounding down
+        assert_eq!(calculate_vesting_start(100, 299, 800, 500), 400);
+        // Start cannot precede slot 0
+        let start = calculate_vesting_start(1_000, 1, 1_500, 500);
+        assert_eq!(start, 0);
+        assert!(calculate_vested_amount(1_001, start, 1_500, 500) < 1_000);
+    }
+    #[test]
+    fn test_calculate_staking_boost() {
+        let max_staking_boost = Decimal::from_percent(50);
+        // No SNY collateral
+        {
+            let boost = calculate_staking_boost(
+                Decimal::from_usd(0),
+                Decimal::from_integer(100).to_usd(),
+                max_staking_boost,
+            );
+            assert_eq!(boost, Decimal::from_percent(0));
+        }
+        // SNY covers 40% of debt
+        {
+            let boost = calculate_staking_boost(
+                Decimal::from_integer(40).to_usd(),
+                Decimal::from_integer(100).to_usd(),
+                max_staking_boost,
+            );
+            assert_eq!(boost, Decimal::from_percent(20));
+        }
+        // Rounding down
+        {
+            let boost = calculate_staking_boost(
+                Decimal::from_integer(1).to_usd(),
+                Decimal::from_integer(3).to_usd(),
+                max_staking_boost,
+            );
+            assert_eq!(boost, Decimal::new(16_666, UNIFIED_PERCENT_SCALE));
+        }
+        // Capped
+        {
+            let boost = calculate_staking_boost(
+                Decimal::from_integer(300).to_usd(),
+                Decimal::from_integer(100).to_usd(),
+                max_staking_boost,
+            );
+            assert_eq!(boost, max_staking_boost);
+        }
+        // Disabled
+        {
+            let boost = calculate_staking_boost(
+                Decimal::from_integer(40).to_usd(),
+                Decimal::from_integer(100).to_usd(),
+                Decimal::from_percent(0),
+            );
+            assert_eq!(boost, Decimal::from_percent(0));
+        }
+    }
+    #[test]
+    fn test_calculate_boosted_debt_shares() {
+        // No boost
+        {
+            let shares = calculate_boosted_debt_shares(1_000, Decimal::from_percent(0));
+            assert_eq!(shares, 1_000);
+        }
+        // Boost of 20%
+        {
+            let shares = calculate_boosted_debt_shares(1_000, Decimal::from_percent(20));
+            assert_eq!(shares, 1_200);
+        }
+        // Rounding down
+        {
+            let shares =
+                calculate_boosted_debt_shares(1_000, Decimal::new(16_666, UNIFIED_PERCENT_SCALE));
+            assert_eq!(shares, 1_166);
+        }
+        // Boost of account created before staking boost
+        {
+            let shares = calculate_boosted_debt_shares(1_000, Decimal::default());
+            assert_eq!(shares, 1_000);
+        }
+    }
+    #[test]
+    fn test_calculate_close_factor() {
+        let liquidation_rate = Decimal::from_percent(20);
+        let full_liquidation_health = Decimal::from_percent(50);
+        let user_debt = Decimal::from_integer(1000).to_usd();
+        // Disabled
+        {
+            let max_debt = Decimal::from_integer(100).to_usd();
+            let result = calculate_close_factor(
+                user_debt,
+                max_debt,
+                liquidation_rate,
+                Decimal::from_percent(0),
+            );
+            assert_eq!(result, liquidation_rate);
+        }
+        // Healthy account
+        {
+            let max_debt = Decimal::from_integer(1000).to_usd();
+            let result = calculate_close_factor(
+                user_debt,
+                max_debt,
+                liquidation_rate,
+                full_liquidation_health,
+            );
+            assert_eq!(result, liquidation_rate);
+        }
+        // Health of 90% - 20% + 80% * 10% / 50%
+        {
+            let max_debt = Decimal::from_integer(900).to_usd();
+            let result = calculate_close_factor(
+                user_debt,
+                max_debt,
+                liquidation_rate,
+                full_liquidation_health,
+            );
+            assert_eq!(result, Decimal::from_percent(36));
+        }
+        // Health of 75% - 20% + 80% * 25% / 50%
+        {
+            let max_debt = Decimal::from_integer(750).to_usd();
+            let result = calculate_close_factor(
+                user_debt,
+                max_debt,
+                liquidation_rate,
+                full_liquidation_health,
+            );
+            assert_eq!(result, Decimal::from_percent(60));
+        }
+        // Deeply underwater
+        {
+            let max_debt = Decimal::from_integer(500).to_usd();
+            let result = calculate_close_factor(
+                user_debt,
+                max_debt,
+                liquidation_rate,
+                full_liquidation_health,
+            );
+            assert_eq!(result, Decimal::from_percent(100));
+            assert_eq!(calculate_max_repay(user_debt, result), 1_000_000_000);
+        }
+        // No collateral
+        {
+            let max_debt = Decimal::from_usd(0);
+            let result = calculate_close_factor(
+                user_debt,
+                max_debt,
+                liquidation_rate,
+                full_liquidation_health,
+            );
+            assert_eq!(result, Decimal::from_percent(100));
+        }
+    }
+
+    #[test]
+    fn test_calculate_seized_collateral() {
+        let asset = Asset {
+            price: Decimal::from_integer(2).to_price(),
+            ..Default::default()
+        };
+        let liquidation_amount = Decimal::from_integer(100).to_usd();
+        let penalty = Decimal::from_percent(10);
+        // Enough collateral
+        {
+            let user_collateral = Decimal::from_integer(100).to_scale(6);
+            let (seized, repaid) =
+                calculate_seized_collateral(liquidation_amount, penalty, &asset, user_collateral);
+            assert_eq!(seized, Decimal::from_integer(55).to_scale(6));
+            assert_eq!(repaid, liquidation_amount);
+        }
+        // Whole collateral seized
+        {
+            let user_collateral = Decimal::from_integer(50).to_scale(6);
+            let (seized, repaid) =
+                calculate_seized_collateral(liquidation_amount, penalty, &asset, user_collateral);
+            assert_eq!(seized, user_collateral);
+            assert_eq!(repaid, liquidation_amount);
+        }
+        {
+            let user_collateral = Decimal::from_integer(20).to_scale(6);
+            let (seized, repaid) =
+                calculate_seized_collateral(liquidation_amount, penalty, &asset, user_collateral);
+            assert_eq!(seized, user_collateral);
+            assert_eq!(repaid, Decimal::from_integer(40).to_usd());
+        }
+    }
 }
@@ -0,0 +1,449 @@
+use std::mem::size_of;
+
+use anchor_lang::Discriminator;
+
+use crate::decimal::{Add, REWARD_PER_SHARE_SCALE};
+use crate::*;
+use account::*;
+
+// Current layout versions, bump together with a new migration step below
+pub const EXCHANGE_ACCOUNT_VERSION: u8 = 3;
+pub const STATE_VERSION: u8 = 11;
+
+// AssetsList before per collateral liquidation penalties (148 bytes per Collateral)
+pub const ASSETS_LIST_V0_SIZE: usize = 8 + 93333;
+pub const ASSETS_LIST_SIZE: usize = 8 + size_of::<AssetsList>();
+const COLLATERAL_V0_SIZE: usize = 148;
+
+// Vault and VaultEntry before basket collaterals, new fields are appended
+// and zeroed by realloc, only Vault interest rate needs to be set afterwards
+pub const VAULT_V0_SIZE: usize = 8 + 376;
+pub const VAULT_SIZE: usize = 8 + size_of::<Vault>();
+pub const VAULT_ENTRY_V0_SIZE: usize = 8 + 116;
+pub const VAULT_ENTRY_SIZE: usize = 8 + size_of::<VaultEntry>();
+
+// Offset of ExchangeAccount.version (8 discriminator + 32 owner)
+const EXCHANGE_ACCOUNT_VERSION_OFFSET: usize = 40;
+// Size of initial ExchangeAccount layout (without reserved space)
+const EXCHANGE_ACCOUNT_V0_SIZE: usize = 8 + 1412;
+
+pub fn exchange_account_size(version: u8) -> usize {
+    match version {
+        0 => EXCHANGE_ACCOUNT_V0_SIZE,
+        _ => 8 + size_of::<ExchangeAccount>(),
+    }
+}
+pub fn read_exchange_account_version(account_info: &AccountInfo) -> Result<u8> {
+    let data = account_info.try_borrow_data()?;
+    require!(
+        data.len() > EXCHANGE_ACCOUNT_VERSION_OFFSET
+            && data[..8] == ExchangeAccount::discriminator(),
+        InvalidExchangeAccount
+    );
+    let version = data[EXCHANGE_ACCOUNT_VERSION_OFFSET];
+    // Layout of every version has fixed size
+    require!(
+        data.len() == exchange_account_size(version),
+        InvalidExchangeAccount
+    );
+    Ok(version)
+}
+pub fn check_legacy_layout(
+    account_info: &AccountInfo,
+    discriminator: [u8; 8],
+    legacy_size: usize,
+) -> Result<()> {
+    let data = account_info.try_borrow_data()?;
+    require!(
+        data.len() >= 8 && data[..8] == discriminator,
+        InvalidAccount
+    );
+    require!(data.len() == legacy_size, AccountAlreadyMigrated);
+    Ok(())
+}
+pub fn is_exchange_account_migrated(account_info: &AccountInfo) -> Result<bool> {
+    Ok(read_exchange_account_version(account_info)? == EXCHANGE_ACCOUNT_VERSION)
+}
+
+// Accounts are only extended, so every layout is prefix of the next one
+// and transformation can be done on loaded account after realloc
+pub fn migrate_exchange_account_data(
+    exchange_account: &mut ExchangeAccount,
+    from_version: u8,
+    state: &State,
+) {
+    let mut version = from_version;
+    while version < EXCHANGE_ACCOUNT_VERSION {
+        match version {
+            // v1 appends reserved space, zeroed by realloc
+            0 => {}
+            // v2 replaces staking rounds with continuous accrual, rewards of rounds
+            // frozen by state migration are moved to amount_to_claim
+            1 => {
+                adjust_staking_account_rounds(exchange_account, &state.staking);
+                let user_staking_data = &mut exchange_account.user_staking_data;
+                let round_rewards = calculate_round_reward(
+                    &state.staking.finished_round,
+                    user_staking_data.finished_round_points,
+                )
+                .add(calculate_round_reward(
+                    &state.staking.current_round,
+                    user_staking_data.current_round_points,
+                ))
+                .unwrap();
+                user_staking_data.amount_to_claim = user_staking_data
+                    .amount_to_claim
+                    .add(round_rewards)
+                    .unwrap();
+                user_staking_data.finished_round_points = 0;
+                user_staking_data.current_round_points = 0;
+                user_staking_data.next_round_points = 0;
+                // Debt shares of not migrated account are unchanged since accrual started
+                exchange_account.last_reward_per_share = Decimal::new(0, REWARD_PER_SHARE_SCALE);
+            }
+            // v3 introduces staking boost, shares of not migrated account are not boosted
+            2 => {
+                exchange_account.staking_boost = Decimal::from_percent(0);
+                exchange_account.boosted_debt_shares = exchange_account.debt_shares;
+            }
+            _ => unreachable!(),
+        }
+        version += 1;
+    }
+    exchange_account.version = EXCHANGE_ACCOUNT_VERSION;
+}
+// State has fixed size, new fields are taken from padding
+pub fn migrate_state_data(state: &mut State, slot: u64) {
+    let mut version = state.version;
+    while version < STATE_VERSION {
+        match version {
+            // v1 only introduces version field
+            0 => {}
+            // v2 introduces liquidation auction, disabled by default
+            1 => {
+                state.liquidation_auction_duration = 0;
+                state.max_penalty_to_liquidator = state.penalty_to_liquidator;
+            }
+            // v3 introduces empty insurance fund
+            2 => {
+                state.insurance_fund = Decimal::from_usd(0);
+                state.insurance_fund_ratio = Decimal::from_percent(0);
+            }
+            // v4 introduces keeper bounty, disabled by default
+            3 => {
+                state.keeper_bounty = Decimal::from_usd(0);
+                state.keeper_bounty_cooldown = 0;
+            }
+            // v5 introduces flash liquidations, none in progress
+            4 => {
+                state.flash_liquidation_debt = 0;
+            }
+            // v6 introduces close factor based on health, disabled by default
+            5 => {
+                state.full_liquidation_health = Decimal::from_percent(0);
+            }
+            // v7 replaces staking rounds with continuous accrual, rounds are frozen
+            // and accrual starts once current round ends
+            6 => {
+                if state.staking.round_length > 0 {
+                    adjust_staking_rounds(state, slot);
+                }
+                state.reward_per_share = Decimal::new(0, REWARD_PER_SHARE_SCALE);
+                state.last_reward_update = state.staking.next_round.start;
+            }
+            // v8 introduces additional reward streams, none by default
+            7 => {
+                state.head_reward_streams = 0;
+            }
+            // v9 introduces rewards vesting, disabled by default
+            8 => {
+                state.rewards_vesting_duration = 0;
+                state.early_exit_penalty = Decimal::from_percent(0);
+            }
+            // v10 introduces swap tax distribution to debt stakers, disabled by default
+            9 => {
+                state.swap_tax_reward_ratio = Decimal::from_percent(0);
+                state.swap_tax_reward_per_share = Decimal::new(0, REWARD_PER_SHARE_SCALE);
+            }
+            // v11 introduces staking boost for SNY collateral, disabled by default
+            10 => {
+                state.max_staking_boost = Decimal::from_percent(0);
+                state.boosted_debt_shares = state.debt_shares;
+            }
+            _ => unreachable!(),
+        }
+        version += 1;
+    }
+    state.version = STATE_VERSION;
+}
+// Collaterals are in the middle of AssetsList, so after realloc synthetics
+// and every collateral are moved to their new offsets
+pub fn migrate_assets_list_data(data: &mut [u8]) {
+    let collateral_size = size_of::<Collateral>();
+    let collaterals_offset = 8 + 3 + size_of::<[Asset; 255]>();
+    let synthetics_size = size_of::<[Synthetic; 255]>();
+
+    let old_synthetics_offset = collaterals_offset + 255 * COLLATERAL_V0_SIZE;
+    let new_synthetics_offset = collaterals_offset + 255 * collateral_size;
+    data.copy_within(
+        old_synthetics_offset..old_synthetics_offset + synthetics_size,
+        new_synthetics_offset,
+    );
+    // Moving from the last one never overwrites collaterals not moved yet
+    for index in (0..255).rev() {
+        let old_offset = collaterals_offset + index * COLLATERAL_V0_SIZE;
+        let new_offset = collaterals_offset + index * collateral_size;
+        data.copy_within(old_offset..old_offset + COLLATERAL_V0_SIZE, new_offset);
+        // Penalty overrides are not set
+        data[new_offset + COLLATERAL_V0_SIZE..new_offset + collateral_size].fill(0);
+    }
+}
+// Interest accrued until first adjustment uses rate of legacy vault
+pub fn migrate_vault_data(vault: &mut Vault) {
+    vault.current_interest_rate = vault.debt_interest_rate;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vault_size() {
+        assert_eq!(VAULT_SIZE, 8 + 1536);
+        assert_eq!(VAULT_ENTRY_SIZE, 8 + 384);
+        // Legacy layouts are prefix of current ones
+        let vault = Vault::default();
+        let vault_legacy_size = std::ptr::addr_of!(vault.head_collaterals) as usize
+            - std::ptr::addr_of!(vault) as usize;
+        assert_eq!(vault_legacy_size, VAULT_V0_SIZE - 8);
+        let vault_entry = VaultEntry::default();
+        let vault_entry_legacy_size = std::ptr::addr_of!(vault_entry.collateral_amounts) as usize
+            - std::ptr::addr_of!(vault_entry) as usize;
+        assert_eq!(vault_entry_legacy_size, VAULT_ENTRY_V0_SIZE - 8);
+    }
+    #[test]
+    fn test_exchange_account_size() {
+        assert_eq!(exchange_account_size(0), 1420);
+        assert_eq!(
+            exchange_account_size(EXCHANGE_ACCOUNT_VERSION),
+            8 + size_of::<ExchangeAccount>()
+        );
+        // Legacy layout is prefix of current one
+        assert_eq!(
+            size_of::<ExchangeAccount>() - size_of::<[u8; 512]>(),
+            EXCHANGE_ACCOUNT_V0_SIZE - 8
+        );
+    }
+    #[test]
+    fn test_migrate_exchange_account_data() {
+        let mut exchange_account = ExchangeAccount {
+            debt_shares: 10,
+            head: 1,
+            user_staking_data: UserStaking {
+                amount_to_claim: Decimal::from_sny(0),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let exchange_account_copy = exchange_account;
+        migrate_exchange_account_data(&mut exchange_account, 0, &State::default());
+
+        assert_eq!(exchange_account.version, EXCHANGE_ACCOUNT_VERSION);
+        assert_eq!({ exchange_account.debt_shares }, {
+            exchange_account_copy.debt_shares
+        });
+        assert_eq!(exchange_account.head, exchange_account_copy.head);
+        assert_eq!({ exchange_account.boosted_debt_shares }, 10);
+        assert_eq!({ exchange_account.staking_boost }, Decimal::from_percent(0));
+    }
+    #[test]
+    fn test_migrate_exchange_account_staking_rounds() {
+        let state = State {
+            staking: Staking {
+                round_length: 100,
+                amount_per_round: Decimal::from_sny(300),
+                finished_round: StakingRound {
+                    start: 100,
+                    amount: Decimal::from_sny(300),
+                    all_points: 30,
+                },
+                current_round: StakingRound {
+                    start: 200,
+                    amount: Decimal::from_sny(300),
+                    all_points: 60,
+                },
+                next_round: StakingRound {
+                    start: 300,
+                    amount: Decimal::from_sny(300),
+                    all_points: 60,
+                },
+                ..Default::default()
+            },
+            reward_per_share: Decimal::new(1, 0).to_scale(REWARD_PER_SHARE_SCALE),
+            last_reward_update: 400,
+            swap_tax_reward_per_share: Decimal::new(0, REWARD_PER_SHARE_SCALE),
+            ..Default::default()
+        };
+        let exchange_account = ExchangeAccount {
+            version: 1,
+            debt_shares: 10,
+            user_staking_data: UserStaking {
+                amount_to_claim: Decimal::from_sny(5),
+                finished_round_points: 3,
+                current_round_points: 6,
+                next_round_points: 10,
+                last_update: 201,
+            },
+            ..Default::default()
+        };
+        // Rewards of finished and current round
+        {
+            let mut exchange_account = exchange_account;
+            migrate_exchange_account_data(&mut exchange_account, 1, &state);
+
+            assert_eq!(exchange_account.version, EXCHANGE_ACCOUNT_VERSION);
+            // 5 + 300 * 3 / 30 + 300 * 6 / 60
+            assert_eq!(
+                exchange_account.user_staking_data.amount_to_claim,
+                Decimal::from_sny(65)
+            );
+            assert_eq!(
+                exchange_account.user_staking_data,
+                UserStaking {
+                    amount_to_claim: Decimal::from_sny(65),
+                    finished_round_points: 0,
+                    current_round_points: 0,
+                    next_round_points: 0,
+                    last_update: 201,
+                }
+            );
+            // Accrual since start of continuous staking is not lost
+            assert_eq!(
+                exchange_account.last_reward_per_share,
+                Decimal::new(0, REWARD_PER_SHARE_SCALE)
+            );
+            adjust_staking_account(&mut exchange_account, &state);
+            assert_eq!(
+                exchange_account.user_staking_data.amount_to_claim,
+                Decimal::from_sny(10_000_065)
+            );
+        }
+        // Rewards of finished round already claimed
+        {
+            let mut exchange_account = exchange_account;
+            exchange_account.user_staking_data.finished_round_points = 0;
+            migrate_exchange_account_data(&mut exchange_account, 1, &state);
+            assert_eq!(
+                exchange_account.user_staking_data.amount_to_claim,
+                Decimal::from_sny(35)
+            );
+        }
+        // Last update before current round
+        {
+            let mut exchange_account = exchange_account;
+            exchange_account.user_staking_data.last_update = 101;
+            migrate_exchange_account_data(&mut exchange_account, 1, &state);
+            // 5 + 300 * 6 / 30 + 300 * 10 / 60
+            assert_eq!(
+                exchange_account.user_staking_data.amount_to_claim,
+                Decimal::from_sny(115)
+            );
+        }
+    }
+    #[test]
+    fn test_migrate_state_data() {
+        let mut state = State {
+            debt_shares: 10,
+            penalty_to_liquidator: Decimal::from_percent(5),
+            staking: Staking {
+                round_length: 100,
+                amount_per_round: Decimal::from_sny(300),
+                next_round: StakingRound {
+                    start: 200,
+                    amount: Decimal::from_sny(300),
+                    all_points: 10,
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        migrate_state_data(&mut state, 250);
+        assert_eq!(state.version, STATE_VERSION);
+        assert_eq!({ state.debt_shares }, 10);
+        assert_eq!({ state.liquidation_auction_duration }, 0);
+        assert_eq!(
+            { state.max_penalty_to_liquidator },
+            Decimal::from_percent(5)
+        );
+        assert_eq!({ state.insurance_fund }, Decimal::from_usd(0));
+        assert_eq!({ state.insurance_fund_ratio }, Decimal::from_percent(0));
+        assert_eq!({ state.keeper_bounty }, Decimal::from_usd(0));
+        assert_eq!({ state.flash_liquidation_debt }, 0);
+        assert_eq!({ state.full_liquidation_health }, Decimal::from_percent(0));
+        // Rounds are frozen at slot of migration
+        assert_eq!({ state.staking.current_round.start }, 200);
+        assert_eq!({ state.staking.next_round.start }, 300);
+        assert_eq!(
+            { state.reward_per_share },
+            Decimal::new(0, REWARD_PER_SHARE_SCALE)
+        );
+        assert_eq!({ state.last_reward_update }, 300);
+        assert_eq!(state.head_reward_streams, 0);
+        assert_eq!({ state.rewards_vesting_duration }, 0);
+        assert_eq!({ state.early_exit_penalty }, Decimal::from_percent(0));
+        assert_eq!({ state.swap_tax_reward_ratio }, Decimal::from_percent(0));
+        assert_eq!(
+            { state.swap_tax_reward_per_share },
+            Decimal::new(0, REWARD_PER_SHARE_SCALE)
+        );
+        assert_eq!({ state.max_staking_boost }, Decimal::from_percent(0));
+        assert_eq!({ state.boosted_debt_shares }, 10);
+        // Migration is idempotent
+        migrate_state_data(&mut state, 400);
+        assert_eq!(state.version, STATE_VERSION);
+    }
+    #[test]
+    fn test_migrate_assets_list_data() {
+        let collaterals_offset = 8 + 3 + size_of::<[Asset; 255]>();
+        let synthetics_size = size_of::<[Synthetic; 255]>();
+        let collateral_size = size_of::<Collateral>();
+
+        // Legacy layout with every collateral filled with its index
+        let mut data = vec![0xAAu8; collaterals_offset];
+        for index in 0..255u8 {
+            data.extend_from_slice(&[index; COLLATERAL_V0_SIZE]);
+        }
+        data.extend_from_slice(&vec![0xBBu8; synthetics_size]);
+        assert_eq!(data.len(), ASSETS_LIST_V0_SIZE);
+
+        // Realloc appends zeroed space
+        data.resize(ASSETS_LIST_SIZE, 0);
+        migrate_assets_list_data(&mut data);
+
+        assert!(data[..collaterals_offset].iter().all(|x| *x == 0xAA));
+        for index in 0..255usize {
+            let offset = collaterals_offset + index * collateral_size;
+            let collateral = &data[offset..offset + collateral_size];
+            assert!(collateral[..COLLATERAL_V0_SIZE]
+                .iter()
+                .all(|x| *x == index as u8));
+            assert!(collateral[COLLATERAL_V0_SIZE..].iter().all(|x| *x == 0));
+        }
+        let synthetics_offset = collaterals_offset + 255 * collateral_size;
+        assert_eq!(synthetics_offset + synthetics_size, ASSETS_LIST_SIZE);
+        assert!(data[synthetics_offset..].iter().all(|x| *x == 0xBB));
+    }
+    #[test]
+    fn test_migrate_vault_data() {
+        let mut vault = Vault {
+            debt_interest_rate: Decimal::new(55, 3).to_interest_rate(),
+            ..Default::default()
+        };
+        migrate_vault_data(&mut vault);
+        assert_eq!(
+            { vault.current_interest_rate },
+            Decimal::new(55, 3).to_interest_rate()
+        );
+        assert!(!vault.interest_curve_enabled);
+    }
+}
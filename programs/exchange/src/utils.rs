@@ -3,10 +3,25 @@ use std::cell::RefMut;
 use std::convert::TryInto;
 use std::str::FromStr;
 
-use crate::decimal::{Add, Compare, Div, Mul, MulUp, PowAccuracy, Sub, PRICE_SCALE};
-use crate::math::{calculate_compounded_interest, calculate_debt, calculate_minute_interest_rate};
+use crate::decimal::{
+    Add, Compare, Div, DivScale, Mul, MulUp, PowAccuracy, Sub, PRICE_SCALE, REWARD_PER_SHARE_SCALE,
+    SNY_SCALE, XUSD_SCALE,
+};
+use crate::math::{
+    calculate_boosted_debt_shares, calculate_compounded_interest, calculate_debt,
+    calculate_deleverage_value, calculate_minute_interest_rate,
+    calculate_reward_per_share_increase, calculate_staking_boost, calculate_staking_reward,
+    calculate_utilization_interest_rate, calculate_value_in_usd, calculate_vault_borrow_limit,
+    calculate_vault_max_borrow_based_max_debt, calculate_vault_utilization,
+    calculate_vested_amount, calculate_vesting_end, calculate_vesting_start,
+};
 use crate::*;
 use account::*;
+use anchor_lang::solana_program::program::invoke;
+use anchor_lang::solana_program::system_instruction;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
 
 pub fn check_feed_update(
     assets: &[Asset],
@@ -73,6 +88,29 @@ pub fn load_price_from_feed(
         }
     }
 }
+// Prices of basket collaterals, feeds are given in order of vault collaterals
+pub fn load_vault_basket_prices(
+    vault: &Vault,
+    price_feeds: &[AccountInfo],
+    assets: &[Asset; 255],
+) -> Result<Vec<Decimal>> {
+    require!(
+        price_feeds.len() == vault.head_collaterals as usize,
+        InvalidAccount
+    );
+    price_feeds
+        .iter()
+        .zip(vault.collaterals.iter())
+        .map(|(price_feed, vault_collateral)| {
+            require!(
+                price_feed.key.eq(&vault_collateral.collateral_price_feed),
+                InvalidAccount
+            );
+            check_value_collateral_price_feed(price_feed, vault_collateral.oracle_type)?;
+            load_price_from_feed(price_feed, vault_collateral.oracle_type, assets)
+        })
+        .collect()
+}
 pub fn load_pyth_price(price_feed_account: &AccountInfo) -> Result<Decimal> {
     let price_feed = Price::load(price_feed_account)?;
     let offset = price_feed.expo.checked_add(PRICE_SCALE.into()).unwrap();
@@ -122,6 +160,7 @@ pub fn div_up(a: u128, b: u128) -> u128 {
         .unwrap();
 }
 
+// Round based staking is only used to settle rewards during migration
 pub fn adjust_staking_rounds(state: &mut State, slot: u64) {
     if slot <= state.staking.next_round.start {
         return;
@@ -224,7 +263,7 @@ pub fn adjust_staking_rounds(state: &mut State, slot: u64) {
     }
     return;
 }
-pub fn adjust_staking_account(exchange_account: &mut ExchangeAccount, staking: &Staking) {
+pub fn adjust_staking_account_rounds(exchange_account: &mut ExchangeAccount, staking: &Staking) {
     if exchange_account.user_staking_data.last_update >= staking.current_round.start {
         return;
     } else {
@@ -246,6 +285,217 @@ pub fn adjust_staking_account(exchange_account: &mut ExchangeAccount, staking: &
     return;
 }
 
+// Accrues SNY per boosted debt share and reward streams per debt share up to given slot
+pub fn adjust_staking_rewards(state: &mut State, slot: u64) {
+    let debt_shares = state.debt_shares;
+    let head_reward_streams = state.head_reward_streams as usize;
+    for reward_stream in state.reward_streams[..head_reward_streams].iter_mut() {
+        adjust_reward_stream(reward_stream, debt_shares, slot);
+    }
+
+    // Accrual of migrated state starts once last staking round ends
+    if slot <= state.last_reward_update {
+        return;
+    }
+    let slots = slot.checked_sub(state.last_reward_update).unwrap();
+    let increase = calculate_reward_per_share_increase(
+        state.staking.amount_per_round,
+        state.staking.round_length,
+        slots,
+        state.boosted_debt_shares,
+    );
+    state.reward_per_share = state.reward_per_share.add(increase).unwrap();
+    state.last_reward_update = slot;
+}
+pub fn adjust_reward_stream(reward_stream: &mut RewardStream, debt_shares: u64, slot: u64) {
+    // Stream stops accruing at end_slot
+    let accrued_until = slot.min(reward_stream.end_slot);
+    if accrued_until <= reward_stream.last_update {
+        return;
+    }
+    let slots = accrued_until
+        .checked_sub(reward_stream.last_update)
+        .unwrap();
+    let increase =
+        calculate_reward_per_share_increase(reward_stream.amount_per_slot, 1, slots, debt_shares);
+    reward_stream.reward_per_share = reward_stream.reward_per_share.add(increase).unwrap();
+    reward_stream.last_update = accrued_until;
+}
+// Moves rewards accrued by debt shares of account to amount_to_claim of SNY, every stream
+// and swap tax
+// Has to be called before every change of account debt shares
+pub fn adjust_staking_account(exchange_account: &mut ExchangeAccount, state: &State) {
+    let reward = calculate_staking_reward(
+        exchange_account.boosted_debt_shares,
+        state.reward_per_share,
+        exchange_account.last_reward_per_share,
+        SNY_SCALE,
+    );
+    exchange_account.user_staking_data.amount_to_claim = exchange_account
+        .user_staking_data
+        .amount_to_claim
+        .add(reward)
+        .unwrap();
+    exchange_account.last_reward_per_share = state.reward_per_share;
+
+    let debt_shares = exchange_account.debt_shares;
+    for (index, reward_stream) in state.reward_streams[..state.head_reward_streams as usize]
+        .iter()
+        .enumerate()
+    {
+        let entry = &mut exchange_account.reward_streams[index];
+        // Entries of streams added after account creation are still zeroed
+        if entry.last_reward_per_share.scale != REWARD_PER_SHARE_SCALE {
+            entry.amount_to_claim = Decimal::new(0, reward_stream.amount_per_slot.scale);
+            entry.last_reward_per_share = Decimal::new(0, REWARD_PER_SHARE_SCALE);
+        }
+        let reward = calculate_staking_reward(
+            debt_shares,
+            reward_stream.reward_per_share,
+            entry.last_reward_per_share,
+            entry.amount_to_claim.scale,
+        );
+        entry.amount_to_claim = entry.amount_to_claim.add(reward).unwrap();
+        entry.last_reward_per_share = reward_stream.reward_per_share;
+    }
+
+    let swap_tax_rewards = &mut exchange_account.swap_tax_rewards;
+    // Entry of account created before swap tax distribution is still zeroed
+    if swap_tax_rewards.last_reward_per_share.scale != REWARD_PER_SHARE_SCALE {
+        swap_tax_rewards.amount_to_claim = Decimal::from_usd(0);
+        swap_tax_rewards.last_reward_per_share = Decimal::new(0, REWARD_PER_SHARE_SCALE);
+    }
+    let reward = calculate_staking_reward(
+        debt_shares,
+        state.swap_tax_reward_per_share,
+        swap_tax_rewards.last_reward_per_share,
+        XUSD_SCALE,
+    );
+    swap_tax_rewards.amount_to_claim = swap_tax_rewards.amount_to_claim.add(reward).unwrap();
+    swap_tax_rewards.last_reward_per_share = state.swap_tax_reward_per_share;
+}
+// Has to be called after every change of SNY collateral or debt shares of account
+// and on check_account_collateralization, so boost follows prices
+// Account has to be adjusted with previous boosted shares before
+pub fn adjust_staking_boost(
+    state: &mut State,
+    exchange_account: &mut ExchangeAccount,
+    assets_list: &AssetsList,
+    user_debt: Decimal,
+) {
+    // SNY is always first collateral
+    let sny_collateral = &assets_list.collaterals[0];
+    let sny_balance = get_user_sny_collateral_balance(exchange_account, sny_collateral);
+    let sny_collateral_value = calculate_value_in_usd(
+        assets_list.assets[sny_collateral.asset_index as usize].price,
+        sny_balance,
+    );
+    exchange_account.staking_boost =
+        calculate_staking_boost(sny_collateral_value, user_debt, state.max_staking_boost);
+
+    let boosted_debt_shares =
+        calculate_boosted_debt_shares(exchange_account.debt_shares, exchange_account.staking_boost);
+    state.boosted_debt_shares = state
+        .boosted_debt_shares
+        .checked_sub(exchange_account.boosted_debt_shares)
+        .unwrap()
+        .checked_add(boosted_debt_shares)
+        .unwrap();
+    exchange_account.boosted_debt_shares = boosted_debt_shares;
+}
+// Share of swap tax is distributed to debt stakers, the rest goes to swap_tax_reserve
+pub fn distribute_swap_tax(state: &mut State, swap_tax: Decimal) {
+    // Without stakers whole tax stays in reserve
+    let stakers_tax = match state.debt_shares {
+        0 => Decimal::from_usd(0),
+        _ => swap_tax.mul(state.swap_tax_reward_ratio),
+    };
+    let increase = calculate_reward_per_share_increase(stakers_tax, 1, 1, state.debt_shares);
+    state.swap_tax_reward_per_share = state.swap_tax_reward_per_share.add(increase).unwrap();
+
+    let reserve_tax = swap_tax.sub(stakers_tax).unwrap();
+    state.swap_tax_reserve = state.swap_tax_reserve.add(reserve_tax).unwrap();
+}
+// Moves SNY amount_to_claim to vesting schedule and returns rewards free to withdraw
+// Without vesting duration amount_to_claim is free immediately
+pub fn release_vested_rewards(
+    exchange_account: &mut ExchangeAccount,
+    vesting_duration: u32,
+    slot: u64,
+) -> Decimal {
+    let vested = calculate_vested_amount(
+        exchange_account.vesting_amount,
+        exchange_account.vesting_start,
+        exchange_account.vesting_end,
+        slot,
+    );
+    let remaining = exchange_account.vesting_amount.checked_sub(vested).unwrap();
+    let amount_to_claim = exchange_account.user_staking_data.amount_to_claim.to_u64();
+    exchange_account.user_staking_data.amount_to_claim = Decimal::from_sny(0);
+
+    let (released, new_vesting) = match vesting_duration {
+        0 => (vested.checked_add(amount_to_claim).unwrap(), 0),
+        _ => (vested, amount_to_claim),
+    };
+    exchange_account.vesting_end = calculate_vesting_end(
+        remaining,
+        exchange_account.vesting_end,
+        new_vesting,
+        vesting_duration,
+        slot,
+    );
+    exchange_account.vesting_amount = remaining.checked_add(new_vesting).unwrap();
+    exchange_account.vesting_start = slot;
+    Decimal::from_sny(released.into())
+}
+// Releases all SNY rewards, unvested part is reduced by early_exit_penalty
+// Returns (released, penalty)
+pub fn exit_rewards_vesting(
+    exchange_account: &mut ExchangeAccount,
+    vesting_duration: u32,
+    early_exit_penalty: Decimal,
+    slot: u64,
+) -> (Decimal, Decimal) {
+    let released = release_vested_rewards(exchange_account, vesting_duration, slot);
+    let unvested = Decimal::from_sny(exchange_account.vesting_amount.into());
+    // Rounding up - penalty is taken in favor of stakers
+    let penalty = unvested.mul_up(early_exit_penalty).to_sny();
+    exchange_account.vesting_amount = 0;
+    exchange_account.vesting_end = slot;
+
+    let released = released.add(unvested.sub(penalty).unwrap()).unwrap();
+    (released, penalty)
+}
+// Penalty is shared by debt stakers except exiting account
+pub fn distribute_early_exit_penalty(
+    state: &mut State,
+    exchange_account: &mut ExchangeAccount,
+    penalty: Decimal,
+) {
+    let other_debt_shares = state
+        .boosted_debt_shares
+        .checked_sub(exchange_account.boosted_debt_shares)
+        .unwrap();
+    let increase = calculate_reward_per_share_increase(penalty, 1, 1, other_debt_shares);
+    state.reward_per_share = state.reward_per_share.add(increase).unwrap();
+    exchange_account.last_reward_per_share = state.reward_per_share;
+}
+// Share of round rewards earned by given points
+pub fn calculate_round_reward(round: &StakingRound, points: u64) -> Decimal {
+    if round.all_points == 0 {
+        return Decimal::from_sny(0);
+    }
+    Decimal::from_sny(
+        round
+            .amount
+            .val
+            .checked_mul(points.into())
+            .unwrap()
+            .checked_div(round.all_points.into())
+            .unwrap(),
+    )
+}
+
 pub fn calculate_debt_with_adjustment(
     state: &mut State,
     assets_list: &mut RefMut<AssetsList>,
@@ -262,20 +512,9 @@ pub fn adjust_interest_debt(
     slot: u64,
     timestamp: i64,
 ) {
-    const ADJUSTMENT_PERIOD: i64 = 60;
-    let diff = timestamp
-        .checked_sub(state.last_debt_adjustment)
-        .unwrap()
-        .checked_div(ADJUSTMENT_PERIOD)
-        .unwrap();
-    if diff >= 1 {
-        let total_debt_twap = calculate_debt(assets_list, slot, state.max_delay, true).unwrap();
-        let minute_interest_rate = calculate_minute_interest_rate(state.debt_interest_rate);
-        let compounded_interest = calculate_compounded_interest(
-            total_debt_twap,
-            minute_interest_rate,
-            diff.try_into().unwrap(),
-        );
+    if let Some((compounded_interest, last_debt_adjustment)) =
+        calculate_interest_debt(state, assets_list, slot, timestamp)
+    {
         let usd = &mut assets_list.borrow_mut().synthetics[0];
 
         // increase in interest supply may exceed the max supply limit
@@ -284,12 +523,53 @@ pub fn adjust_interest_debt(
             .accumulated_debt_interest
             .add(compounded_interest)
             .unwrap();
-        state.last_debt_adjustment = diff
-            .checked_mul(ADJUSTMENT_PERIOD)
-            .unwrap()
-            .checked_add(state.last_debt_adjustment)
-            .unwrap();
+        state.last_debt_adjustment = last_debt_adjustment;
+    }
+}
+// Debt with interest not adjusted yet, for instructions not changing accounts
+pub fn calculate_debt_with_interest(
+    state: &State,
+    assets_list: &AssetsList,
+    slot: u64,
+    timestamp: i64,
+) -> Result<Decimal> {
+    let debt = calculate_debt(assets_list, slot, state.max_delay, false)?;
+    // Interest is minted as xUSD valued at 1 USD
+    match calculate_interest_debt(state, assets_list, slot, timestamp) {
+        Some((compounded_interest, _)) => Ok(debt.add(compounded_interest).unwrap()),
+        None => Ok(debt),
+    }
+}
+// Interest of whole periods since last adjustment with time of next adjustment,
+// None if period did not pass yet
+pub fn calculate_interest_debt(
+    state: &State,
+    assets_list: &AssetsList,
+    slot: u64,
+    timestamp: i64,
+) -> Option<(Decimal, i64)> {
+    const ADJUSTMENT_PERIOD: i64 = 60;
+    let diff = timestamp
+        .checked_sub(state.last_debt_adjustment)
+        .unwrap()
+        .checked_div(ADJUSTMENT_PERIOD)
+        .unwrap();
+    if diff < 1 {
+        return None;
     }
+    let total_debt_twap = calculate_debt(assets_list, slot, state.max_delay, true).unwrap();
+    let minute_interest_rate = calculate_minute_interest_rate(state.debt_interest_rate);
+    let compounded_interest = calculate_compounded_interest(
+        total_debt_twap,
+        minute_interest_rate,
+        diff.try_into().unwrap(),
+    );
+    let last_debt_adjustment = diff
+        .checked_mul(ADJUSTMENT_PERIOD)
+        .unwrap()
+        .checked_add(state.last_debt_adjustment)
+        .unwrap();
+    Some((compounded_interest, last_debt_adjustment))
 }
 
 pub fn adjust_vault_interest_rate(vault: &mut Vault, timestamp: i64) {
@@ -300,8 +580,9 @@ pub fn adjust_vault_interest_rate(vault: &mut Vault, timestamp: i64) {
         .checked_div(ADJUSTMENT_PERIOD)
         .unwrap();
 
+    // Elapsed period accrues with rate set on previous adjustment
     if diff >= 1 {
-        let minute_interest_rate = calculate_minute_interest_rate(vault.debt_interest_rate);
+        let minute_interest_rate = calculate_minute_interest_rate(vault.current_interest_rate);
         let one = Decimal::from_integer(1).to_interest_rate();
         let base = minute_interest_rate.add(one).unwrap();
         let time_period_interest = base.pow_with_accuracy(diff.try_into().unwrap());
@@ -313,6 +594,17 @@ pub fn adjust_vault_interest_rate(vault: &mut Vault, timestamp: i64) {
             .checked_add(vault.last_update)
             .unwrap();
     }
+    // Refresh rate for following period, utilization or parameters might have changed
+    vault.current_interest_rate = match vault.interest_curve_enabled {
+        true => calculate_utilization_interest_rate(
+            calculate_vault_utilization(vault.mint_amount, vault.max_borrow),
+            vault.debt_interest_rate,
+            vault.optimal_utilization,
+            vault.interest_rate_at_optimal,
+            vault.max_interest_rate,
+        ),
+        false => vault.debt_interest_rate,
+    };
 }
 pub fn adjust_vault_entry_interest_debt(
     vault: &mut Vault,
@@ -348,6 +640,159 @@ pub fn adjust_vault_entry_interest_debt(
     // commit adjustment by setting interest nominator as new interest denominator
     vault_entry.last_accumulated_interest_rate = interest_nominator;
 }
+// Upper bound of borrow -> swap -> deposit steps of single leverage_vault
+pub const LEVERAGE_ITERATIONS: usize = 10;
+// Loops borrowing synthetic, swapping it to collateral through swapline and depositing it
+// until debt reaches target_ratio of collateral value. Returns collateral taken from swapline
+pub fn leverage_vault_entry(
+    vault: &mut Vault,
+    vault_entry: &mut VaultEntry,
+    synthetic: &mut Synthetic,
+    synthetic_asset: Asset,
+    swapline: &mut Swapline,
+    collateral_price: Decimal,
+    target_ratio: Decimal,
+) -> Result<Decimal> {
+    // Swapline trades only primary collateral, debt backed by basket would be levered blindly
+    require!(vault.head_collaterals == 0, InvalidVaultType);
+    let mut collateral_out = Decimal::new(0, vault_entry.collateral_amount.scale);
+
+    for _ in 0..LEVERAGE_ITERATIONS {
+        let borrow_limit = calculate_vault_borrow_limit(
+            collateral_price,
+            synthetic_asset,
+            *synthetic,
+            vault_entry.collateral_amount,
+            target_ratio,
+        );
+        if borrow_limit.lte(vault_entry.synthetic_amount)?
+            || vault.max_borrow.lte(vault.mint_amount)?
+        {
+            break;
+        }
+        // Debt is limited by target ratio and vault max_borrow
+        let entry_available = borrow_limit.sub(vault_entry.synthetic_amount)?;
+        let vault_available = vault.max_borrow.sub(vault.mint_amount)?;
+        let max_debt = match entry_available.lt(vault_available)? {
+            true => entry_available,
+            false => vault_available,
+        };
+        let mut mint_amount = calculate_vault_max_borrow_based_max_debt(max_debt, vault.open_fee);
+
+        // Swap back is limited by swapline liquidity
+        let swapline_available = swapline
+            .balance
+            .sub(swapline.accumulated_fee)?
+            .to_scale(mint_amount.scale);
+        for limit in [synthetic.swapline_supply, swapline_available] {
+            if mint_amount.gt(limit)? {
+                mint_amount = limit;
+            }
+        }
+
+        let swap_fee = mint_amount.mul(swapline.fee);
+        let amount_out = mint_amount
+            .sub(swap_fee)?
+            .to_scale(vault_entry.collateral_amount.scale);
+        if amount_out.val == 0 {
+            break;
+        }
+
+        // Borrow
+        let open_fee_amount = mint_amount.mul_up(vault.open_fee);
+        vault.accumulated_interest = vault.accumulated_interest.add(open_fee_amount)?;
+        vault_entry.increase_supply_cascade(vault, synthetic, mint_amount.add(open_fee_amount)?)?;
+
+        // Swap borrowed synthetic to collateral
+        synthetic.set_supply_safely(synthetic.supply.sub(mint_amount)?)?;
+        synthetic.swapline_supply = synthetic.swapline_supply.sub(mint_amount)?;
+        swapline.balance = swapline
+            .balance
+            .sub(amount_out.to_scale(swapline.balance.scale))?;
+        swapline.accumulated_fee = swapline
+            .accumulated_fee
+            .add(swap_fee.to_scale(swapline.accumulated_fee.scale))?;
+
+        // Deposit
+        vault_entry.collateral_amount = vault_entry.collateral_amount.add(amount_out)?;
+        vault.collateral_amount = vault.collateral_amount.add(amount_out)?;
+        collateral_out = collateral_out.add(amount_out)?;
+    }
+    Ok(collateral_out)
+}
+// Sells collateral through swapline to repay debt once it exceeds deleverage_threshold
+// of collateral value. Returns (sold collateral, keeper fee)
+pub fn deleverage_vault_entry(
+    vault: &mut Vault,
+    vault_entry: &mut VaultEntry,
+    synthetic: &mut Synthetic,
+    synthetic_asset: Asset,
+    swapline: &mut Swapline,
+    collateral_price: Decimal,
+) -> Result<(Decimal, Decimal)> {
+    require!(
+        vault_entry.deleverage_threshold.val != 0,
+        DeleverageNotTriggered
+    );
+    // Same as leverage, trigger and sold amount are valued on primary collateral only
+    require!(vault.head_collaterals == 0, InvalidVaultType);
+    let trigger_limit = calculate_vault_borrow_limit(
+        collateral_price,
+        synthetic_asset,
+        *synthetic,
+        vault_entry.collateral_amount,
+        vault_entry.deleverage_threshold,
+    );
+    require!(
+        trigger_limit.lt(vault_entry.synthetic_amount)?,
+        DeleverageNotTriggered
+    );
+
+    let debt_value = calculate_value_in_usd(synthetic_asset.price, vault_entry.synthetic_amount);
+    let collateral_value = calculate_value_in_usd(collateral_price, vault_entry.collateral_amount);
+    let sell_value = calculate_deleverage_value(
+        debt_value,
+        collateral_value,
+        vault_entry.deleverage_target,
+        swapline.fee,
+        vault.deleverage_fee,
+    )?;
+    let sold_collateral =
+        sell_value.div_to_scale(collateral_price, vault_entry.collateral_amount.scale);
+    let keeper_fee = sold_collateral.mul(vault.deleverage_fee);
+    let collateral_decrease = sold_collateral.add(keeper_fee)?;
+    require!(
+        collateral_decrease.lte(vault_entry.collateral_amount)?,
+        InvalidLiquidation
+    );
+
+    // Swap sold collateral to synthetic, same as native_to_synthetic
+    let swap_fee = sold_collateral.mul(swapline.fee);
+    let swapped = sold_collateral
+        .sub(swap_fee)?
+        .to_scale(vault_entry.synthetic_amount.scale);
+    let repay_amount = match swapped.gt(vault_entry.synthetic_amount)? {
+        true => vault_entry.synthetic_amount,
+        false => swapped,
+    };
+    swapline.accumulated_fee = swapline.accumulated_fee.add(swap_fee)?;
+    swapline.balance = swapline.balance.add(sold_collateral)?;
+    require!(
+        swapline
+            .balance
+            .sub(swapline.accumulated_fee)?
+            .lte(swapline.limit)?,
+        SwaplineLimit
+    );
+    synthetic.supply = synthetic.supply.add(repay_amount)?;
+    synthetic.swapline_supply = synthetic.swapline_supply.add(repay_amount)?;
+
+    // Swapped synthetic is burned right away
+    vault_entry.decrease_supply_cascade(vault, synthetic, repay_amount)?;
+    vault_entry.collateral_amount = vault_entry.collateral_amount.sub(collateral_decrease)?;
+    vault.collateral_amount = vault.collateral_amount.sub(collateral_decrease)?;
+    Ok((sold_collateral, keeper_fee))
+}
 impl Synthetic {
     pub fn set_supply_safely(self: &mut Self, new_supply: Decimal) -> ProgramResult {
         // increase can throw error
@@ -359,6 +804,16 @@ impl Synthetic {
         Ok(())
     }
 }
+impl Collateral {
+    // Returns (penalty_to_liquidator, penalty_to_exchange) for liquidation of this collateral
+    pub fn liquidation_penalties(&self, state: &State) -> (Decimal, Decimal) {
+        // Override is set together for both penalties
+        match self.penalty_to_liquidator.scale {
+            0 => (state.penalty_to_liquidator, state.penalty_to_exchange),
+            _ => (self.penalty_to_liquidator, self.penalty_to_exchange),
+        }
+    }
+}
 impl Vault {
     pub fn set_mint_amount_safely(self: &mut Self, new_mint_amount: Decimal) -> ProgramResult {
         // increase can throw error
@@ -371,6 +826,37 @@ impl Vault {
     }
 }
 impl VaultEntry {
+    pub fn increase_collateral_cascade(
+        &mut self,
+        vault: &mut Vault,
+        index: usize,
+        amount: Decimal,
+    ) -> ProgramResult {
+        let vault_collateral = &mut vault.collaterals[index];
+        // Entries created before collateral was added to basket are still zeroed
+        if self.collateral_amounts[index].scale != vault_collateral.collateral_amount.scale {
+            self.collateral_amounts[index] =
+                Decimal::new(0, vault_collateral.collateral_amount.scale);
+        }
+        self.collateral_amounts[index] = self.collateral_amounts[index].add(amount)?;
+        vault_collateral.collateral_amount = vault_collateral.collateral_amount.add(amount)?;
+        Ok(())
+    }
+    pub fn decrease_collateral_cascade(
+        &mut self,
+        vault: &mut Vault,
+        index: usize,
+        amount: Decimal,
+    ) -> ProgramResult {
+        require!(
+            self.collateral_amounts[index].gte(amount)?,
+            VaultWithdrawLimit
+        );
+        self.collateral_amounts[index] = self.collateral_amounts[index].sub(amount)?;
+        vault.collaterals[index].collateral_amount =
+            vault.collaterals[index].collateral_amount.sub(amount)?;
+        Ok(())
+    }
     pub fn decrease_supply_cascade(
         self: &mut Self,
         vault: &mut Vault,
@@ -421,6 +907,159 @@ pub fn get_user_sny_collateral_balance(
         None => return Decimal::from_sny(0),
     }
 }
+// Both accounts have to be adjusted to current staking round before
+pub fn move_position(from: &mut ExchangeAccount, to: &mut ExchangeAccount, slot: u64) {
+    to.debt_shares = to.debt_shares.checked_add(from.debt_shares).unwrap();
+    from.debt_shares = 0;
+
+    // Move collateral entries, merging ones with the same collateral
+    for entry in from.collaterals[..from.head as usize].iter() {
+        let to_entry = to.collaterals[..to.head as usize]
+            .iter_mut()
+            .find(|x| x.collateral_address.eq(&entry.collateral_address));
+        match to_entry {
+            Some(to_entry) => to_entry.amount = to_entry.amount.checked_add(entry.amount).unwrap(),
+            None => to.append(*entry),
+        }
+    }
+    while from.head > 0 {
+        from.remove(0);
+    }
+
+    // Move staking points together with rewards waiting for withdraw
+    let from_staking = &mut from.user_staking_data;
+    let to_staking = &mut to.user_staking_data;
+    to_staking.amount_to_claim = to_staking
+        .amount_to_claim
+        .add(from_staking.amount_to_claim)
+        .unwrap();
+    to_staking.finished_round_points = to_staking
+        .finished_round_points
+        .checked_add(from_staking.finished_round_points)
+        .unwrap();
+    to_staking.current_round_points = to_staking
+        .current_round_points
+        .checked_add(from_staking.current_round_points)
+        .unwrap();
+    to_staking.next_round_points = to_staking
+        .next_round_points
+        .checked_add(from_staking.next_round_points)
+        .unwrap();
+    from_staking.amount_to_claim = Decimal::from_sny(0);
+    from_staking.finished_round_points = 0;
+    from_staking.current_round_points = 0;
+    from_staking.next_round_points = 0;
+
+    // Move rewards of streams, entries of both accounts are adjusted to the same streams
+    for index in 0..from.reward_streams.len() {
+        let amount_to_claim = from.reward_streams[index].amount_to_claim;
+        if amount_to_claim.val == 0 {
+            continue;
+        }
+        let to_entry = &mut to.reward_streams[index];
+        to_entry.amount_to_claim = to_entry.amount_to_claim.add(amount_to_claim).unwrap();
+        from.reward_streams[index].amount_to_claim = Decimal::new(0, amount_to_claim.scale);
+    }
+
+    // Move swap tax rewards, both accounts are adjusted to the same swap_tax_reward_per_share
+    let swap_tax_to_claim = from.swap_tax_rewards.amount_to_claim;
+    if swap_tax_to_claim.val > 0 {
+        to.swap_tax_rewards.amount_to_claim = to
+            .swap_tax_rewards
+            .amount_to_claim
+            .add(swap_tax_to_claim)
+            .unwrap();
+        from.swap_tax_rewards.amount_to_claim = Decimal::from_usd(0);
+    }
+
+    // Merge vesting schedules, vested part stays withdrawable and the rest
+    // vests until weighted end of both schedules
+    let from_vested = calculate_vested_amount(
+        from.vesting_amount,
+        from.vesting_start,
+        from.vesting_end,
+        slot,
+    );
+    let to_vested =
+        calculate_vested_amount(to.vesting_amount, to.vesting_start, to.vesting_end, slot);
+    let from_remaining = from.vesting_amount.checked_sub(from_vested).unwrap();
+    let to_remaining = to.vesting_amount.checked_sub(to_vested).unwrap();
+    let vested = from_vested.checked_add(to_vested).unwrap();
+    let remaining = from_remaining.checked_add(to_remaining).unwrap();
+    to.vesting_end = calculate_vesting_end(
+        to_remaining,
+        to.vesting_end,
+        from_remaining,
+        from.vesting_end.saturating_sub(slot).try_into().unwrap(),
+        slot,
+    );
+    to.vesting_start = calculate_vesting_start(vested, remaining, to.vesting_end, slot);
+    to.vesting_amount = vested.checked_add(remaining).unwrap();
+    from.vesting_amount = 0;
+    from.vesting_start = slot;
+    from.vesting_end = slot;
+
+    // Pending liquidation cannot be escaped by moving position
+    to.liquidation_deadline = to.liquidation_deadline.min(from.liquidation_deadline);
+    from.liquidation_deadline = u64::MAX;
+}
+// Both entries have to be adjusted to current accumulated interest rate before
+pub fn move_vault_entry(from: &mut VaultEntry, to: &mut VaultEntry) -> ProgramResult {
+    to.synthetic_amount = to.synthetic_amount.add(from.synthetic_amount)?;
+    to.collateral_amount = to.collateral_amount.add(from.collateral_amount)?;
+    from.synthetic_amount = Decimal::new(0, from.synthetic_amount.scale);
+    from.collateral_amount = Decimal::new(0, from.collateral_amount.scale);
+
+    for index in 0..from.collateral_amounts.len() {
+        let amount = from.collateral_amounts[index];
+        if amount.val == 0 {
+            continue;
+        }
+        // Recipient may not hold given basket collateral yet
+        if to.collateral_amounts[index].scale != amount.scale {
+            to.collateral_amounts[index] = Decimal::new(0, amount.scale);
+        }
+        to.collateral_amounts[index] = to.collateral_amounts[index].add(amount)?;
+        from.collateral_amounts[index] = Decimal::new(0, amount.scale);
+    }
+    Ok(())
+}
+// Returns true if new liquidation_deadline was set
+pub fn update_liquidation_deadline(
+    exchange_account: &mut ExchangeAccount,
+    user_debt: Decimal,
+    max_debt: Decimal,
+    slot: u64,
+    liquidation_buffer: u32,
+) -> Result<bool> {
+    // If account is undercollateralized set liquidation_deadline
+    // After liquidation_deadline slot account can be liquidated
+    if max_debt.gt(user_debt)? {
+        exchange_account.liquidation_deadline = u64::MAX;
+    } else if exchange_account.liquidation_deadline == u64::MAX {
+        exchange_account.liquidation_deadline =
+            slot.checked_add(liquidation_buffer.into()).unwrap();
+        return Ok(true);
+    }
+    Ok(false)
+}
+// Checks instructions sysvar for instruction of this program with given data prefix
+// executed later in current transaction
+pub fn is_followed_by_instruction(instructions: &AccountInfo, data: &[u8]) -> Result<bool> {
+    let mut index = load_current_index_checked(instructions)? as usize + 1;
+    loop {
+        let instruction = match load_instruction_at_checked(index, instructions) {
+            Ok(instruction) => instruction,
+            // Index out of bounds, no more instructions
+            Err(ProgramError::InvalidArgument) => return Ok(false),
+            Err(err) => return Err(err.into()),
+        };
+        if instruction.program_id == crate::ID && instruction.data.starts_with(data) {
+            return Ok(true);
+        }
+        index += 1;
+    }
+}
 pub enum OracleType {
     Pyth = 0,
     Chainlink = 1,
@@ -432,13 +1071,67 @@ pub fn to_oracle_type(value: u8) -> Result<OracleType> {
         _ => Err(ErrorCode::InvalidOracleType.into()),
     }
 }
+#[derive(PartialEq, Debug)]
+pub enum VaultType {
+    // Collateralized debt position with interest and liquidation penalties
+    Standard = 0,
+    // Stablecoin collateral only, liquidated without penalty against its peg
+    Stablecoin = 1,
+    // No interest, only open_fee is charged on borrow
+    InterestFree = 2,
+}
+pub fn to_vault_type(value: u8) -> Result<VaultType> {
+    match value {
+        0 => Ok(VaultType::Standard),
+        1 => Ok(VaultType::Stablecoin),
+        2 => Ok(VaultType::InterestFree),
+        _ => Err(ErrorCode::InvalidVaultType.into()),
+    }
+}
+// Stablecoin collateral is never valued above its peg of 1 USD
+pub fn vault_collateral_price(vault: &Vault, collateral_price: Decimal) -> Result<Decimal> {
+    match to_vault_type(vault.kind)? {
+        VaultType::Stablecoin => {
+            let peg = Decimal::from_integer(1).to_price();
+            match collateral_price.gt(peg)? {
+                true => Ok(peg),
+                false => Ok(collateral_price),
+            }
+        }
+        _ => Ok(collateral_price),
+    }
+}
+// Tops up rent from payer and extends account to new_size, appended bytes are zeroed
+pub fn realloc_account<'info>(
+    account_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    rent: &Rent,
+    new_size: usize,
+) -> Result<()> {
+    let rent_exempt_balance = rent.minimum_balance(new_size);
+    let current_balance = account_info.lamports();
+    if rent_exempt_balance > current_balance {
+        let transfer_instruction = system_instruction::transfer(
+            payer.key,
+            account_info.key,
+            rent_exempt_balance.checked_sub(current_balance).unwrap(),
+        );
+        invoke(
+            &transfer_instruction,
+            &[payer.clone(), account_info.clone(), system_program.clone()],
+        )?;
+    }
+    account_info.realloc(new_size, true)?;
+    Ok(())
+}
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::{cell::RefCell, u64};
 
     #[test]
-    fn adjust_staking_account_test() {
+    fn adjust_staking_account_rounds_test() {
         let staking_round_length = 100;
         let amount_per_round = Decimal::from_sny(300);
         let slot = 12u64;
@@ -482,7 +1175,7 @@ mod tests {
                 ..Default::default()
             };
             let exchange_account_copy = exchange_account.clone();
-            adjust_staking_account(&mut exchange_account, &staking);
+            adjust_staking_account_rounds(&mut exchange_account, &staking);
             assert_ne!(
                 exchange_account.user_staking_data,
                 exchange_account_copy.user_staking_data
@@ -516,7 +1209,7 @@ mod tests {
                 ..Default::default()
             };
             let exchange_account_copy = exchange_account.clone();
-            adjust_staking_account(&mut exchange_account, &staking);
+            adjust_staking_account_rounds(&mut exchange_account, &staking);
             assert_ne!(
                 exchange_account.user_staking_data,
                 exchange_account_copy.user_staking_data
@@ -550,7 +1243,7 @@ mod tests {
                 ..Default::default()
             };
             let exchange_account_copy = exchange_account.clone();
-            adjust_staking_account(&mut exchange_account, &staking);
+            adjust_staking_account_rounds(&mut exchange_account, &staking);
             assert_eq!(
                 exchange_account.user_staking_data,
                 exchange_account_copy.user_staking_data
@@ -558,115 +1251,697 @@ mod tests {
         }
     }
     #[test]
-    fn adjust_staking_rounds_with_fixed_round_length_test() {
-        let staking_round_length = 100;
-        let amount_per_round = Decimal::from_sny(300);
-        let debt_shares = 999u64;
-        let staking = Staking {
-            round_length: staking_round_length,
-            amount_per_round: amount_per_round,
-            finished_round: StakingRound {
-                all_points: 0,
-                amount: Decimal::from_sny(0),
-                start: 0,
-            },
-            current_round: StakingRound {
-                all_points: 0,
-                amount: Decimal::from_sny(0),
-                start: staking_round_length.into(),
-            },
-            next_round: StakingRound {
-                all_points: 0,
-                amount: amount_per_round,
-                start: (staking_round_length * 2).into(),
+    fn test_adjust_staking_rewards() {
+        let state = State {
+            debt_shares: 1_000,
+            boosted_debt_shares: 1_000,
+            staking: Staking {
+                round_length: 100,
+                amount_per_round: Decimal::from_integer(100).to_sny(),
+                ..Default::default()
             },
+            reward_per_share: Decimal::new(0, REWARD_PER_SHARE_SCALE),
+            last_reward_update: 200,
             ..Default::default()
         };
-        let original_state = State {
-            debt_shares: debt_shares,
-            staking: staking,
-            ..Default::default()
-        };
+        // Before accrual start
         {
-            // Should stay same
-            let mut adjusted_state = original_state.clone();
-            adjust_staking_rounds(&mut adjusted_state, 150);
-            assert_eq!(adjusted_state, original_state);
-        }
-        {
-            // Should stay same
-            let mut adjusted_state = original_state.clone();
-            adjust_staking_rounds(&mut adjusted_state, 200);
-            assert_eq!(adjusted_state, original_state);
+            let mut adjusted_state = state;
+            adjust_staking_rewards(&mut adjusted_state, 150);
+            assert_eq!(adjusted_state, state);
         }
+        // Half of round, 50 SNY / 1000 shares
         {
-            // Should move one round forward
-            let mut adjusted_state = original_state.clone();
-            adjust_staking_rounds(&mut adjusted_state, 201);
-            assert_ne!(adjusted_state, original_state);
+            let mut adjusted_state = state;
+            adjust_staking_rewards(&mut adjusted_state, 250);
             assert_eq!(
-                adjusted_state.staking.finished_round,
-                original_state.staking.current_round
+                adjusted_state.reward_per_share,
+                Decimal::new(5, 2).to_scale(REWARD_PER_SHARE_SCALE)
             );
+            assert_eq!({ adjusted_state.last_reward_update }, 250);
+
+            // Same slot
+            adjust_staking_rewards(&mut adjusted_state, 250);
             assert_eq!(
-                adjusted_state.staking.current_round,
-                original_state.staking.next_round
+                adjusted_state.reward_per_share,
+                Decimal::new(5, 2).to_scale(REWARD_PER_SHARE_SCALE)
             );
+            // Next half of round with doubled boosted shares
+            adjusted_state.boosted_debt_shares = 2_000;
+            adjust_staking_rewards(&mut adjusted_state, 300);
             assert_eq!(
-                adjusted_state.staking.next_round,
-                StakingRound {
-                    start: 300,
-                    all_points: debt_shares,
-                    amount: original_state.staking.amount_per_round,
-                }
+                adjusted_state.reward_per_share,
+                Decimal::new(75, 3).to_scale(REWARD_PER_SHARE_SCALE)
             );
         }
+        // No stakers
         {
-            // Should move one round forward
-            let mut adjusted_state = original_state.clone();
-            adjust_staking_rounds(&mut adjusted_state, 300);
-            assert_ne!(adjusted_state, original_state);
+            let mut adjusted_state = state;
+            adjusted_state.debt_shares = 0;
+            adjusted_state.boosted_debt_shares = 0;
+            adjust_staking_rewards(&mut adjusted_state, 250);
+            assert_eq!(adjusted_state.reward_per_share, state.reward_per_share);
+            assert_eq!({ adjusted_state.last_reward_update }, 250);
+        }
+    }
+    #[test]
+    fn test_adjust_staking_account() {
+        let state = State {
+            reward_per_share: Decimal::new(15, 2).to_scale(REWARD_PER_SHARE_SCALE),
+            swap_tax_reward_per_share: Decimal::new(0, REWARD_PER_SHARE_SCALE),
+            ..Default::default()
+        };
+        let exchange_account = ExchangeAccount {
+            debt_shares: 300,
+            boosted_debt_shares: 300,
+            user_staking_data: UserStaking {
+                amount_to_claim: Decimal::from_integer(1).to_sny(),
+                ..Default::default()
+            },
+            last_reward_per_share: Decimal::new(5, 2).to_scale(REWARD_PER_SHARE_SCALE),
+            ..Default::default()
+        };
+        // 0.1 SNY per share
+        {
+            let mut exchange_account = exchange_account;
+            adjust_staking_account(&mut exchange_account, &state);
             assert_eq!(
-                adjusted_state.staking.finished_round,
-                original_state.staking.current_round
+                exchange_account.user_staking_data.amount_to_claim,
+                Decimal::from_integer(31).to_sny()
             );
             assert_eq!(
-                adjusted_state.staking.current_round,
-                original_state.staking.next_round
+                exchange_account.last_reward_per_share,
+                state.reward_per_share
             );
+
+            // Rewards are not accrued twice
+            adjust_staking_account(&mut exchange_account, &state);
             assert_eq!(
-                adjusted_state.staking.next_round,
-                StakingRound {
-                    start: 300,
-                    all_points: debt_shares,
-                    amount: original_state.staking.amount_per_round,
-                }
+                exchange_account.user_staking_data.amount_to_claim,
+                Decimal::from_integer(31).to_sny()
             );
         }
+        // No shares
         {
-            // Should move two rounds forward
-            let mut adjusted_state = original_state.clone();
-            adjust_staking_rounds(&mut adjusted_state, 301);
-            assert_ne!(adjusted_state, original_state);
-            assert_eq!(
-                adjusted_state.staking.finished_round,
-                original_state.staking.next_round
-            );
+            let mut exchange_account = exchange_account;
+            exchange_account.debt_shares = 0;
+            exchange_account.boosted_debt_shares = 0;
+            adjust_staking_account(&mut exchange_account, &state);
             assert_eq!(
-                adjusted_state.staking.current_round,
-                StakingRound {
-                    start: 300,
-                    all_points: debt_shares,
-                    amount: original_state.staking.amount_per_round,
-                }
+                exchange_account.user_staking_data.amount_to_claim,
+                Decimal::from_integer(1).to_sny()
             );
             assert_eq!(
-                adjusted_state.staking.next_round,
-                StakingRound {
-                    start: 400,
-                    all_points: debt_shares,
-                    amount: original_state.staking.amount_per_round,
-                }
+                exchange_account.last_reward_per_share,
+                state.reward_per_share
+            );
+        }
+    }
+    #[test]
+    fn test_adjust_staking_account_swap_tax() {
+        let state = State {
+            reward_per_share: Decimal::new(0, REWARD_PER_SHARE_SCALE),
+            swap_tax_reward_per_share: Decimal::new(3, 2).to_scale(REWARD_PER_SHARE_SCALE),
+            ..Default::default()
+        };
+        let exchange_account = ExchangeAccount {
+            debt_shares: 200,
+            user_staking_data: UserStaking {
+                amount_to_claim: Decimal::from_sny(0),
+                ..Default::default()
+            },
+            last_reward_per_share: Decimal::new(0, REWARD_PER_SHARE_SCALE),
+            ..Default::default()
+        };
+        // Zeroed entry accrues since start of distribution
+        {
+            let mut exchange_account = exchange_account;
+            adjust_staking_account(&mut exchange_account, &state);
+            assert_eq!(
+                exchange_account.swap_tax_rewards.amount_to_claim,
+                Decimal::from_integer(6).to_usd()
+            );
+            assert_eq!(
+                exchange_account.swap_tax_rewards.last_reward_per_share,
+                state.swap_tax_reward_per_share
+            );
+
+            // Rewards are not accrued twice
+            adjust_staking_account(&mut exchange_account, &state);
+            assert_eq!(
+                exchange_account.swap_tax_rewards.amount_to_claim,
+                Decimal::from_integer(6).to_usd()
+            );
+        }
+        // Rounding down
+        {
+            let mut exchange_account = exchange_account;
+            exchange_account.debt_shares = 1;
+            exchange_account.swap_tax_rewards = UserRewardStream {
+                amount_to_claim: Decimal::from_usd(0),
+                last_reward_per_share: Decimal::new(2_999_999_999, 11)
+                    .to_scale(REWARD_PER_SHARE_SCALE),
+            };
+            adjust_staking_account(&mut exchange_account, &state);
+            assert_eq!(
+                exchange_account.swap_tax_rewards.amount_to_claim,
+                Decimal::from_usd(0)
+            );
+        }
+    }
+    #[test]
+    fn test_adjust_staking_boost() {
+        let sny_address = Pubkey::new_unique();
+        let mut assets_list = AssetsList {
+            ..Default::default()
+        };
+        // xUSD asset
+        assets_list.append_asset(Asset {
+            price: Decimal::from_integer(1).to_price(),
+            ..Default::default()
+        });
+        assets_list.append_asset(Asset {
+            price: Decimal::from_integer(2).to_price(),
+            ..Default::default()
+        });
+        assets_list.append_collateral(Collateral {
+            asset_index: 1,
+            collateral_address: sny_address,
+            ..Default::default()
+        });
+        let mut exchange_account = ExchangeAccount {
+            debt_shares: 100,
+            boosted_debt_shares: 100,
+            user_staking_data: UserStaking {
+                amount_to_claim: Decimal::from_sny(0),
+                ..Default::default()
+            },
+            last_reward_per_share: Decimal::new(0, REWARD_PER_SHARE_SCALE),
+            ..Default::default()
+        };
+        exchange_account.append(CollateralEntry {
+            collateral_address: sny_address,
+            amount: Decimal::from_integer(10).to_sny().to_u64(),
+            ..Default::default()
+        });
+        // Other stakers hold 900 shares without boost
+        let mut state = State {
+            debt_shares: 1_000,
+            boosted_debt_shares: 1_000,
+            max_staking_boost: Decimal::from_percent(50),
+            staking: Staking {
+                round_length: 100,
+                amount_per_round: Decimal::from_integer(101).to_sny(),
+                ..Default::default()
+            },
+            reward_per_share: Decimal::new(0, REWARD_PER_SHARE_SCALE),
+            swap_tax_reward_per_share: Decimal::new(0, REWARD_PER_SHARE_SCALE),
+            ..Default::default()
+        };
+
+        // 20 USD of SNY for 100 USD of debt
+        adjust_staking_boost(
+            &mut state,
+            &mut exchange_account,
+            &assets_list,
+            Decimal::from_integer(100).to_usd(),
+        );
+        assert_eq!(exchange_account.staking_boost, Decimal::from_percent(10));
+        assert_eq!({ exchange_account.boosted_debt_shares }, 110);
+        assert_eq!({ state.boosted_debt_shares }, 1_010);
+        assert_eq!({ state.debt_shares }, 1_000);
+
+        // Boost weights shares, so whole round still distributes amount_per_round
+        adjust_staking_rewards(&mut state, 100);
+        adjust_staking_account(&mut exchange_account, &state);
+        let others_reward = calculate_staking_reward(
+            900,
+            state.reward_per_share,
+            Decimal::new(0, REWARD_PER_SHARE_SCALE),
+            SNY_SCALE,
+        );
+        assert_eq!(
+            exchange_account.user_staking_data.amount_to_claim,
+            Decimal::from_integer(11).to_sny()
+        );
+        assert_eq!(others_reward, Decimal::from_integer(90).to_sny());
+
+        // Without SNY collateral boost is removed from total
+        exchange_account.collaterals[0].amount = 0;
+        adjust_staking_boost(
+            &mut state,
+            &mut exchange_account,
+            &assets_list,
+            Decimal::from_integer(100).to_usd(),
+        );
+        assert_eq!(exchange_account.staking_boost, Decimal::from_percent(0));
+        assert_eq!({ exchange_account.boosted_debt_shares }, 100);
+        assert_eq!({ state.boosted_debt_shares }, 1_000);
+    }
+    #[test]
+    fn test_adjust_staking_boost_stale() {
+        let sny_address = Pubkey::new_unique();
+        let mut assets_list = AssetsList {
+            ..Default::default()
+        };
+        // xUSD asset
+        assets_list.append_asset(Asset {
+            price: Decimal::from_integer(1).to_price(),
+            ..Default::default()
+        });
+        assets_list.append_asset(Asset {
+            price: Decimal::from_integer(20).to_price(),
+            ..Default::default()
+        });
+        assets_list.append_collateral(Collateral {
+            asset_index: 1,
+            collateral_address: sny_address,
+            ..Default::default()
+        });
+        let mut exchange_account = ExchangeAccount {
+            debt_shares: 100,
+            boosted_debt_shares: 100,
+            ..Default::default()
+        };
+        exchange_account.append(CollateralEntry {
+            collateral_address: sny_address,
+            amount: Decimal::from_integer(10).to_sny().to_u64(),
+            ..Default::default()
+        });
+        let mut state = State {
+            debt_shares: 1_000,
+            boosted_debt_shares: 1_000,
+            max_staking_boost: Decimal::from_percent(50),
+            ..Default::default()
+        };
+
+        // 200 USD of SNY locks in max boost for 100 USD of debt
+        adjust_staking_boost(
+            &mut state,
+            &mut exchange_account,
+            &assets_list,
+            Decimal::from_integer(100).to_usd(),
+        );
+        assert_eq!(exchange_account.staking_boost, Decimal::from_percent(50));
+        assert_eq!({ exchange_account.boosted_debt_shares }, 150);
+        assert_eq!({ state.boosted_debt_shares }, 1_050);
+
+        // SNY falls to 2 USD and debt grows to 200 USD without any action of owner
+        assets_list.assets[1].price = Decimal::from_integer(2).to_price();
+        adjust_staking_boost(
+            &mut state,
+            &mut exchange_account,
+            &assets_list,
+            Decimal::from_integer(200).to_usd(),
+        );
+        assert_eq!(exchange_account.staking_boost, Decimal::from_percent(5));
+        assert_eq!({ exchange_account.boosted_debt_shares }, 105);
+        assert_eq!({ state.boosted_debt_shares }, 1_005);
+        assert_eq!({ state.debt_shares }, 1_000);
+    }
+    #[test]
+    fn test_distribute_swap_tax() {
+        let state = State {
+            debt_shares: 1_000,
+            swap_tax_reserve: Decimal::from_usd(0),
+            swap_tax_reward_ratio: Decimal::from_percent(40),
+            swap_tax_reward_per_share: Decimal::new(0, REWARD_PER_SHARE_SCALE),
+            ..Default::default()
+        };
+        // 40% of tax to stakers
+        {
+            let mut state = state;
+            distribute_swap_tax(&mut state, Decimal::from_integer(100).to_usd());
+            assert_eq!(
+                state.swap_tax_reward_per_share,
+                Decimal::new(4, 2).to_scale(REWARD_PER_SHARE_SCALE)
+            );
+            assert_eq!(state.swap_tax_reserve, Decimal::from_integer(60).to_usd());
+        }
+        // Distribution disabled
+        {
+            let mut state = state;
+            state.swap_tax_reward_ratio = Decimal::from_percent(0);
+            distribute_swap_tax(&mut state, Decimal::from_integer(100).to_usd());
+            assert_eq!(
+                state.swap_tax_reward_per_share,
+                Decimal::new(0, REWARD_PER_SHARE_SCALE)
+            );
+            assert_eq!(state.swap_tax_reserve, Decimal::from_integer(100).to_usd());
+        }
+        // No stakers
+        {
+            let mut state = state;
+            state.debt_shares = 0;
+            distribute_swap_tax(&mut state, Decimal::from_integer(100).to_usd());
+            assert_eq!(
+                state.swap_tax_reward_per_share,
+                Decimal::new(0, REWARD_PER_SHARE_SCALE)
+            );
+            assert_eq!(state.swap_tax_reserve, Decimal::from_integer(100).to_usd());
+        }
+    }
+    #[test]
+    fn test_adjust_reward_stream() {
+        let reward_stream = RewardStream {
+            amount_per_slot: Decimal::from_integer(1).to_scale(9),
+            end_slot: 300,
+            reward_per_share: Decimal::new(0, REWARD_PER_SHARE_SCALE),
+            last_update: 200,
+            ..Default::default()
+        };
+        // 50 tokens / 1000 shares
+        {
+            let mut reward_stream = reward_stream;
+            adjust_reward_stream(&mut reward_stream, 1_000, 250);
+            assert_eq!(
+                reward_stream.reward_per_share,
+                Decimal::new(5, 2).to_scale(REWARD_PER_SHARE_SCALE)
+            );
+            assert_eq!({ reward_stream.last_update }, 250);
+        }
+        // Accrued only until end slot
+        {
+            let mut reward_stream = reward_stream;
+            adjust_reward_stream(&mut reward_stream, 1_000, 400);
+            assert_eq!(
+                reward_stream.reward_per_share,
+                Decimal::new(1, 1).to_scale(REWARD_PER_SHARE_SCALE)
+            );
+            assert_eq!({ reward_stream.last_update }, 300);
+
+            adjust_reward_stream(&mut reward_stream, 1_000, 500);
+            assert_eq!(
+                reward_stream.reward_per_share,
+                Decimal::new(1, 1).to_scale(REWARD_PER_SHARE_SCALE)
+            );
+        }
+        // Streams are accrued with SNY rewards
+        {
+            let mut state = State {
+                debt_shares: 1_000,
+                head_reward_streams: 1,
+                last_reward_update: u64::MAX,
+                ..Default::default()
+            };
+            state.reward_streams[0] = reward_stream;
+            adjust_staking_rewards(&mut state, 250);
+            assert_eq!(
+                state.reward_streams[0].reward_per_share,
+                Decimal::new(5, 2).to_scale(REWARD_PER_SHARE_SCALE)
+            );
+            // Streams over head are skipped
+            assert_eq!(state.reward_streams[1], RewardStream::default());
+        }
+    }
+    #[test]
+    fn test_adjust_staking_account_reward_streams() {
+        let mut state = State {
+            reward_per_share: Decimal::new(0, REWARD_PER_SHARE_SCALE),
+            swap_tax_reward_per_share: Decimal::new(0, REWARD_PER_SHARE_SCALE),
+            ..Default::default()
+        };
+        state.append_reward_stream(RewardStream {
+            amount_per_slot: Decimal::from_integer(1).to_scale(9),
+            reward_per_share: Decimal::new(2, 1).to_scale(REWARD_PER_SHARE_SCALE),
+            ..Default::default()
+        });
+        let exchange_account = ExchangeAccount {
+            debt_shares: 100,
+            user_staking_data: UserStaking {
+                amount_to_claim: Decimal::from_sny(0),
+                ..Default::default()
+            },
+            last_reward_per_share: Decimal::new(0, REWARD_PER_SHARE_SCALE),
+            ..Default::default()
+        };
+        // Entry of stream added after account creation
+        {
+            let mut exchange_account = exchange_account;
+            adjust_staking_account(&mut exchange_account, &state);
+            assert_eq!(
+                exchange_account.reward_streams[0],
+                UserRewardStream {
+                    amount_to_claim: Decimal::from_integer(20).to_scale(9),
+                    last_reward_per_share: Decimal::new(2, 1).to_scale(REWARD_PER_SHARE_SCALE),
+                }
+            );
+            assert_eq!(
+                exchange_account.user_staking_data.amount_to_claim,
+                Decimal::from_sny(0)
+            );
+            // Rewards are not accrued twice
+            adjust_staking_account(&mut exchange_account, &state);
+            assert_eq!(
+                exchange_account.reward_streams[0].amount_to_claim,
+                Decimal::from_integer(20).to_scale(9)
+            );
+        }
+        // Entry with rewards already accrued
+        {
+            let mut exchange_account = exchange_account;
+            exchange_account.reward_streams[0] = UserRewardStream {
+                amount_to_claim: Decimal::from_integer(1).to_scale(9),
+                last_reward_per_share: Decimal::new(1, 1).to_scale(REWARD_PER_SHARE_SCALE),
+            };
+            adjust_staking_account(&mut exchange_account, &state);
+            assert_eq!(
+                exchange_account.reward_streams[0].amount_to_claim,
+                Decimal::from_integer(11).to_scale(9)
+            );
+            assert_eq!(
+                exchange_account.reward_streams[1],
+                UserRewardStream::default()
+            );
+        }
+    }
+    #[test]
+    fn test_release_vested_rewards() {
+        let exchange_account = ExchangeAccount {
+            user_staking_data: UserStaking {
+                amount_to_claim: Decimal::from_sny(1_000),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        // Vesting disabled
+        {
+            let mut exchange_account = exchange_account;
+            let released = release_vested_rewards(&mut exchange_account, 0, 500);
+            assert_eq!(released, Decimal::from_sny(1_000));
+            assert_eq!(
+                exchange_account.user_staking_data.amount_to_claim,
+                Decimal::from_sny(0)
+            );
+            assert_eq!({ exchange_account.vesting_amount }, 0);
+        }
+        // Vesting started
+        {
+            let mut exchange_account = exchange_account;
+            let released = release_vested_rewards(&mut exchange_account, 100, 500);
+            assert_eq!(released, Decimal::from_sny(0));
+            assert_eq!({ exchange_account.vesting_amount }, 1_000);
+            assert_eq!({ exchange_account.vesting_start }, 500);
+            assert_eq!({ exchange_account.vesting_end }, 600);
+
+            // Quarter of schedule with new rewards
+            exchange_account.user_staking_data.amount_to_claim = Decimal::from_sny(750);
+            let released = release_vested_rewards(&mut exchange_account, 100, 525);
+            assert_eq!(released, Decimal::from_sny(250));
+            assert_eq!({ exchange_account.vesting_amount }, 1_500);
+            assert_eq!({ exchange_account.vesting_start }, 525);
+            // (750 * 75 + 750 * 100) / 1500
+            assert_eq!({ exchange_account.vesting_end }, 612);
+
+            // Schedule ended after vesting was disabled
+            let released = release_vested_rewards(&mut exchange_account, 0, 700);
+            assert_eq!(released, Decimal::from_sny(1_500));
+            assert_eq!({ exchange_account.vesting_amount }, 0);
+        }
+    }
+    #[test]
+    fn test_exit_rewards_vesting() {
+        let mut exchange_account = ExchangeAccount {
+            debt_shares: 100,
+            boosted_debt_shares: 100,
+            user_staking_data: UserStaking {
+                amount_to_claim: Decimal::from_sny(600),
+                ..Default::default()
+            },
+            vesting_amount: 1_000,
+            vesting_start: 500,
+            vesting_end: 600,
+            last_reward_per_share: Decimal::new(0, REWARD_PER_SHARE_SCALE),
+            ..Default::default()
+        };
+        // 500 vested, 500 + 600 unvested with 10% penalty
+        let (released, penalty) =
+            exit_rewards_vesting(&mut exchange_account, 100, Decimal::from_percent(10), 550);
+        assert_eq!(released, Decimal::from_sny(1_490));
+        assert_eq!(penalty, Decimal::from_sny(110));
+        assert_eq!({ exchange_account.vesting_amount }, 0);
+        assert_eq!(
+            exchange_account.user_staking_data.amount_to_claim,
+            Decimal::from_sny(0)
+        );
+
+        let mut state = State {
+            debt_shares: 1_100,
+            boosted_debt_shares: 1_100,
+            reward_per_share: Decimal::new(0, REWARD_PER_SHARE_SCALE),
+            ..Default::default()
+        };
+        distribute_early_exit_penalty(&mut state, &mut exchange_account, penalty);
+        // 110 / 1000 shares of other stakers
+        assert_eq!(
+            state.reward_per_share,
+            Decimal::new(110_000_000_000, REWARD_PER_SHARE_SCALE)
+        );
+        assert_eq!(
+            exchange_account.last_reward_per_share,
+            state.reward_per_share
+        );
+
+        // Exiting account is the only staker
+        let mut state = State {
+            debt_shares: 100,
+            boosted_debt_shares: 100,
+            reward_per_share: Decimal::new(0, REWARD_PER_SHARE_SCALE),
+            ..Default::default()
+        };
+        distribute_early_exit_penalty(&mut state, &mut exchange_account, penalty);
+        assert_eq!(
+            state.reward_per_share,
+            Decimal::new(0, REWARD_PER_SHARE_SCALE)
+        );
+    }
+    #[test]
+    fn test_calculate_round_reward() {
+        let round = StakingRound {
+            start: 0,
+            amount: Decimal::from_sny(300),
+            all_points: 1_000,
+        };
+        assert_eq!(calculate_round_reward(&round, 100), Decimal::from_sny(30));
+        // Rounding down
+        assert_eq!(calculate_round_reward(&round, 3), Decimal::from_sny(0));
+        // Empty round
+        let round = StakingRound {
+            all_points: 0,
+            ..round
+        };
+        assert_eq!(calculate_round_reward(&round, 0), Decimal::from_sny(0));
+    }
+    #[test]
+    fn adjust_staking_rounds_with_fixed_round_length_test() {
+        let staking_round_length = 100;
+        let amount_per_round = Decimal::from_sny(300);
+        let debt_shares = 999u64;
+        let staking = Staking {
+            round_length: staking_round_length,
+            amount_per_round: amount_per_round,
+            finished_round: StakingRound {
+                all_points: 0,
+                amount: Decimal::from_sny(0),
+                start: 0,
+            },
+            current_round: StakingRound {
+                all_points: 0,
+                amount: Decimal::from_sny(0),
+                start: staking_round_length.into(),
+            },
+            next_round: StakingRound {
+                all_points: 0,
+                amount: amount_per_round,
+                start: (staking_round_length * 2).into(),
+            },
+            ..Default::default()
+        };
+        let original_state = State {
+            debt_shares: debt_shares,
+            staking: staking,
+            ..Default::default()
+        };
+        {
+            // Should stay same
+            let mut adjusted_state = original_state.clone();
+            adjust_staking_rounds(&mut adjusted_state, 150);
+            assert_eq!(adjusted_state, original_state);
+        }
+        {
+            // Should stay same
+            let mut adjusted_state = original_state.clone();
+            adjust_staking_rounds(&mut adjusted_state, 200);
+            assert_eq!(adjusted_state, original_state);
+        }
+        {
+            // Should move one round forward
+            let mut adjusted_state = original_state.clone();
+            adjust_staking_rounds(&mut adjusted_state, 201);
+            assert_ne!(adjusted_state, original_state);
+            assert_eq!(
+                adjusted_state.staking.finished_round,
+                original_state.staking.current_round
+            );
+            assert_eq!(
+                adjusted_state.staking.current_round,
+                original_state.staking.next_round
+            );
+            assert_eq!(
+                adjusted_state.staking.next_round,
+                StakingRound {
+                    start: 300,
+                    all_points: debt_shares,
+                    amount: original_state.staking.amount_per_round,
+                }
+            );
+        }
+        {
+            // Should move one round forward
+            let mut adjusted_state = original_state.clone();
+            adjust_staking_rounds(&mut adjusted_state, 300);
+            assert_ne!(adjusted_state, original_state);
+            assert_eq!(
+                adjusted_state.staking.finished_round,
+                original_state.staking.current_round
+            );
+            assert_eq!(
+                adjusted_state.staking.current_round,
+                original_state.staking.next_round
+            );
+            assert_eq!(
+                adjusted_state.staking.next_round,
+                StakingRound {
+                    start: 300,
+                    all_points: debt_shares,
+                    amount: original_state.staking.amount_per_round,
+                }
+            );
+        }
+        {
+            // Should move two rounds forward
+            let mut adjusted_state = original_state.clone();
+            adjust_staking_rounds(&mut adjusted_state, 301);
+            assert_ne!(adjusted_state, original_state);
+            assert_eq!(
+                adjusted_state.staking.finished_round,
+                original_state.staking.next_round
+            );
+            assert_eq!(
+                adjusted_state.staking.current_round,
+                StakingRound {
+                    start: 300,
+                    all_points: debt_shares,
+                    amount: original_state.staking.amount_per_round,
+                }
+            );
+            assert_eq!(
+                adjusted_state.staking.next_round,
+                StakingRound {
+                    start: 400,
+                    all_points: debt_shares,
+                    amount: original_state.staking.amount_per_round,
+                }
             );
         }
         {
@@ -1022,6 +2297,68 @@ mod tests {
         }
     }
     #[test]
+    fn test_calculate_debt_with_interest() {
+        // 1% debt interest rate
+        let state = State {
+            debt_interest_rate: Decimal::from_percent(1).to_interest_rate(),
+            accumulated_debt_interest: Decimal::from_usd(0),
+            last_debt_adjustment: 0,
+            max_delay: 10,
+            ..Default::default()
+        };
+        let current_slot = 100;
+
+        let mut assets_list = AssetsList {
+            ..Default::default()
+        };
+        assets_list.append_asset(Asset {
+            price: Decimal::from_integer(1).to_price(),
+            twap: Decimal::from_integer(1).to_price(),
+            last_update: current_slot,
+            ..Default::default()
+        });
+        assets_list.append_synthetic(Synthetic {
+            supply: Decimal::from_integer(100_000).to_usd(),
+            swapline_supply: Decimal::from_usd(0),
+            borrowed_supply: Decimal::from_usd(0),
+            asset_index: assets_list.head_assets - 1,
+            ..Default::default()
+        });
+        // Before adjustment period
+        {
+            let debt =
+                calculate_debt_with_interest(&state, &assets_list, current_slot, 59).unwrap();
+            assert_eq!(debt, Decimal::from_integer(100_000).to_usd());
+        }
+        // Same debt as after adjustment, accounts unchanged
+        {
+            let debt =
+                calculate_debt_with_interest(&state, &assets_list, current_slot, 120).unwrap();
+            assert_eq!(debt, Decimal::from_usd(100_000_003_806));
+            assert_eq!(state.accumulated_debt_interest, Decimal::from_usd(0));
+            assert_eq!(
+                assets_list.synthetics[0].supply,
+                Decimal::from_integer(100_000).to_usd()
+            );
+
+            let mut state = state;
+            let assets_ref = RefCell::new(assets_list);
+            let adjusted_debt = calculate_debt_with_adjustment(
+                &mut state,
+                &mut assets_ref.borrow_mut(),
+                current_slot,
+                120,
+            )
+            .unwrap();
+            assert_eq!(adjusted_debt, debt);
+        }
+        // Outdated oracle
+        {
+            let result = calculate_debt_with_interest(&state, &assets_list, current_slot + 11, 120);
+            assert!(result.is_err());
+        }
+    }
+    #[test]
     fn test_calculate_debt_with_interest_multi_adjustment() {
         {
             let slot = 100;
@@ -1181,6 +2518,7 @@ mod tests {
         let vault = Vault {
             // APR 5.5%
             debt_interest_rate: Decimal::new(55, 3).to_interest_rate(),
+            current_interest_rate: Decimal::new(55, 3).to_interest_rate(),
             accumulated_interest_rate: initial_interest_rate,
             accumulated_interest: Decimal::new(0, synthetic_total_supply.scale),
             mint_amount: synthetic_borrowed_supply,
@@ -1641,129 +2979,644 @@ mod tests {
             assert!(cascade_increase.is_ok());
             assert_eq!(
                 vault_entry.synthetic_amount,
-                Decimal::from_integer(6).to_scale(synthetic_decimal)
+                Decimal::from_integer(6).to_scale(synthetic_decimal)
+            );
+            assert_eq!(
+                vault.mint_amount,
+                Decimal::from_integer(7).to_scale(synthetic_decimal)
+            );
+            assert_eq!(
+                synthetic.borrowed_supply,
+                Decimal::from_integer(15).to_scale(synthetic_decimal)
+            );
+            assert_eq!(
+                synthetic.supply,
+                Decimal::from_integer(55).to_scale(synthetic_decimal)
+            );
+        }
+        // increase, crossed max mint amount
+        {
+            let mut synthetic = base_synthetic.clone();
+            let mut vault = base_vault.clone();
+            let mut vault_entry = base_vault_entry.clone();
+            let mint_amount = Decimal::from_integer(20).to_scale(synthetic_decimal);
+
+            let cascade_increase =
+                vault_entry.increase_supply_cascade(&mut vault, &mut synthetic, mint_amount);
+
+            assert!(cascade_increase.is_err());
+        }
+        // increase, crossed max synthetic supply
+        {
+            let mut synthetic = base_synthetic.clone();
+            let mut vault = base_vault.clone();
+            let mut vault_entry = base_vault_entry.clone();
+            vault.max_borrow = Decimal::from_integer(300).to_scale(synthetic_decimal);
+            let mint_amount = Decimal::from_integer(100).to_scale(synthetic_decimal);
+
+            let cascade_increase =
+                vault_entry.increase_supply_cascade(&mut vault, &mut synthetic, mint_amount);
+
+            assert!(cascade_increase.is_err());
+        }
+        // decrease supply, crossed max supply
+        {
+            let mut synthetic = base_synthetic.clone();
+            let mut vault = base_vault.clone();
+            let mut vault_entry = base_vault_entry.clone();
+            let synthetic_supply = Decimal::from_integer(300).to_scale(synthetic_decimal);
+            let borrowed_supply = Decimal::from_integer(200).to_scale(synthetic_decimal);
+            let vault_mint_amount = Decimal::from_integer(200).to_scale(synthetic_decimal);
+            let vault_entry_synthetic_amount =
+                Decimal::from_integer(150).to_scale(synthetic_decimal);
+
+            synthetic.supply = synthetic_supply;
+            synthetic.borrowed_supply = borrowed_supply;
+            vault.mint_amount = vault_mint_amount;
+            vault_entry.synthetic_amount = vault_entry_synthetic_amount;
+            let burn_amount = Decimal::from_integer(10).to_scale(synthetic_decimal);
+
+            let cascade_decrease =
+                vault_entry.decrease_supply_cascade(&mut vault, &mut synthetic, burn_amount);
+
+            assert!(cascade_decrease.is_ok());
+            assert_eq!(
+                vault_entry.synthetic_amount,
+                Decimal::from_integer(140).to_scale(synthetic_decimal)
+            );
+            assert_eq!(
+                vault.mint_amount,
+                Decimal::from_integer(190).to_scale(synthetic_decimal)
+            );
+            assert_eq!(
+                synthetic.borrowed_supply,
+                Decimal::from_integer(190).to_scale(synthetic_decimal)
+            );
+            assert_eq!(
+                synthetic.supply,
+                Decimal::from_integer(290).to_scale(synthetic_decimal)
+            );
+        }
+    }
+
+    #[test]
+    fn test_set_mint_amount_safely() {
+        // decrease is safe
+        {
+            let mut vault = Vault {
+                mint_amount: Decimal::from_integer(2),
+                max_borrow: Decimal::from_integer(2),
+                ..Default::default()
+            };
+            let new_mint_amount = Decimal::from_integer(1);
+
+            vault.set_mint_amount_safely(new_mint_amount).unwrap();
+
+            assert_eq!(vault.mint_amount, new_mint_amount)
+        }
+        // increase without error
+        {
+            let mut vault = Vault {
+                mint_amount: Decimal::from_integer(2),
+                max_borrow: Decimal::from_integer(4),
+                ..Default::default()
+            };
+            let new_mint_amount = Decimal::from_integer(3);
+
+            vault.set_mint_amount_safely(new_mint_amount).unwrap();
+
+            assert_eq!(vault.mint_amount, new_mint_amount)
+        }
+        // checking error
+        {
+            let mut vault = Vault {
+                mint_amount: Decimal::from_integer(2),
+                max_borrow: Decimal::from_integer(2),
+                ..Default::default()
+            };
+            let new_mint_amount = Decimal::from_integer(3);
+
+            let result = vault.set_mint_amount_safely(new_mint_amount);
+
+            assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn test_vault_collateral_cascade() {
+        let mut vault = Vault {
+            ..Default::default()
+        };
+        vault.append_collateral(VaultCollateral {
+            collateral_amount: Decimal::from_integer(10).to_scale(9),
+            ..Default::default()
+        });
+        let mut vault_entry = VaultEntry {
+            ..Default::default()
+        };
+        // Zeroed entry takes scale of vault collateral
+        vault_entry
+            .increase_collateral_cascade(&mut vault, 0, Decimal::from_integer(5).to_scale(9))
+            .unwrap();
+        assert_eq!(
+            vault_entry.collateral_amounts[0],
+            Decimal::from_integer(5).to_scale(9)
+        );
+        assert_eq!(
+            vault.collaterals[0].collateral_amount,
+            Decimal::from_integer(15).to_scale(9)
+        );
+
+        vault_entry
+            .decrease_collateral_cascade(&mut vault, 0, Decimal::from_integer(2).to_scale(9))
+            .unwrap();
+        assert_eq!(
+            vault_entry.collateral_amounts[0],
+            Decimal::from_integer(3).to_scale(9)
+        );
+        assert_eq!(
+            vault.collaterals[0].collateral_amount,
+            Decimal::from_integer(13).to_scale(9)
+        );
+
+        // Cannot withdraw more than entry holds
+        let result = vault_entry.decrease_collateral_cascade(
+            &mut vault,
+            0,
+            Decimal::from_integer(4).to_scale(9),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_leverage_vault_entry() {
+        let synthetic_asset = Asset {
+            price: Decimal::from_integer(1).to_price(),
+            ..Default::default()
+        };
+        let collateral_price = Decimal::from_integer(1).to_price();
+        let vault = Vault {
+            collateral_ratio: Decimal::from_percent(80),
+            open_fee: Decimal::from_percent(0),
+            max_borrow: Decimal::from_integer(1000).to_usd(),
+            mint_amount: Decimal::from_usd(0),
+            accumulated_interest: Decimal::from_usd(0),
+            collateral_amount: Decimal::from_integer(100).to_scale(6),
+            ..Default::default()
+        };
+        let vault_entry = VaultEntry {
+            synthetic_amount: Decimal::from_usd(0),
+            collateral_amount: Decimal::from_integer(100).to_scale(6),
+            ..Default::default()
+        };
+        let synthetic = Synthetic {
+            supply: Decimal::from_integer(1000).to_usd(),
+            max_supply: Decimal::from_integer(10000).to_usd(),
+            borrowed_supply: Decimal::from_usd(0),
+            swapline_supply: Decimal::from_integer(1000).to_usd(),
+            ..Default::default()
+        };
+        let swapline = Swapline {
+            fee: Decimal::from_percent(0),
+            balance: Decimal::from_integer(1000).to_scale(6),
+            accumulated_fee: Decimal::new(0, 6),
+            ..Default::default()
+        };
+        // Converges towards 200 collateral and 100 debt
+        {
+            let (mut vault, mut vault_entry, mut synthetic, mut swapline) =
+                (vault, vault_entry, synthetic, swapline);
+            let collateral_out = leverage_vault_entry(
+                &mut vault,
+                &mut vault_entry,
+                &mut synthetic,
+                synthetic_asset,
+                &mut swapline,
+                collateral_price,
+                Decimal::from_percent(50),
+            )
+            .unwrap();
+            assert!(collateral_out.gt(Decimal::new(99_900_000, 6)).unwrap());
+            assert!(collateral_out
+                .lt(Decimal::from_integer(100).to_scale(6))
+                .unwrap());
+            assert_eq!(
+                vault_entry.collateral_amount,
+                Decimal::from_integer(100)
+                    .to_scale(6)
+                    .add(collateral_out)
+                    .unwrap()
+            );
+            assert_eq!(vault.collateral_amount, vault_entry.collateral_amount);
+            // No fees so debt equals bought collateral
+            assert_eq!(
+                vault_entry.synthetic_amount,
+                Decimal::new(collateral_out.val, XUSD_SCALE)
+            );
+            assert_eq!(vault.mint_amount, vault_entry.synthetic_amount);
+            assert_eq!(synthetic.borrowed_supply, vault_entry.synthetic_amount);
+            // Borrowed synthetic is swapped back right away
+            assert_eq!(synthetic.supply, Decimal::from_integer(1000).to_usd());
+            assert_eq!(
+                swapline.balance,
+                Decimal::from_integer(1000)
+                    .to_scale(6)
+                    .sub(collateral_out)
+                    .unwrap()
+            );
+        }
+        // Limited by max_borrow
+        {
+            let (mut vault, mut vault_entry, mut synthetic, mut swapline) =
+                (vault, vault_entry, synthetic, swapline);
+            vault.max_borrow = Decimal::from_integer(30).to_usd();
+            let collateral_out = leverage_vault_entry(
+                &mut vault,
+                &mut vault_entry,
+                &mut synthetic,
+                synthetic_asset,
+                &mut swapline,
+                collateral_price,
+                Decimal::from_percent(50),
+            )
+            .unwrap();
+            assert_eq!(collateral_out, Decimal::from_integer(30).to_scale(6));
+            assert_eq!(vault.mint_amount, Decimal::from_integer(30).to_usd());
+        }
+        // Limited by swapline liquidity
+        {
+            let (mut vault, mut vault_entry, mut synthetic, mut swapline) =
+                (vault, vault_entry, synthetic, swapline);
+            synthetic.swapline_supply = Decimal::from_integer(20).to_usd();
+            let collateral_out = leverage_vault_entry(
+                &mut vault,
+                &mut vault_entry,
+                &mut synthetic,
+                synthetic_asset,
+                &mut swapline,
+                collateral_price,
+                Decimal::from_percent(50),
+            )
+            .unwrap();
+            assert_eq!(collateral_out, Decimal::from_integer(20).to_scale(6));
+            assert_eq!(synthetic.swapline_supply, Decimal::from_usd(0));
+        }
+        // Fees stay within target ratio
+        {
+            let (mut vault, mut vault_entry, mut synthetic, mut swapline) =
+                (vault, vault_entry, synthetic, swapline);
+            vault.open_fee = Decimal::from_percent(1);
+            swapline.fee = Decimal::from_percent(1);
+            leverage_vault_entry(
+                &mut vault,
+                &mut vault_entry,
+                &mut synthetic,
+                synthetic_asset,
+                &mut swapline,
+                collateral_price,
+                Decimal::from_percent(50),
+            )
+            .unwrap();
+            let borrow_limit = calculate_vault_borrow_limit(
+                collateral_price,
+                synthetic_asset,
+                synthetic,
+                vault_entry.collateral_amount,
+                Decimal::from_percent(50),
+            );
+            assert!(vault_entry.synthetic_amount.lte(borrow_limit).unwrap());
+            assert!(vault.accumulated_interest.gt(Decimal::from_usd(0)).unwrap());
+            assert!(swapline.accumulated_fee.gt(Decimal::new(0, 6)).unwrap());
+        }
+        // Vault with basket collaterals
+        {
+            let (mut vault, mut vault_entry, mut synthetic, mut swapline) =
+                (vault, vault_entry, synthetic, swapline);
+            vault.append_collateral(VaultCollateral {
+                ..Default::default()
+            });
+            let result = leverage_vault_entry(
+                &mut vault,
+                &mut vault_entry,
+                &mut synthetic,
+                synthetic_asset,
+                &mut swapline,
+                collateral_price,
+                Decimal::from_percent(50),
+            );
+            assert!(result.is_err());
+            assert_eq!(vault_entry.synthetic_amount, Decimal::from_usd(0));
+        }
+    }
+    #[test]
+    fn test_deleverage_vault_entry() {
+        let synthetic_asset = Asset {
+            price: Decimal::from_integer(1).to_price(),
+            ..Default::default()
+        };
+        let collateral_price = Decimal::from_integer(1).to_price();
+        let vault = Vault {
+            deleverage_fee: Decimal::from_percent(0),
+            mint_amount: Decimal::from_integer(70).to_usd(),
+            collateral_amount: Decimal::from_integer(100).to_scale(6),
+            ..Default::default()
+        };
+        let vault_entry = VaultEntry {
+            synthetic_amount: Decimal::from_integer(70).to_usd(),
+            collateral_amount: Decimal::from_integer(100).to_scale(6),
+            deleverage_threshold: Decimal::from_percent(65),
+            deleverage_target: Decimal::from_percent(50),
+            ..Default::default()
+        };
+        let synthetic = Synthetic {
+            supply: Decimal::from_integer(1000).to_usd(),
+            borrowed_supply: Decimal::from_integer(70).to_usd(),
+            swapline_supply: Decimal::from_usd(0),
+            ..Default::default()
+        };
+        let swapline = Swapline {
+            fee: Decimal::from_percent(0),
+            balance: Decimal::new(0, 6),
+            accumulated_fee: Decimal::new(0, 6),
+            limit: Decimal::from_integer(1000).to_scale(6),
+            ..Default::default()
+        };
+        // Sells 40 to repay 40, ratio drops from 70% to 50%
+        {
+            let (mut vault, mut vault_entry, mut synthetic, mut swapline) =
+                (vault, vault_entry, synthetic, swapline);
+            let (sold_collateral, keeper_fee) = deleverage_vault_entry(
+                &mut vault,
+                &mut vault_entry,
+                &mut synthetic,
+                synthetic_asset,
+                &mut swapline,
+                collateral_price,
+            )
+            .unwrap();
+            assert_eq!(sold_collateral, Decimal::from_integer(40).to_scale(6));
+            assert_eq!(keeper_fee, Decimal::new(0, 6));
+            assert_eq!(
+                vault_entry.collateral_amount,
+                Decimal::from_integer(60).to_scale(6)
+            );
+            assert_eq!(
+                vault_entry.synthetic_amount,
+                Decimal::from_integer(30).to_usd()
             );
+            assert_eq!(vault.mint_amount, Decimal::from_integer(30).to_usd());
             assert_eq!(
-                vault.mint_amount,
-                Decimal::from_integer(7).to_scale(synthetic_decimal)
+                vault.collateral_amount,
+                Decimal::from_integer(60).to_scale(6)
             );
+            assert_eq!(swapline.balance, Decimal::from_integer(40).to_scale(6));
             assert_eq!(
-                synthetic.borrowed_supply,
-                Decimal::from_integer(15).to_scale(synthetic_decimal)
+                synthetic.swapline_supply,
+                Decimal::from_integer(40).to_usd()
             );
+            // Swapped synthetic is burned right away
+            assert_eq!(synthetic.supply, Decimal::from_integer(1000).to_usd());
             assert_eq!(
-                synthetic.supply,
-                Decimal::from_integer(55).to_scale(synthetic_decimal)
+                synthetic.borrowed_supply,
+                Decimal::from_integer(30).to_usd()
             );
         }
-        // increase, crossed max mint amount
+        // Fees are paid from collateral, ratio still reaches target
         {
-            let mut synthetic = base_synthetic.clone();
-            let mut vault = base_vault.clone();
-            let mut vault_entry = base_vault_entry.clone();
-            let mint_amount = Decimal::from_integer(20).to_scale(synthetic_decimal);
-
-            let cascade_increase =
-                vault_entry.increase_supply_cascade(&mut vault, &mut synthetic, mint_amount);
-
-            assert!(cascade_increase.is_err());
+            let (mut vault, mut vault_entry, mut synthetic, mut swapline) =
+                (vault, vault_entry, synthetic, swapline);
+            vault.deleverage_fee = Decimal::from_percent(1);
+            swapline.fee = Decimal::from_percent(1);
+            let (sold_collateral, keeper_fee) = deleverage_vault_entry(
+                &mut vault,
+                &mut vault_entry,
+                &mut synthetic,
+                synthetic_asset,
+                &mut swapline,
+                collateral_price,
+            )
+            .unwrap();
+            assert_eq!(keeper_fee, sold_collateral.mul(Decimal::from_percent(1)));
+            assert!(swapline.accumulated_fee.gt(Decimal::new(0, 6)).unwrap());
+            let target_limit = calculate_vault_borrow_limit(
+                collateral_price,
+                synthetic_asset,
+                synthetic,
+                vault_entry.collateral_amount,
+                Decimal::from_percent(50),
+            );
+            let difference = vault_entry.synthetic_amount.val as i128 - target_limit.val as i128;
+            assert!(difference.abs() <= 1);
         }
-        // increase, crossed max synthetic supply
+        // Ratio below threshold
         {
-            let mut synthetic = base_synthetic.clone();
-            let mut vault = base_vault.clone();
-            let mut vault_entry = base_vault_entry.clone();
-            vault.max_borrow = Decimal::from_integer(300).to_scale(synthetic_decimal);
-            let mint_amount = Decimal::from_integer(100).to_scale(synthetic_decimal);
-
-            let cascade_increase =
-                vault_entry.increase_supply_cascade(&mut vault, &mut synthetic, mint_amount);
-
-            assert!(cascade_increase.is_err());
+            let (mut vault, mut vault_entry, mut synthetic, mut swapline) =
+                (vault, vault_entry, synthetic, swapline);
+            vault_entry.deleverage_threshold = Decimal::from_percent(75);
+            let result = deleverage_vault_entry(
+                &mut vault,
+                &mut vault_entry,
+                &mut synthetic,
+                synthetic_asset,
+                &mut swapline,
+                collateral_price,
+            );
+            assert!(result.is_err());
         }
-        // decrease supply, crossed max supply
+        // Entry at 5% LTV with basket is not sold off on primary collateral alone
         {
-            let mut synthetic = base_synthetic.clone();
-            let mut vault = base_vault.clone();
-            let mut vault_entry = base_vault_entry.clone();
-            let synthetic_supply = Decimal::from_integer(300).to_scale(synthetic_decimal);
-            let borrowed_supply = Decimal::from_integer(200).to_scale(synthetic_decimal);
-            let vault_mint_amount = Decimal::from_integer(200).to_scale(synthetic_decimal);
-            let vault_entry_synthetic_amount =
-                Decimal::from_integer(150).to_scale(synthetic_decimal);
-
-            synthetic.supply = synthetic_supply;
-            synthetic.borrowed_supply = borrowed_supply;
-            vault.mint_amount = vault_mint_amount;
-            vault_entry.synthetic_amount = vault_entry_synthetic_amount;
-            let burn_amount = Decimal::from_integer(10).to_scale(synthetic_decimal);
-
-            let cascade_decrease =
-                vault_entry.decrease_supply_cascade(&mut vault, &mut synthetic, burn_amount);
-
-            assert!(cascade_decrease.is_ok());
-            assert_eq!(
-                vault_entry.synthetic_amount,
-                Decimal::from_integer(140).to_scale(synthetic_decimal)
+            let (mut vault, mut vault_entry, mut synthetic, mut swapline) =
+                (vault, vault_entry, synthetic, swapline);
+            vault.append_collateral(VaultCollateral {
+                ..Default::default()
+            });
+            vault_entry.collateral_amounts[0] = Decimal::from_integer(1000).to_scale(6);
+            vault_entry.synthetic_amount = Decimal::from_integer(60).to_usd();
+            vault_entry.deleverage_threshold = Decimal::from_percent(50);
+            vault_entry.deleverage_target = Decimal::from_percent(40);
+            let result = deleverage_vault_entry(
+                &mut vault,
+                &mut vault_entry,
+                &mut synthetic,
+                synthetic_asset,
+                &mut swapline,
+                collateral_price,
             );
+            assert!(result.is_err());
             assert_eq!(
-                vault.mint_amount,
-                Decimal::from_integer(190).to_scale(synthetic_decimal)
+                vault_entry.collateral_amount,
+                Decimal::from_integer(100).to_scale(6)
+            );
+        }
+        // Disabled
+        {
+            let (mut vault, mut vault_entry, mut synthetic, mut swapline) =
+                (vault, vault_entry, synthetic, swapline);
+            vault_entry.deleverage_threshold = Decimal::default();
+            let result = deleverage_vault_entry(
+                &mut vault,
+                &mut vault_entry,
+                &mut synthetic,
+                synthetic_asset,
+                &mut swapline,
+                collateral_price,
             );
+            assert!(result.is_err());
+        }
+    }
+    #[test]
+    fn test_vault_collateral_price() {
+        let above_peg = Decimal::from_price(101_000_000);
+        let below_peg = Decimal::from_price(97_000_000);
+        // Standard
+        {
+            let vault = Vault {
+                kind: VaultType::Standard as u8,
+                ..Default::default()
+            };
             assert_eq!(
-                synthetic.borrowed_supply,
-                Decimal::from_integer(190).to_scale(synthetic_decimal)
+                vault_collateral_price(&vault, above_peg).unwrap(),
+                above_peg
             );
             assert_eq!(
-                synthetic.supply,
-                Decimal::from_integer(290).to_scale(synthetic_decimal)
+                vault_collateral_price(&vault, below_peg).unwrap(),
+                below_peg
             );
         }
-    }
-
-    #[test]
-    fn test_set_mint_amount_safely() {
-        // decrease is safe
+        // Stablecoin is capped at peg
         {
-            let mut vault = Vault {
-                mint_amount: Decimal::from_integer(2),
-                max_borrow: Decimal::from_integer(2),
+            let vault = Vault {
+                kind: VaultType::Stablecoin as u8,
                 ..Default::default()
             };
-            let new_mint_amount = Decimal::from_integer(1);
-
-            vault.set_mint_amount_safely(new_mint_amount).unwrap();
-
-            assert_eq!(vault.mint_amount, new_mint_amount)
+            assert_eq!(
+                vault_collateral_price(&vault, above_peg).unwrap(),
+                Decimal::from_integer(1).to_price()
+            );
+            assert_eq!(
+                vault_collateral_price(&vault, below_peg).unwrap(),
+                below_peg
+            );
         }
-        // increase without error
+        // Unknown type
         {
-            let mut vault = Vault {
-                mint_amount: Decimal::from_integer(2),
-                max_borrow: Decimal::from_integer(4),
+            let vault = Vault {
+                kind: 3,
                 ..Default::default()
             };
-            let new_mint_amount = Decimal::from_integer(3);
-
-            vault.set_mint_amount_safely(new_mint_amount).unwrap();
-
-            assert_eq!(vault.mint_amount, new_mint_amount)
+            assert!(vault_collateral_price(&vault, above_peg).is_err());
         }
-        // checking error
+        // Kind is independent from vault_type seed
         {
-            let mut vault = Vault {
-                mint_amount: Decimal::from_integer(2),
-                max_borrow: Decimal::from_integer(2),
+            let vault = Vault {
+                vault_type: 3,
+                kind: VaultType::Standard as u8,
                 ..Default::default()
             };
-            let new_mint_amount = Decimal::from_integer(3);
-
-            let result = vault.set_mint_amount_safely(new_mint_amount);
-
-            assert!(result.is_err());
+            assert_eq!(
+                vault_collateral_price(&vault, above_peg).unwrap(),
+                above_peg
+            );
         }
     }
+    #[test]
+    fn test_liquidate_stablecoin_vault_at_peg() {
+        use crate::math::{calculate_liquidation_penalty_split, calculate_vault_seized_collateral};
 
+        let usdc_decimals = 6;
+        let xusd_asset = Asset {
+            price: Decimal::from_integer(1).to_price(),
+            ..Default::default()
+        };
+        let xusd_synthetic = Synthetic {
+            supply: Decimal::from_integer(1_000).to_usd(),
+            ..Default::default()
+        };
+        let vault = Vault {
+            kind: VaultType::Stablecoin as u8,
+            liquidation_threshold: Decimal::from_percent(95),
+            liquidation_ratio: Decimal::from_percent(50),
+            liquidation_penalty_liquidator: Decimal::from_percent(0),
+            liquidation_penalty_exchange: Decimal::from_percent(0),
+            ..Default::default()
+        };
+        let collateral_amount = Decimal::from_integer(100).to_scale(usdc_decimals);
+        let synthetic_amount = Decimal::from_integer(96).to_usd();
+        let liquidation_amount = synthetic_amount.mul(vault.liquidation_ratio);
+
+        // Collateral above peg is liquidated as if it was at peg
+        for oracle_price in [
+            Decimal::from_price(102_000_000),
+            Decimal::from_integer(1).to_price(),
+        ] {
+            let collateral_price = vault_collateral_price(&vault, oracle_price).unwrap();
+            let liquidation_limit = calculate_vault_borrow_limit(
+                collateral_price,
+                xusd_asset,
+                xusd_synthetic,
+                collateral_amount,
+                vault.liquidation_threshold,
+            );
+            // 100 * 1 * 95% = 95 < 96
+            assert_eq!(liquidation_limit, Decimal::from_integer(95).to_usd());
+            assert!(liquidation_limit.lt(synthetic_amount).unwrap());
+
+            let seized_collateral = calculate_vault_seized_collateral(
+                liquidation_amount,
+                vault
+                    .liquidation_penalty_liquidator
+                    .add(vault.liquidation_penalty_exchange)
+                    .unwrap(),
+                xusd_asset.price,
+                collateral_price,
+                usdc_decimals,
+            );
+            // Repaid 1:1 without penalty
+            assert_eq!(
+                seized_collateral,
+                Decimal::from_integer(48).to_scale(usdc_decimals)
+            );
+            let (to_liquidator, to_exchange) = calculate_liquidation_penalty_split(
+                seized_collateral,
+                vault.liquidation_penalty_liquidator,
+                vault.liquidation_penalty_exchange,
+            );
+            assert_eq!(to_liquidator, seized_collateral);
+            assert_eq!(to_exchange, Decimal::new(0, usdc_decimals));
+        }
+        // Same position is safe when valued at oracle price above peg
+        {
+            let liquidation_limit = calculate_vault_borrow_limit(
+                Decimal::from_price(102_000_000),
+                xusd_asset,
+                xusd_synthetic,
+                collateral_amount,
+                vault.liquidation_threshold,
+            );
+            // 100 * 1.02 * 95% = 96.9
+            assert_eq!(liquidation_limit, Decimal::new(969, 1).to_usd());
+            assert!(liquidation_limit.gte(synthetic_amount).unwrap());
+        }
+        // Depegged collateral is seized at oracle price
+        {
+            let oracle_price = Decimal::from_price(96_000_000);
+            let collateral_price = vault_collateral_price(&vault, oracle_price).unwrap();
+            assert_eq!(collateral_price, oracle_price);
+
+            let seized_collateral = calculate_vault_seized_collateral(
+                liquidation_amount,
+                Decimal::from_percent(0),
+                xusd_asset.price,
+                collateral_price,
+                usdc_decimals,
+            );
+            // 48 / 0.96 = 50
+            assert_eq!(
+                seized_collateral,
+                Decimal::from_integer(50).to_scale(usdc_decimals)
+            );
+        }
+    }
     #[test]
     fn test_append_exchange_account() {
         {
@@ -1825,6 +3678,393 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_move_position() {
+        let sny = Pubkey::new_unique();
+        let btc = Pubkey::new_unique();
+        let eth = Pubkey::new_unique();
+        let mut from = ExchangeAccount {
+            debt_shares: 100,
+            liquidation_deadline: 1000,
+            user_staking_data: UserStaking {
+                amount_to_claim: Decimal::from_sny(20),
+                finished_round_points: 10,
+                current_round_points: 100,
+                next_round_points: 100,
+                last_update: 50,
+            },
+            ..Default::default()
+        };
+        from.reward_streams[0] = UserRewardStream {
+            amount_to_claim: Decimal::new(30, 8),
+            last_reward_per_share: Decimal::new(0, REWARD_PER_SHARE_SCALE),
+        };
+        from.swap_tax_rewards = UserRewardStream {
+            amount_to_claim: Decimal::from_usd(70),
+            last_reward_per_share: Decimal::new(0, REWARD_PER_SHARE_SCALE),
+        };
+        // 100 of 400 vested at slot 500
+        from.vesting_amount = 400;
+        from.vesting_start = 400;
+        from.vesting_end = 800;
+        from.append(CollateralEntry {
+            amount: 30,
+            collateral_address: sny,
+            index: 0,
+        });
+        from.append(CollateralEntry {
+            amount: 40,
+            collateral_address: btc,
+            index: 1,
+        });
+        let mut to = ExchangeAccount {
+            debt_shares: 50,
+            liquidation_deadline: u64::MAX,
+            user_staking_data: UserStaking {
+                amount_to_claim: Decimal::from_sny(5),
+                finished_round_points: 50,
+                current_round_points: 50,
+                next_round_points: 50,
+                last_update: 50,
+            },
+            ..Default::default()
+        };
+        to.reward_streams[0] = UserRewardStream {
+            amount_to_claim: Decimal::new(5, 8),
+            last_reward_per_share: Decimal::new(0, REWARD_PER_SHARE_SCALE),
+        };
+        to.swap_tax_rewards = UserRewardStream {
+            amount_to_claim: Decimal::from_usd(10),
+            last_reward_per_share: Decimal::new(0, REWARD_PER_SHARE_SCALE),
+        };
+        // 100 of 200 vested at slot 500
+        to.vesting_amount = 200;
+        to.vesting_start = 300;
+        to.vesting_end = 700;
+        to.append(CollateralEntry {
+            amount: 15,
+            collateral_address: eth,
+            index: 2,
+        });
+        to.append(CollateralEntry {
+            amount: 10,
+            collateral_address: sny,
+            index: 0,
+        });
+
+        let slot = 500;
+        move_position(&mut from, &mut to, slot);
+
+        // Source is empty
+        assert_eq!({ from.debt_shares }, 0);
+        assert_eq!(from.head, 0);
+        assert_eq!(from.collaterals[0], CollateralEntry::default());
+        assert_eq!(from.user_staking_data.amount_to_claim, Decimal::from_sny(0));
+        assert_eq!({ from.user_staking_data.current_round_points }, 0);
+        assert_eq!(
+            { from.reward_streams[0].amount_to_claim },
+            Decimal::new(0, 8)
+        );
+        assert_eq!({ from.vesting_amount }, 0);
+        assert_eq!(
+            { from.swap_tax_rewards.amount_to_claim },
+            Decimal::from_usd(0)
+        );
+        assert_eq!({ from.liquidation_deadline }, u64::MAX);
+        // Destination holds everything
+        assert_eq!({ to.debt_shares }, 150);
+        assert_eq!(to.head, 3);
+        assert_eq!({ to.collaterals[0].amount }, 15);
+        assert_eq!({ to.collaterals[1].amount }, 40);
+        assert_eq!({ to.collaterals[2].amount }, 40);
+        assert_eq!(to.collaterals[2].collateral_address, btc);
+        assert_eq!(to.user_staking_data.amount_to_claim, Decimal::from_sny(25));
+        assert_eq!({ to.user_staking_data.finished_round_points }, 60);
+        assert_eq!({ to.user_staking_data.current_round_points }, 150);
+        assert_eq!({ to.user_staking_data.next_round_points }, 150);
+        assert_eq!(
+            { to.reward_streams[0].amount_to_claim },
+            Decimal::new(35, 8)
+        );
+        assert_eq!({ to.reward_streams[1] }, UserRewardStream::default());
+        assert_eq!(
+            { to.swap_tax_rewards.amount_to_claim },
+            Decimal::from_usd(80)
+        );
+        // 300 and 100 remaining for 300 and 200 slots
+        assert_eq!({ to.vesting_amount }, 600);
+        assert_eq!({ to.vesting_end }, 775);
+        // Vested part of both schedules, rounded down
+        assert_eq!(
+            calculate_vested_amount(to.vesting_amount, to.vesting_start, to.vesting_end, slot),
+            199
+        );
+        assert_eq!(
+            calculate_vested_amount(to.vesting_amount, to.vesting_start, to.vesting_end, 775),
+            600
+        );
+        assert_eq!({ to.liquidation_deadline }, 1000);
+    }
+
+    #[test]
+    fn test_move_vault_entry() {
+        let mut from = VaultEntry {
+            synthetic_amount: Decimal::from_usd(100),
+            collateral_amount: Decimal::new(300, 6),
+            ..Default::default()
+        };
+        from.collateral_amounts[1] = Decimal::new(50, 8);
+        // Fresh recipient
+        {
+            let mut to = VaultEntry {
+                synthetic_amount: Decimal::from_usd(0),
+                collateral_amount: Decimal::new(0, 6),
+                ..Default::default()
+            };
+            let mut from = from;
+            move_vault_entry(&mut from, &mut to).unwrap();
+
+            assert_eq!({ from.synthetic_amount }, Decimal::from_usd(0));
+            assert_eq!({ from.collateral_amount }, Decimal::new(0, 6));
+            assert_eq!({ from.collateral_amounts[1] }, Decimal::new(0, 8));
+            assert_eq!({ to.synthetic_amount }, Decimal::from_usd(100));
+            assert_eq!({ to.collateral_amount }, Decimal::new(300, 6));
+            assert_eq!({ to.collateral_amounts[0] }, Decimal::default());
+            assert_eq!({ to.collateral_amounts[1] }, Decimal::new(50, 8));
+        }
+        // Recipient with existing position
+        {
+            let mut to = VaultEntry {
+                synthetic_amount: Decimal::from_usd(20),
+                collateral_amount: Decimal::new(100, 6),
+                ..Default::default()
+            };
+            to.collateral_amounts[1] = Decimal::new(10, 8);
+            let mut from = from;
+            move_vault_entry(&mut from, &mut to).unwrap();
+
+            assert_eq!({ to.synthetic_amount }, Decimal::from_usd(120));
+            assert_eq!({ to.collateral_amount }, Decimal::new(400, 6));
+            assert_eq!({ to.collateral_amounts[1] }, Decimal::new(60, 8));
+        }
+        // Mismatched scale
+        {
+            let mut to = VaultEntry {
+                synthetic_amount: Decimal::from_usd(0),
+                collateral_amount: Decimal::new(0, 8),
+                ..Default::default()
+            };
+            let mut from = from;
+            assert!(move_vault_entry(&mut from, &mut to).is_err());
+        }
+    }
+
+    #[test]
+    fn test_update_liquidation_deadline() {
+        let slot = 100;
+        let buffer = 50;
+        // Collateralized account
+        {
+            let mut exchange_account = ExchangeAccount {
+                liquidation_deadline: 120,
+                ..Default::default()
+            };
+            let result = update_liquidation_deadline(
+                &mut exchange_account,
+                Decimal::from_usd(10),
+                Decimal::from_usd(11),
+                slot,
+                buffer,
+            )
+            .unwrap();
+            assert!(!result);
+            assert_eq!({ exchange_account.liquidation_deadline }, u64::MAX);
+        }
+        // Undercollateralized account
+        {
+            let mut exchange_account = ExchangeAccount {
+                liquidation_deadline: u64::MAX,
+                ..Default::default()
+            };
+            let result = update_liquidation_deadline(
+                &mut exchange_account,
+                Decimal::from_usd(11),
+                Decimal::from_usd(10),
+                slot,
+                buffer,
+            )
+            .unwrap();
+            assert!(result);
+            assert_eq!({ exchange_account.liquidation_deadline }, 150);
+            // Deadline already set
+            let result = update_liquidation_deadline(
+                &mut exchange_account,
+                Decimal::from_usd(11),
+                Decimal::from_usd(10),
+                slot + 10,
+                buffer,
+            )
+            .unwrap();
+            assert!(!result);
+            assert_eq!({ exchange_account.liquidation_deadline }, 150);
+        }
+    }
+
+    #[test]
+    fn test_is_followed_by_instruction() {
+        use anchor_lang::solana_program::instruction::Instruction;
+        use anchor_lang::solana_program::message::{Message, SanitizedMessage};
+        use anchor_lang::solana_program::sysvar::instructions::{
+            construct_instructions_data, store_current_index,
+        };
+        use std::convert::TryFrom;
+
+        let payer = Pubkey::new_unique();
+        let other_program = Pubkey::new_unique();
+        let repay_data = [7u8; 8];
+        let transaction = [
+            Instruction::new_with_bytes(crate::ID, &[1u8; 8], vec![]),
+            Instruction::new_with_bytes(other_program, &repay_data, vec![]),
+            Instruction::new_with_bytes(crate::ID, &repay_data, vec![]),
+        ];
+        let message = SanitizedMessage::try_from(Message::new(&transaction, Some(&payer))).unwrap();
+        let instructions_data = construct_instructions_data(&message, true);
+
+        let is_followed = |key: &Pubkey, current_index: u16, data: &[u8]| {
+            let mut instructions_data = instructions_data.clone();
+            store_current_index(&mut instructions_data, current_index);
+            let mut lamports = 0;
+            let owner = Pubkey::default();
+            let instructions = AccountInfo::new(
+                key,
+                false,
+                false,
+                &mut lamports,
+                &mut instructions_data,
+                &owner,
+                false,
+                0,
+            );
+            is_followed_by_instruction(&instructions, data)
+        };
+        let instructions_id = anchor_lang::solana_program::sysvar::instructions::ID;
+        // Instruction of this program later in transaction
+        assert!(is_followed(&instructions_id, 0, &repay_data).unwrap());
+        assert!(is_followed(&instructions_id, 1, &repay_data).unwrap());
+        // Only instructions after current one are checked
+        assert!(!is_followed(&instructions_id, 2, &repay_data).unwrap());
+        assert!(!is_followed(&instructions_id, 0, &[1u8; 8]).unwrap());
+        // Not an instructions sysvar
+        assert!(is_followed(&Pubkey::new_unique(), 0, &repay_data).is_err());
+    }
+
+    #[test]
+    fn test_vault_entry_is_empty() {
+        let mut vault_entry = VaultEntry {
+            synthetic_amount: Decimal::from_integer(10).to_usd(),
+            collateral_amount: Decimal::from_integer(1).to_scale(8),
+            collateral_amounts: [Decimal::new(0, 6); 4],
+            ..Default::default()
+        };
+        // Debt and collateral left
+        assert!(!vault_entry.is_empty());
+        // Collateral left
+        vault_entry.synthetic_amount = Decimal::new(0, XUSD_SCALE);
+        assert!(!vault_entry.is_empty());
+        // Nothing left
+        vault_entry.collateral_amount = Decimal::new(0, 8);
+        assert!(vault_entry.is_empty());
+        // Basket collateral left
+        vault_entry.collateral_amounts[2] = Decimal::new(1, 6);
+        assert!(!vault_entry.is_empty());
+        vault_entry.collateral_amounts[2] = Decimal::new(0, 6);
+        assert!(vault_entry.is_empty());
+        // Debt left
+        vault_entry.synthetic_amount = Decimal::new(1, XUSD_SCALE);
+        assert!(!vault_entry.is_empty());
+    }
+    #[test]
+    fn test_close_vault_entry_refunds_rent() {
+        use anchor_lang::{AccountsClose, Discriminator};
+
+        let owner_key = Pubkey::new_unique();
+        let vault_entry_key = Pubkey::new_unique();
+        let system_program = Pubkey::default();
+        let rent = Rent::default().minimum_balance(VAULT_ENTRY_SIZE);
+        let initial_owner_lamports = 1_000_000;
+
+        let mut owner_lamports = initial_owner_lamports;
+        let mut owner_data = vec![];
+        let owner = AccountInfo::new(
+            &owner_key,
+            true,
+            true,
+            &mut owner_lamports,
+            &mut owner_data,
+            &system_program,
+            false,
+            0,
+        );
+        // Opened entry holds rent exempt balance
+        let mut vault_entry_lamports = rent;
+        let mut vault_entry_data = vec![0u8; VAULT_ENTRY_SIZE];
+        vault_entry_data[..8].copy_from_slice(&VaultEntry::discriminator());
+        let vault_entry_info = AccountInfo::new(
+            &vault_entry_key,
+            false,
+            true,
+            &mut vault_entry_lamports,
+            &mut vault_entry_data,
+            &crate::ID,
+            false,
+            0,
+        );
+        let vault_entry = Loader::<VaultEntry>::try_from(&crate::ID, &vault_entry_info).unwrap();
+        {
+            let mut entry = vault_entry.load_mut().unwrap();
+            entry.owner = owner_key;
+            entry.synthetic_amount = Decimal::new(0, XUSD_SCALE);
+            entry.collateral_amount = Decimal::new(0, 6);
+            assert!(entry.is_empty());
+        }
+
+        vault_entry.close(owner.clone()).unwrap();
+        // Rent is returned to owner and entry can't be loaded anymore
+        assert_eq!(owner.lamports(), initial_owner_lamports + rent);
+        assert_eq!(vault_entry_info.lamports(), 0);
+        assert!(vault_entry.load().is_err());
+    }
+    #[test]
+    fn test_collateral_liquidation_penalties() {
+        let state = State {
+            penalty_to_liquidator: Decimal::from_percent(5),
+            penalty_to_exchange: Decimal::from_percent(5),
+            ..Default::default()
+        };
+        // Global penalties
+        {
+            let collateral = Collateral {
+                ..Default::default()
+            };
+            assert_eq!(
+                collateral.liquidation_penalties(&state),
+                (Decimal::from_percent(5), Decimal::from_percent(5))
+            );
+        }
+        // Overridden penalties
+        {
+            let collateral = Collateral {
+                penalty_to_liquidator: Decimal::from_percent(2),
+                penalty_to_exchange: Decimal::from_percent(0),
+                ..Default::default()
+            };
+            assert_eq!(
+                collateral.liquidation_penalties(&state),
+                (Decimal::from_percent(2), Decimal::from_percent(0))
+            );
+        }
+    }
+
     #[test]
     fn test_remove_exchange_account() {
         {
@@ -2079,6 +4319,7 @@ mod tests {
         let vault = Vault {
             // APR 5.5%
             debt_interest_rate: Decimal::new(55, 3).to_interest_rate(),
+            current_interest_rate: Decimal::new(55, 3).to_interest_rate(),
             accumulated_interest_rate: initial_interest_rate,
             last_update: 0,
             ..Default::default()
@@ -2099,6 +4340,41 @@ mod tests {
                 expected_accumulated_interest_rate
             );
             assert_eq!({ vault.last_update }, expected_last_update);
+            assert_eq!(vault.current_interest_rate, vault.debt_interest_rate);
+        }
+        // Utilization curve at full utilization sets max rate
+        {
+            let timestamp = 430;
+            let vault = &mut Vault {
+                debt_interest_rate: Decimal::from_percent(1).to_interest_rate(),
+                current_interest_rate: Decimal::from_percent(1).to_interest_rate(),
+                interest_curve_enabled: true,
+                optimal_utilization: Decimal::from_percent(80),
+                interest_rate_at_optimal: Decimal::from_percent(4).to_interest_rate(),
+                max_interest_rate: Decimal::new(55, 3).to_interest_rate(),
+                mint_amount: Decimal::from_integer(100).to_usd(),
+                max_borrow: Decimal::from_integer(100).to_usd(),
+                ..vault
+            };
+            adjust_vault_interest_rate(vault, timestamp);
+            assert_eq!(
+                vault.current_interest_rate,
+                Decimal::new(55, 3).to_interest_rate()
+            );
+
+            // Elapsed period accrues with max rate even though utilization dropped since
+            vault.mint_amount = Decimal::from_integer(0).to_usd();
+            vault.accumulated_interest_rate = initial_interest_rate;
+            adjust_vault_interest_rate(vault, timestamp + 420);
+
+            assert_eq!(
+                vault.accumulated_interest_rate,
+                Decimal::from_interest_rate(1000000732496424772)
+            );
+            assert_eq!(
+                vault.current_interest_rate,
+                Decimal::from_percent(1).to_interest_rate()
+            );
         }
     }
 }
@@ -14,7 +14,9 @@ import {
   TransactionInstruction,
   Transaction,
   sendAndConfirmRawTransaction,
-  Keypair
+  Keypair,
+  AccountMeta,
+  SYSVAR_INSTRUCTIONS_PUBKEY
 } from '@solana/web3.js'
 
 export const STATE_SEED = 'statev1'
@@ -213,6 +215,12 @@ export class Exchange {
       bump
     }
   }
+  // Instructions valuing vault entry take price feeds of all basket collaterals
+  private basketPriceFeedsAccounts(basketPriceFeeds: Array<PublicKey>): Array<AccountMeta> {
+    return basketPriceFeeds.map((priceFeed) => {
+      return { pubkey: priceFeed, isWritable: false, isSigner: false }
+    })
+  }
   public async getVaultForPair(synthetic: PublicKey, collateral: PublicKey, vaultType: number) {
     const { vaultAddress } = await this.getVaultAddress(synthetic, collateral, vaultType)
     const account = (await this.program.account.vault.fetch(vaultAddress)) as Vault
@@ -456,6 +464,97 @@ export class Exchange {
     )
     return account
   }
+  // Index 0 is account created by createExchangeAccount
+  public async getSubAccountAddress(owner: PublicKey, index: number) {
+    const indexBuffer = Buffer.alloc(2)
+    indexBuffer.writeUInt16LE(index)
+    const [account, bump] = await PublicKey.findProgramAddress(
+      [Buffer.from(utils.bytes.utf8.encode('accountv1')), owner.toBuffer(), indexBuffer],
+      this.program.programId
+    )
+    return { account, bump }
+  }
+  public async createSubAccountInstruction(owner: PublicKey, index: number) {
+    const { account, bump } = await this.getSubAccountAddress(owner, index)
+    const ix = (await this.program.instruction.createSubAccount(bump, index, {
+      accounts: {
+        exchangeAccount: account,
+        admin: owner,
+        payer: this.wallet.publicKey,
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId
+      }
+    })) as TransactionInstruction
+    return { account, ix }
+  }
+  public async transferCollateralInstruction({
+    exchangeAccount,
+    toExchangeAccount,
+    owner,
+    collateralAddress,
+    amount
+  }: TransferCollateralInstruction) {
+    return (await this.program.instruction.transferCollateral(collateralAddress, amount, {
+      accounts: {
+        state: this.stateAddress,
+        assetsList: this.state.assetsList,
+        exchangeAccount,
+        toExchangeAccount,
+        owner
+      }
+    })) as TransactionInstruction
+  }
+  // Both owners have to sign, receiving account takes over debt and collaterals
+  public async transferPositionInstruction({
+    exchangeAccount,
+    toExchangeAccount,
+    owner,
+    toOwner
+  }: TransferPositionInstruction) {
+    return (await this.program.instruction.transferPosition({
+      accounts: {
+        state: this.stateAddress,
+        assetsList: this.state.assetsList,
+        exchangeAccount,
+        toExchangeAccount,
+        owner,
+        toOwner
+      }
+    })) as TransactionInstruction
+  }
+  public async migrateExchangeAccountInstruction(exchangeAccount: PublicKey) {
+    return (await this.program.instruction.migrateExchangeAccount({
+      accounts: {
+        state: this.stateAddress,
+        exchangeAccount,
+        payer: this.wallet.publicKey,
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId
+      }
+    })) as TransactionInstruction
+  }
+  public async migrateStateInstruction() {
+    return (await this.program.instruction.migrateState({
+      accounts: {
+        state: this.stateAddress,
+        admin: this.state.admin
+      }
+    })) as TransactionInstruction
+  }
+  public async setDelegateInstruction({
+    exchangeAccount,
+    owner,
+    delegate,
+    permissions
+  }: SetDelegate) {
+    return (await this.program.instruction.setDelegate(permissions, {
+      accounts: {
+        exchangeAccount,
+        owner,
+        delegate
+      }
+    })) as TransactionInstruction
+  }
 
   public async depositInstruction({
     amount,
@@ -467,7 +566,7 @@ export class Exchange {
     return (await this.program.instruction.deposit(amount, {
       accounts: {
         state: this.stateAddress,
-        owner: owner,
+        signer: owner,
         exchangeAccount: exchangeAccount,
         userCollateralAccount: userCollateralAccount,
         tokenProgram: TOKEN_PROGRAM_ID,
@@ -492,7 +591,7 @@ export class Exchange {
         reserveAccount,
         userCollateralAccount,
         tokenProgram: TOKEN_PROGRAM_ID,
-        owner: owner,
+        signer: owner,
         exchangeAccount: exchangeAccount
       }
     }) as TransactionInstruction)
@@ -506,7 +605,7 @@ export class Exchange {
         to: to,
         tokenProgram: TOKEN_PROGRAM_ID,
         exchangeAccount: exchangeAccount,
-        owner: owner,
+        signer: owner,
         assetsList: this.state.assetsList
       }
     }) as TransactionInstruction)
@@ -565,6 +664,100 @@ export class Exchange {
       }
     }) as TransactionInstruction)
   }
+  // Collateral is seized before xUSD is burned, repayFlashLiquidationInstruction
+  // has to follow later in the same transaction
+  public async flashLiquidateInstruction({
+    exchangeAccount,
+    signer,
+    liquidationFund,
+    liquidatorCollateralAccount,
+    liquidatorUsdAccount,
+    reserveAccount,
+    amount
+  }: LiquidateInstruction) {
+    return await (this.program.instruction.flashLiquidate(amount, {
+      accounts: {
+        liquidate: {
+          state: this.stateAddress,
+          exchangeAuthority: this.exchangeAuthority,
+          assetsList: this.state.assetsList,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          usdToken: this.assetsList.synthetics[0].assetAddress,
+          liquidatorUsdAccount: liquidatorUsdAccount,
+          liquidatorCollateralAccount: liquidatorCollateralAccount,
+          exchangeAccount: exchangeAccount,
+          signer: signer,
+          liquidationFund: liquidationFund,
+          reserveAccount: reserveAccount
+        },
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY
+      }
+    }) as TransactionInstruction)
+  }
+  public async repayFlashLiquidationInstruction({
+    liquidatorUsdAccount,
+    signer
+  }: RepayFlashLiquidationInstruction) {
+    return await (this.program.instruction.repayFlashLiquidation({
+      accounts: {
+        state: this.stateAddress,
+        exchangeAuthority: this.exchangeAuthority,
+        assetsList: this.state.assetsList,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        usdToken: this.assetsList.synthetics[0].assetAddress,
+        liquidatorUsdAccount,
+        signer
+      }
+    }) as TransactionInstruction)
+  }
+  // Collaterals are seized in given order until amount is repaid
+  public async liquidateMultiInstruction({
+    exchangeAccount,
+    signer,
+    liquidatorUsdAccount,
+    collaterals,
+    amount
+  }: LiquidateMultiInstruction) {
+    const remainingAccounts: Array<AccountMeta> = []
+    collaterals.forEach((collateral) => {
+      remainingAccounts.push(
+        { pubkey: collateral.reserveAccount, isWritable: true, isSigner: false },
+        { pubkey: collateral.liquidationFund, isWritable: true, isSigner: false },
+        { pubkey: collateral.liquidatorCollateralAccount, isWritable: true, isSigner: false }
+      )
+    })
+
+    return await (this.program.instruction.liquidateMulti(amount, {
+      remainingAccounts,
+      accounts: {
+        state: this.stateAddress,
+        exchangeAuthority: this.exchangeAuthority,
+        assetsList: this.state.assetsList,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        usdToken: this.assetsList.synthetics[0].assetAddress,
+        liquidatorUsdAccount,
+        exchangeAccount,
+        signer
+      }
+    }) as TransactionInstruction)
+  }
+  // Read only, result is returned as LiquidationPreview in transaction return data
+  public async previewLiquidationInstruction({
+    exchangeAccount,
+    collateralAddress,
+    amount
+  }: PreviewLiquidationInstruction) {
+    return await (this.program.instruction.previewLiquidation(collateralAddress, amount, {
+      accounts: {
+        state: this.stateAddress,
+        assetsList: this.state.assetsList,
+        exchangeAccount
+      }
+    }) as TransactionInstruction)
+  }
+  public decodeLiquidationPreview(returnData: Buffer): LiquidationPreview {
+    return this.program.coder.types.decode('LiquidationPreview', returnData)
+  }
   public async burnInstruction({
     amount,
     exchangeAccount,
@@ -579,7 +772,7 @@ export class Exchange {
         userTokenAccountBurn: userTokenAccountBurn,
         tokenProgram: TOKEN_PROGRAM_ID,
         exchangeAccount: exchangeAccount,
-        owner: owner,
+        signer: owner,
         assetsList: this.state.assetsList
       }
     }) as TransactionInstruction)
@@ -595,22 +788,110 @@ export class Exchange {
   public async withdrawRewardsInstruction({
     exchangeAccount,
     owner,
-    userTokenAccount
+    userTokenAccount,
+    rewardStreams = []
   }: WithdrawRewardsInstruction) {
+    const remainingAccounts: Array<AccountMeta> = []
+    rewardStreams.forEach((rewardStream) => {
+      remainingAccounts.push(
+        { pubkey: rewardStream.fundAccount, isWritable: true, isSigner: false },
+        { pubkey: rewardStream.userTokenAccount, isWritable: true, isSigner: false }
+      )
+    })
+
     return await (this.program.instruction.withdrawRewards({
+      remainingAccounts,
       accounts: {
         state: this.stateAddress,
         exchangeAccount: exchangeAccount,
         exchangeAuthority: this.exchangeAuthority,
-        owner: owner,
+        signer: owner,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        userTokenAccount: userTokenAccount,
+        stakingFundAccount: this.state.staking.fundAccount
+      }
+    }) as TransactionInstruction)
+  }
+  // Unvested rewards are withdrawn without vesting, earlyExitPenalty is redistributed to stakers
+  public async withdrawRewardsEarlyInstruction({
+    exchangeAccount,
+    owner,
+    userTokenAccount
+  }: WithdrawRewardsInstruction) {
+    return await (this.program.instruction.withdrawRewardsEarly({
+      accounts: {
+        state: this.stateAddress,
+        exchangeAccount: exchangeAccount,
+        exchangeAuthority: this.exchangeAuthority,
+        signer: owner,
         tokenProgram: TOKEN_PROGRAM_ID,
         userTokenAccount: userTokenAccount,
         stakingFundAccount: this.state.staking.fundAccount
       }
     }) as TransactionInstruction)
   }
-  public async checkAccountInstruction(exchangeAccount: PublicKey) {
+  // SNY rewards are deposited as SNY collateral instead of vesting
+  public async compoundRewardsInstruction({ exchangeAccount, owner }: CompoundRewardsInstruction) {
+    return await (this.program.instruction.compoundRewards({
+      accounts: {
+        state: this.stateAddress,
+        exchangeAccount,
+        assetsList: this.state.assetsList,
+        reserveAddress: this.assetsList.collaterals[0].reserveAddress,
+        stakingFundAccount: this.state.staking.fundAccount,
+        signer: owner,
+        exchangeAuthority: this.exchangeAuthority,
+        tokenProgram: TOKEN_PROGRAM_ID
+      }
+    }) as TransactionInstruction)
+  }
+  // Swap tax rewards are minted as xUSD
+  public async withdrawSwapTaxRewardsInstruction({
+    exchangeAccount,
+    owner,
+    to
+  }: WithdrawSwapTaxRewardsInstruction) {
+    return await (this.program.instruction.withdrawSwapTaxRewards({
+      accounts: {
+        state: this.stateAddress,
+        exchangeAccount,
+        signer: owner,
+        exchangeAuthority: this.exchangeAuthority,
+        assetsList: this.state.assetsList,
+        usdToken: this.assetsList.synthetics[0].assetAddress,
+        to,
+        tokenProgram: TOKEN_PROGRAM_ID
+      }
+    }) as TransactionInstruction)
+  }
+  // Debt of account without collateral is covered by insurance fund and then by all debtors
+  public async writeOffBadDebtInstruction(exchangeAccount: PublicKey) {
+    return await (this.program.instruction.writeOffBadDebt({
+      accounts: {
+        state: this.stateAddress,
+        exchangeAccount,
+        assetsList: this.state.assetsList
+      }
+    }) as TransactionInstruction)
+  }
+  public async checkAccountInstruction(exchangeAccount: PublicKey, keeper?: KeeperBounty) {
+    // Bounty is paid only when keeper accounts are given
+    const remainingAccounts = keeper
+      ? [
+          { pubkey: this.exchangeAuthority, isWritable: false, isSigner: false },
+          {
+            pubkey: this.assetsList.synthetics[0].assetAddress,
+            isWritable: true,
+            isSigner: false
+          },
+          { pubkey: keeper.keeperUsdAccount, isWritable: true, isSigner: false },
+          { pubkey: TOKEN_PROGRAM_ID, isWritable: false, isSigner: false },
+          { pubkey: keeper.signer, isWritable: false, isSigner: true }
+        ]
+      : []
+
     return await (this.program.instruction.checkAccountCollateralization({
+      remainingAccounts,
       accounts: {
         state: this.stateAddress,
         exchangeAccount: exchangeAccount,
@@ -792,6 +1073,85 @@ export class Exchange {
     }) as TransactionInstruction
     return ix
   }
+  // Zero duration disables auction and keeps fixed penaltyToLiquidator
+  public async setLiquidationAuctionInstruction(
+    liquidationAuctionDuration: number,
+    maxPenaltyToLiquidator: Decimal
+  ) {
+    return await (this.program.instruction.setLiquidationAuction(
+      liquidationAuctionDuration,
+      maxPenaltyToLiquidator,
+      {
+        accounts: {
+          state: this.stateAddress,
+          admin: this.state.admin
+        }
+      }
+    ) as TransactionInstruction)
+  }
+  // Fund account has to be owned by exchange authority, amountPerSlot is in decimals of mint
+  public async addRewardStreamInstruction({
+    mint,
+    fundAccount,
+    amountPerSlot,
+    endSlot
+  }: AddRewardStreamInstruction) {
+    return await (this.program.instruction.addRewardStream(amountPerSlot, endSlot, {
+      accounts: {
+        state: this.stateAddress,
+        admin: this.state.admin,
+        mint,
+        fundAccount
+      }
+    }) as TransactionInstruction)
+  }
+  public async setRewardStreamInstruction({
+    index,
+    amountPerSlot,
+    endSlot
+  }: SetRewardStreamInstruction) {
+    return await (this.program.instruction.setRewardStream(index, amountPerSlot, endSlot, {
+      accounts: {
+        state: this.stateAddress,
+        admin: this.state.admin
+      }
+    }) as TransactionInstruction)
+  }
+  // Zero duration disables vesting of withdrawn SNY rewards
+  public async setRewardsVestingInstruction(vestingDuration: number, earlyExitPenalty: Decimal) {
+    return await (this.program.instruction.setRewardsVesting(vestingDuration, earlyExitPenalty, {
+      accounts: {
+        state: this.stateAddress,
+        admin: this.state.admin
+      }
+    }) as TransactionInstruction)
+  }
+  // Zero health keeps fixed liquidationRate as close factor
+  public async setFullLiquidationHealthInstruction(fullLiquidationHealth: Decimal) {
+    return await (this.program.instruction.setFullLiquidationHealth(fullLiquidationHealth, {
+      accounts: {
+        state: this.stateAddress,
+        admin: this.state.admin
+      }
+    }) as TransactionInstruction)
+  }
+  // Zero bounty disables paying keepers in checkAccountInstruction
+  public async setKeeperBountyInstruction(keeperBounty: Decimal, keeperBountyCooldown: number) {
+    return await (this.program.instruction.setKeeperBounty(keeperBounty, keeperBountyCooldown, {
+      accounts: {
+        state: this.stateAddress,
+        admin: this.state.admin
+      }
+    }) as TransactionInstruction)
+  }
+  public async setInsuranceFundRatioInstruction(insuranceFundRatio: Decimal) {
+    return await (this.program.instruction.setInsuranceFundRatio(insuranceFundRatio, {
+      accounts: {
+        state: this.stateAddress,
+        admin: this.state.admin
+      }
+    }) as TransactionInstruction)
+  }
   public async setSwapTaxRatioInstruction(swapTaxRatio: Decimal) {
     return await (this.program.instruction.setSwapTaxRatio(swapTaxRatio, {
       accounts: {
@@ -800,6 +1160,23 @@ export class Exchange {
       }
     }) as TransactionInstruction)
   }
+  public async setSwapTaxRewardRatioInstruction(swapTaxRewardRatio: Decimal) {
+    return await (this.program.instruction.setSwapTaxRewardRatio(swapTaxRewardRatio, {
+      accounts: {
+        state: this.stateAddress,
+        admin: this.state.admin
+      }
+    }) as TransactionInstruction)
+  }
+  // Zero boost disables extra SNY rewards for SNY collateral
+  public async setMaxStakingBoostInstruction(maxStakingBoost: Decimal) {
+    return await (this.program.instruction.setMaxStakingBoost(maxStakingBoost, {
+      accounts: {
+        state: this.stateAddress,
+        admin: this.state.admin
+      }
+    }) as TransactionInstruction)
+  }
   public async setDebtInterestRateInstruction(debtInterestRate: Decimal) {
     return await (this.program.instruction.setDebtInterestRate(debtInterestRate, {
       accounts: {
@@ -1068,6 +1445,36 @@ export class Exchange {
       }
     )) as TransactionInstruction
   }
+  // Zero scale penalties restore global ones
+  public async setCollateralLiquidationPenaltiesInstruction({
+    collateralAddress,
+    penaltyToExchange,
+    penaltyToLiquidator
+  }: SetCollateralLiquidationPenaltiesInstruction) {
+    return (await this.program.instruction.setCollateralLiquidationPenalties(
+      penaltyToExchange,
+      penaltyToLiquidator,
+      {
+        accounts: {
+          state: this.stateAddress,
+          admin: this.state.admin,
+          assetsList: this.state.assetsList,
+          collateralAddress
+        }
+      }
+    )) as TransactionInstruction
+  }
+  public async migrateAssetsListInstruction() {
+    return (await this.program.instruction.migrateAssetsList({
+      accounts: {
+        state: this.stateAddress,
+        admin: this.state.admin,
+        assetsList: this.state.assetsList,
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId
+      }
+    })) as TransactionInstruction
+  }
 
   public async addSyntheticInstruction({
     assetsList,
@@ -1218,13 +1625,15 @@ export class Exchange {
     liquidationPenaltyExchange,
     liquidationRatio,
     oracleType,
-    vaultType
+    vaultType,
+    kind = VaultKind.Standard
   }: CreateVault) {
     const { vaultAddress, bump } = await this.getVaultAddress(synthetic, collateral, vaultType)
 
     const ix = this.program.instruction.createVault(
       bump,
       vaultType,
+      kind,
       openFee,
       debtInterestRate,
       collateralRatio,
@@ -1355,6 +1764,7 @@ export class Exchange {
     synthetic,
     collateral,
     collateralPriceFeed,
+    basketPriceFeeds = [],
     amount,
     vaultType
   }: BorrowVaultInstruction) {
@@ -1367,6 +1777,7 @@ export class Exchange {
     )
 
     return this.program.instruction.borrowVault(amount, {
+      remainingAccounts: this.basketPriceFeedsAccounts(basketPriceFeeds),
       accounts: {
         synthetic,
         collateral,
@@ -1389,6 +1800,7 @@ export class Exchange {
     collateralReserve,
     liquidationFund,
     collateralPriceFeed,
+    basketPriceFeeds = [],
     amount,
     liquidator,
     liquidatorCollateralAccount,
@@ -1404,6 +1816,7 @@ export class Exchange {
     )
 
     return this.program.instruction.liquidateVault(amount, {
+      remainingAccounts: this.basketPriceFeedsAccounts(basketPriceFeeds),
       accounts: {
         state: this.stateAddress,
         assetsList: this.state.assetsList,
@@ -1423,15 +1836,47 @@ export class Exchange {
       }
     }) as TransactionInstruction
   }
-  public async updateVaultSyntheticPriceIx(
-    synthetic: PublicKey
-  ): Promise<TransactionInstruction | null> {
-    const syntheticStructure = this.assetsList.synthetics.find((s) =>
-      s.assetAddress.equals(synthetic)
-    ) as Synthetic
-    const syntheticFeedAddress = this.assetsList.assets[syntheticStructure.assetIndex].feedAddress
+  // Read only, result is returned as LiquidationPreview in transaction return data
+  public async previewVaultLiquidationInstruction({
+    owner,
+    synthetic,
+    collateral,
+    collateralPriceFeed,
+    basketPriceFeeds = [],
+    amount,
+    vaultType
+  }: PreviewVaultLiquidationInstruction) {
+    const { vaultAddress } = await this.getVaultAddress(synthetic, collateral, vaultType)
+    const { vaultEntryAddress } = await this.getVaultEntryAddress(
+      synthetic,
+      collateral,
+      vaultType,
+      owner
+    )
 
-    if (syntheticFeedAddress.equals(PublicKey.default)) {
+    return this.program.instruction.previewVaultLiquidation(amount, {
+      remainingAccounts: this.basketPriceFeedsAccounts(basketPriceFeeds),
+      accounts: {
+        state: this.stateAddress,
+        vaultEntry: vaultEntryAddress,
+        vault: vaultAddress,
+        synthetic,
+        collateral,
+        collateralPriceFeed,
+        assetsList: this.state.assetsList,
+        owner
+      }
+    }) as TransactionInstruction
+  }
+  public async updateVaultSyntheticPriceIx(
+    synthetic: PublicKey
+  ): Promise<TransactionInstruction | null> {
+    const syntheticStructure = this.assetsList.synthetics.find((s) =>
+      s.assetAddress.equals(synthetic)
+    ) as Synthetic
+    const syntheticFeedAddress = this.assetsList.assets[syntheticStructure.assetIndex].feedAddress
+
+    if (syntheticFeedAddress.equals(PublicKey.default)) {
       return null
     }
     const updateSyntheticPriceIx = await this.updateSelectedPricesInstruction(
@@ -1462,6 +1907,7 @@ export class Exchange {
     collateral,
     reserveAddress,
     collateralPriceFeed,
+    basketPriceFeeds = [],
     synthetic,
     userCollateralAccount,
     vaultType
@@ -1475,6 +1921,7 @@ export class Exchange {
     )
 
     const ix = this.program.instruction.withdrawVault(amount, {
+      remainingAccounts: this.basketPriceFeedsAccounts(basketPriceFeeds),
       accounts: {
         userCollateralAccount,
         owner,
@@ -1504,6 +1951,287 @@ export class Exchange {
 
     return tx
   }
+  public async depositVaultCollateralInstruction({
+    owner,
+    synthetic,
+    collateral,
+    vaultType,
+    index,
+    userCollateralAccount,
+    reserveAddress,
+    amount
+  }: DepositVaultCollateralInstruction) {
+    const { vaultAddress } = await this.getVaultAddress(synthetic, collateral, vaultType)
+    const { vaultEntryAddress } = await this.getVaultEntryAddress(
+      synthetic,
+      collateral,
+      vaultType,
+      owner
+    )
+
+    return this.program.instruction.depositVaultCollateral(index, amount, {
+      accounts: {
+        state: this.stateAddress,
+        vaultEntry: vaultEntryAddress,
+        vault: vaultAddress,
+        reserveAddress,
+        userCollateralAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        owner,
+        exchangeAuthority: this.exchangeAuthority
+      }
+    }) as TransactionInstruction
+  }
+  public async withdrawVaultCollateralInstruction({
+    owner,
+    synthetic,
+    collateral,
+    vaultType,
+    index,
+    userCollateralAccount,
+    reserveAddress,
+    collateralPriceFeed,
+    basketPriceFeeds,
+    amount
+  }: WithdrawVaultCollateralInstruction) {
+    const { vaultAddress } = await this.getVaultAddress(synthetic, collateral, vaultType)
+    const { vaultEntryAddress } = await this.getVaultEntryAddress(
+      synthetic,
+      collateral,
+      vaultType,
+      owner
+    )
+
+    return this.program.instruction.withdrawVaultCollateral(index, amount, {
+      remainingAccounts: this.basketPriceFeedsAccounts(basketPriceFeeds),
+      accounts: {
+        state: this.stateAddress,
+        vaultEntry: vaultEntryAddress,
+        vault: vaultAddress,
+        synthetic,
+        collateralPriceFeed,
+        reserveAddress,
+        userCollateralAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        assetsList: this.state.assetsList,
+        owner,
+        exchangeAuthority: this.exchangeAuthority
+      }
+    }) as TransactionInstruction
+  }
+  public async liquidateVaultCollateralInstruction({
+    owner,
+    synthetic,
+    collateral,
+    vaultType,
+    index,
+    collateralReserve,
+    liquidationFund,
+    collateralPriceFeed,
+    basketPriceFeeds,
+    amount,
+    liquidator,
+    liquidatorCollateralAccount,
+    liquidatorSyntheticAccount
+  }: LiquidateVaultCollateralInstruction) {
+    const { vaultAddress } = await this.getVaultAddress(synthetic, collateral, vaultType)
+    const { vaultEntryAddress } = await this.getVaultEntryAddress(
+      synthetic,
+      collateral,
+      vaultType,
+      owner
+    )
+
+    return this.program.instruction.liquidateVaultCollateral(index, amount, {
+      remainingAccounts: this.basketPriceFeedsAccounts(basketPriceFeeds),
+      accounts: {
+        state: this.stateAddress,
+        vaultEntry: vaultEntryAddress,
+        vault: vaultAddress,
+        synthetic,
+        collateralPriceFeed,
+        assetsList: this.state.assetsList,
+        collateralReserve,
+        liquidatorSyntheticAccount,
+        liquidatorCollateralAccount,
+        liquidationFund,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        owner,
+        liquidator,
+        exchangeAuthority: this.exchangeAuthority
+      }
+    }) as TransactionInstruction
+  }
+  // Borrowed synthetic is swapped to collateral through swapline up to targetRatio of debt
+  public async leverageVaultInstruction({
+    owner,
+    synthetic,
+    collateral,
+    collateralPriceFeed,
+    vaultCollateralReserve,
+    swaplineCollateralReserve,
+    targetRatio,
+    vaultType
+  }: LeverageVaultInstruction) {
+    const { vaultAddress } = await this.getVaultAddress(synthetic, collateral, vaultType)
+    const { vaultEntryAddress } = await this.getVaultEntryAddress(
+      synthetic,
+      collateral,
+      vaultType,
+      owner
+    )
+    const { swaplineAddress } = await this.getSwaplineAddress(synthetic, collateral)
+
+    return this.program.instruction.leverageVault(targetRatio, {
+      accounts: {
+        state: this.stateAddress,
+        vaultEntry: vaultEntryAddress,
+        vault: vaultAddress,
+        swapline: swaplineAddress,
+        synthetic,
+        collateral,
+        collateralPriceFeed,
+        assetsList: this.state.assetsList,
+        vaultCollateralReserve,
+        swaplineCollateralReserve,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        owner,
+        exchangeAuthority: this.exchangeAuthority
+      }
+    }) as TransactionInstruction
+  }
+  // Zero threshold disables deleverage of entry by keepers
+  public async setVaultEntryDeleverageInstruction({
+    owner,
+    synthetic,
+    collateral,
+    threshold,
+    target,
+    vaultType
+  }: SetVaultEntryDeleverageInstruction) {
+    const { vaultAddress } = await this.getVaultAddress(synthetic, collateral, vaultType)
+    const { vaultEntryAddress } = await this.getVaultEntryAddress(
+      synthetic,
+      collateral,
+      vaultType,
+      owner
+    )
+
+    return this.program.instruction.setVaultEntryDeleverage(threshold, target, {
+      accounts: {
+        state: this.stateAddress,
+        vaultEntry: vaultEntryAddress,
+        vault: vaultAddress,
+        owner
+      }
+    }) as TransactionInstruction
+  }
+  // Keeper sells collateral of entry above threshold, receives deleverageFee of sold collateral
+  public async deleverageVaultInstruction({
+    owner,
+    synthetic,
+    collateral,
+    collateralPriceFeed,
+    vaultCollateralReserve,
+    swaplineCollateralReserve,
+    keeperCollateralAccount,
+    keeper,
+    vaultType
+  }: DeleverageVaultInstruction) {
+    const { vaultAddress } = await this.getVaultAddress(synthetic, collateral, vaultType)
+    const { vaultEntryAddress } = await this.getVaultEntryAddress(
+      synthetic,
+      collateral,
+      vaultType,
+      owner
+    )
+    const { swaplineAddress } = await this.getSwaplineAddress(synthetic, collateral)
+
+    return this.program.instruction.deleverageVault({
+      accounts: {
+        state: this.stateAddress,
+        vaultEntry: vaultEntryAddress,
+        vault: vaultAddress,
+        swapline: swaplineAddress,
+        synthetic,
+        collateral,
+        collateralPriceFeed,
+        assetsList: this.state.assetsList,
+        vaultCollateralReserve,
+        swaplineCollateralReserve,
+        keeperCollateralAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        owner,
+        keeper,
+        exchangeAuthority: this.exchangeAuthority
+      }
+    }) as TransactionInstruction
+  }
+  // Recipient signs to accept debt, their entry is created if missing
+  public async transferVaultEntryInstruction({
+    synthetic,
+    collateral,
+    owner,
+    recipient,
+    vaultType
+  }: TransferVaultEntryInstruction) {
+    const { vaultAddress } = await this.getVaultAddress(synthetic, collateral, vaultType)
+    const { vaultEntryAddress } = await this.getVaultEntryAddress(
+      synthetic,
+      collateral,
+      vaultType,
+      owner
+    )
+    const { vaultEntryAddress: recipientVaultEntryAddress, bump } = await this.getVaultEntryAddress(
+      synthetic,
+      collateral,
+      vaultType,
+      recipient
+    )
+
+    return this.program.instruction.transferVaultEntry(bump, {
+      accounts: {
+        state: this.stateAddress,
+        vaultEntry: vaultEntryAddress,
+        recipientVaultEntry: recipientVaultEntryAddress,
+        vault: vaultAddress,
+        synthetic,
+        collateral,
+        assetsList: this.state.assetsList,
+        owner,
+        recipient,
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId
+      }
+    }) as TransactionInstruction
+  }
+  // Entry can be closed only without debt and collateral, rent is returned to owner
+  public async closeVaultEntryInstruction({
+    synthetic,
+    collateral,
+    owner,
+    vaultType
+  }: VaultEntryId) {
+    const { vaultAddress } = await this.getVaultAddress(synthetic, collateral, vaultType)
+    const { vaultEntryAddress } = await this.getVaultEntryAddress(
+      synthetic,
+      collateral,
+      vaultType,
+      owner
+    )
+
+    return this.program.instruction.closeVaultEntry({
+      accounts: {
+        state: this.stateAddress,
+        vaultEntry: vaultEntryAddress,
+        vault: vaultAddress,
+        synthetic,
+        collateral,
+        assetsList: this.state.assetsList,
+        owner
+      }
+    }) as TransactionInstruction
+  }
   public async repayVaultInstruction({
     amount,
     owner,
@@ -1557,6 +2285,72 @@ export class Exchange {
 
     await signAndSend(tx, repayVault.signers, this.connection)
   }
+  public async migrateVaultInstruction({ synthetic, collateral, vaultType }: SetVaultParameter) {
+    const { vaultAddress } = await this.getVaultAddress(synthetic, collateral, vaultType)
+
+    return this.program.instruction.migrateVault({
+      accounts: {
+        state: this.stateAddress,
+        admin: this.state.admin,
+        vault: vaultAddress,
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId
+      }
+    }) as TransactionInstruction
+  }
+  public async migrateVaultEntryInstruction({
+    synthetic,
+    collateral,
+    owner,
+    vaultType
+  }: VaultEntryId) {
+    const { vaultEntryAddress } = await this.getVaultEntryAddress(
+      synthetic,
+      collateral,
+      vaultType,
+      owner
+    )
+
+    return this.program.instruction.migrateVaultEntry({
+      accounts: {
+        vaultEntry: vaultEntryAddress,
+        payer: this.wallet.publicKey,
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId
+      }
+    }) as TransactionInstruction
+  }
+  public async addVaultCollateralInstruction({
+    synthetic,
+    collateral,
+    vaultType,
+    basketCollateral,
+    collateralReserve,
+    liquidationFund,
+    collateralPriceFeed,
+    collateralRatio,
+    liquidationThreshold,
+    oracleType
+  }: AddVaultCollateralInstruction) {
+    const { vaultAddress } = await this.getVaultAddress(synthetic, collateral, vaultType)
+
+    return this.program.instruction.addVaultCollateral(
+      collateralRatio,
+      liquidationThreshold,
+      oracleType,
+      {
+        accounts: {
+          state: this.stateAddress,
+          admin: this.state.admin,
+          vault: vaultAddress,
+          collateral: basketCollateral,
+          collateralReserve,
+          liquidationFund,
+          collateralPriceFeed
+        }
+      }
+    ) as TransactionInstruction
+  }
   public async setVaultHaltedInstruction({
     halted,
     collateral,
@@ -1662,6 +2456,45 @@ export class Exchange {
       }
     }) as TransactionInstruction
   }
+  // Debt interest rate grows with utilization of maxBorrow, debtInterestRate is rate at zero
+  public async setVaultInterestRateCurveInstruction(
+    { enabled, optimalUtilization, interestRateAtOptimal, maxInterestRate }: InterestRateCurve,
+    { synthetic, collateral, vaultType }: SetVaultParameter
+  ) {
+    const { vaultAddress } = await this.getVaultAddress(synthetic, collateral, vaultType)
+
+    return this.program.instruction.setVaultInterestRateCurve(
+      enabled,
+      optimalUtilization,
+      interestRateAtOptimal,
+      maxInterestRate,
+      {
+        accounts: {
+          synthetic,
+          collateral,
+          state: this.stateAddress,
+          admin: this.state.admin,
+          vault: vaultAddress
+        }
+      }
+    ) as TransactionInstruction
+  }
+  public async setVaultDeleverageFeeInstruction(
+    deleverageFee: Decimal,
+    { synthetic, collateral, vaultType }: SetVaultParameter
+  ) {
+    const { vaultAddress } = await this.getVaultAddress(synthetic, collateral, vaultType)
+
+    return this.program.instruction.setVaultDeleverageFee(deleverageFee, {
+      accounts: {
+        synthetic,
+        collateral,
+        state: this.stateAddress,
+        admin: this.state.admin,
+        vault: vaultAddress
+      }
+    }) as TransactionInstruction
+  }
   public async setVaultMaxBorrowInstruction(
     maxBorrow: Decimal,
     { synthetic, collateral, vaultType }: SetVaultParameter
@@ -1837,6 +2670,8 @@ export interface Collateral {
   reserveBalance: Decimal
   collateralRatio: Decimal
   maxCollateral: Decimal
+  penaltyToLiquidator: Decimal
+  penaltyToExchange: Decimal
 }
 export interface Synthetic {
   assetIndex: number
@@ -1881,6 +2716,10 @@ export interface SetLiquidationPenaltiesInstruction {
   penaltyToExchange: Decimal
   penaltyToLiquidator: Decimal
 }
+export interface SetCollateralLiquidationPenaltiesInstruction
+  extends SetLiquidationPenaltiesInstruction {
+  collateralAddress: PublicKey
+}
 
 export interface AddSyntheticInstruction {
   assetAddress: PublicKey
@@ -1960,7 +2799,7 @@ export interface WithdrawRewards {
 }
 export interface MintInstruction {
   exchangeAccount: PublicKey
-  owner: PublicKey
+  owner: PublicKey // owner or delegate with permission
   to: PublicKey
   amount: BN
 }
@@ -1982,29 +2821,92 @@ export interface LiquidateInstruction {
   signer: PublicKey
   amount: BN
 }
+export interface RepayFlashLiquidationInstruction {
+  liquidatorUsdAccount: PublicKey
+  signer: PublicKey
+}
+export interface LiquidateMultiInstruction {
+  exchangeAccount: PublicKey
+  liquidatorUsdAccount: PublicKey
+  collaterals: Array<LiquidatedCollateral>
+  signer: PublicKey
+  amount: BN
+}
+export interface LiquidatedCollateral {
+  reserveAccount: PublicKey
+  liquidationFund: PublicKey
+  liquidatorCollateralAccount: PublicKey
+}
+export interface PreviewLiquidationInstruction {
+  exchangeAccount: PublicKey
+  collateralAddress: PublicKey
+  amount: BN
+}
+export interface LiquidationPreview {
+  liquidatable: boolean
+  maxRepay: BN
+  repayAmount: BN
+  seizedCollateral: BN
+  collateralToLiquidator: BN
+  collateralToExchange: BN
+  debtAfter: BN
+  maxDebtAfter: BN
+}
+
+export interface KeeperBounty {
+  keeperUsdAccount: PublicKey
+  signer: PublicKey
+}
 
 export interface BurnInstruction {
   exchangeAccount: PublicKey
-  owner: PublicKey
+  owner: PublicKey // owner or delegate with permission
   userTokenAccountBurn: PublicKey
   amount: BN
 }
 export interface WithdrawRewardsInstruction {
   exchangeAccount: PublicKey
-  owner: PublicKey
+  owner: PublicKey // owner or delegate with permission
+  userTokenAccount: PublicKey
+  rewardStreams?: Array<WithdrawRewardStream>
+}
+export interface CompoundRewardsInstruction {
+  exchangeAccount: PublicKey
+  owner: PublicKey // owner or delegate with permission
+}
+export interface WithdrawSwapTaxRewardsInstruction {
+  exchangeAccount: PublicKey
+  owner: PublicKey // owner or delegate with permission
+  to: PublicKey
+}
+export interface WithdrawRewardStream {
+  fundAccount: PublicKey
   userTokenAccount: PublicKey
 }
 export interface WithdrawInstruction {
   exchangeAccount: PublicKey
   reserveAccount: PublicKey
-  owner: PublicKey
+  owner: PublicKey // owner or delegate with permission
   userCollateralAccount: PublicKey
   amount: BN
 }
+export interface TransferCollateralInstruction {
+  exchangeAccount: PublicKey
+  toExchangeAccount: PublicKey // account of the same owner
+  owner: PublicKey
+  collateralAddress: PublicKey
+  amount: BN
+}
+export interface TransferPositionInstruction {
+  exchangeAccount: PublicKey
+  toExchangeAccount: PublicKey
+  owner: PublicKey
+  toOwner: PublicKey
+}
 export interface DepositInstruction {
   exchangeAccount: PublicKey
   userCollateralAccount: PublicKey
-  owner: PublicKey
+  owner: PublicKey // owner or delegate with permission
   reserveAddress: PublicKey
   amount: BN
 }
@@ -2030,6 +2932,7 @@ export interface Init {
   exchangeAuthority: PublicKey
 }
 export interface ExchangeState {
+  version: number
   admin: PublicKey
   exchangeAuthority: PublicKey
   halted: boolean
@@ -2049,6 +2952,47 @@ export interface ExchangeState {
   penaltyToExchange: Decimal
   liquidationBuffer: number
   staking: Staking
+  liquidationAuctionDuration: number
+  maxPenaltyToLiquidator: Decimal
+  insuranceFund: Decimal
+  insuranceFundRatio: Decimal
+  keeperBounty: Decimal
+  keeperBountyCooldown: number
+  flashLiquidationDebt: BN
+  fullLiquidationHealth: Decimal
+  rewardPerShare: Decimal
+  lastRewardUpdate: BN
+  headRewardStreams: number
+  rewardStreams: Array<RewardStream>
+  rewardsVestingDuration: number
+  earlyExitPenalty: Decimal
+  swapTaxRewardRatio: Decimal
+  swapTaxRewardPerShare: Decimal
+  maxStakingBoost: Decimal
+  boostedDebtShares: BN
+}
+export interface RewardStream {
+  mint: PublicKey
+  fundAccount: PublicKey
+  amountPerSlot: Decimal
+  endSlot: BN
+  rewardPerShare: Decimal
+  lastUpdate: BN
+}
+export interface UserRewardStream {
+  amountToClaim: Decimal
+  lastRewardPerShare: Decimal
+}
+export interface AddRewardStreamInstruction {
+  mint: PublicKey
+  fundAccount: PublicKey
+  amountPerSlot: BN
+  endSlot: BN
+}
+export interface SetRewardStreamInstruction {
+  index: number
+  amountPerSlot: Decimal
+  endSlot: BN
 }
 export interface Staking {
   fundAccount: PublicKey
@@ -2066,6 +3010,18 @@ export interface StakingRound {
 export interface ExchangeAccount {
   owner: PublicKey
   version: number
+  delegate: PublicKey
+  delegatePermissions: number
+  subAccountIndex: number
+  lastKeeperBounty: BN
+  lastRewardPerShare: Decimal
+  rewardStreams: Array<UserRewardStream>
+  vestingAmount: BN
+  vestingStart: BN
+  vestingEnd: BN
+  swapTaxRewards: UserRewardStream
+  stakingBoost: Decimal
+  boostedDebtShares: BN
   debtShares: BN
   liquidationDeadline: BN
   userStakingData: UserStaking
@@ -2103,6 +3059,31 @@ export interface Vault {
   lastUpdate: BN
   oracleType: OracleType
   vaultType: number
+  headCollaterals: number
+  collaterals: Array<VaultCollateral>
+  interestCurveEnabled: boolean
+  optimalUtilization: Decimal
+  interestRateAtOptimal: Decimal
+  maxInterestRate: Decimal
+  currentInterestRate: Decimal
+  deleverageFee: Decimal
+  kind: VaultKind
+}
+export interface InterestRateCurve {
+  enabled: boolean
+  optimalUtilization: Decimal
+  interestRateAtOptimal: Decimal
+  maxInterestRate: Decimal
+}
+export interface VaultCollateral {
+  collateral: PublicKey
+  collateralPriceFeed: PublicKey
+  oracleType: OracleType
+  collateralRatio: Decimal
+  liquidationThreshold: Decimal
+  collateralReserve: PublicKey
+  liquidationFund: PublicKey
+  collateralAmount: Decimal
 }
 export interface VaultEntry {
   owner: PublicKey
@@ -2110,6 +3091,9 @@ export interface VaultEntry {
   lastAccumulatedInterestRate: Decimal
   syntheticAmount: Decimal
   collateralAmount: Decimal
+  collateralAmounts: Array<Decimal> // indexed like collaterals of vault
+  deleverageThreshold: Decimal
+  deleverageTarget: Decimal
 }
 export interface VaultDepositInstruction {
   owner: PublicKey
@@ -2136,6 +3120,16 @@ export interface LiquidateVaultInstruction {
   collateralReserve: PublicKey
   liquidationFund: PublicKey
   collateralPriceFeed: PublicKey
+  basketPriceFeeds?: Array<PublicKey> // price feeds of vault basket collaterals in order
+  amount: BN
+  vaultType: number
+}
+export interface PreviewVaultLiquidationInstruction {
+  owner: PublicKey
+  synthetic: PublicKey
+  collateral: PublicKey
+  collateralPriceFeed: PublicKey
+  basketPriceFeeds?: Array<PublicKey> // price feeds of vault basket collaterals in order
   amount: BN
   vaultType: number
 }
@@ -2145,6 +3139,7 @@ export interface BorrowVaultInstruction {
   synthetic: PublicKey
   collateral: PublicKey
   collateralPriceFeed: PublicKey
+  basketPriceFeeds?: Array<PublicKey> // price feeds of vault basket collaterals in order
   amount: BN
   vaultType: number
 }
@@ -2158,10 +3153,81 @@ export interface WithdrawVaultInstruction {
   collateral: PublicKey
   reserveAddress: PublicKey
   collateralPriceFeed: PublicKey
+  basketPriceFeeds?: Array<PublicKey> // price feeds of vault basket collaterals in order
   synthetic: PublicKey
   userCollateralAccount: PublicKey
   vaultType: number
 }
+export interface LeverageVaultInstruction {
+  owner: PublicKey
+  synthetic: PublicKey
+  collateral: PublicKey
+  collateralPriceFeed: PublicKey
+  vaultCollateralReserve: PublicKey
+  swaplineCollateralReserve: PublicKey
+  targetRatio: Decimal
+  vaultType: number
+}
+export interface SetVaultEntryDeleverageInstruction {
+  owner: PublicKey
+  synthetic: PublicKey
+  collateral: PublicKey
+  threshold: Decimal
+  target: Decimal
+  vaultType: number
+}
+export interface DeleverageVaultInstruction {
+  owner: PublicKey
+  synthetic: PublicKey
+  collateral: PublicKey
+  collateralPriceFeed: PublicKey
+  vaultCollateralReserve: PublicKey
+  swaplineCollateralReserve: PublicKey
+  keeperCollateralAccount: PublicKey
+  keeper: PublicKey
+  vaultType: number
+}
+export interface AddVaultCollateralInstruction {
+  synthetic: PublicKey
+  collateral: PublicKey
+  vaultType: number
+  basketCollateral: PublicKey
+  collateralReserve: PublicKey
+  liquidationFund: PublicKey
+  collateralPriceFeed: PublicKey
+  collateralRatio: Decimal
+  liquidationThreshold: Decimal
+  oracleType: OracleType
+}
+export interface DepositVaultCollateralInstruction {
+  owner: PublicKey
+  synthetic: PublicKey
+  collateral: PublicKey
+  vaultType: number
+  index: number // index of basket collateral in vault
+  userCollateralAccount: PublicKey
+  reserveAddress: PublicKey
+  amount: BN
+}
+export interface WithdrawVaultCollateralInstruction extends DepositVaultCollateralInstruction {
+  collateralPriceFeed: PublicKey
+  basketPriceFeeds: Array<PublicKey>
+}
+export interface LiquidateVaultCollateralInstruction {
+  owner: PublicKey
+  liquidator: PublicKey
+  liquidatorSyntheticAccount: PublicKey
+  liquidatorCollateralAccount: PublicKey
+  synthetic: PublicKey
+  collateral: PublicKey
+  vaultType: number
+  index: number // index of basket collateral in vault
+  collateralReserve: PublicKey
+  liquidationFund: PublicKey
+  collateralPriceFeed: PublicKey
+  basketPriceFeeds: Array<PublicKey>
+  amount: BN
+}
 
 export interface RepayVaultInstruction {
   amount: BN
@@ -2174,6 +3240,12 @@ export interface RepayVaultInstruction {
 export interface RepayVault extends RepayVaultInstruction {
   signers: Array<Account | Keypair>
 }
+export interface SetDelegate {
+  exchangeAccount: PublicKey
+  owner: PublicKey
+  delegate: PublicKey
+  permissions: number
+}
 export interface CollateralEntry {
   amount: BN
   collateralAddress: PublicKey
@@ -2202,6 +3274,7 @@ export interface CreateVault {
   liquidationRatio: Decimal
   oracleType: OracleType
   vaultType: number
+  kind?: VaultKind
 }
 
 export interface CreateVaultEntry {
@@ -2265,6 +3338,9 @@ export interface VaultEntryId {
   owner: PublicKey
   vaultType: number
 }
+export interface TransferVaultEntryInstruction extends VaultEntryId {
+  recipient: PublicKey
+}
 
 export interface WithdrawVaultAccumulatedInterest {
   synthetic: PublicKey
@@ -2290,3 +3366,8 @@ export enum OracleType {
   Pyth = 0,
   Chainlink = 1
 }
+export enum VaultKind {
+  Standard = 0,
+  Stablecoin = 1,
+  InterestFree = 2
+}
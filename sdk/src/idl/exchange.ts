@@ -38,6 +38,207 @@ export type Exchange = {
         }
       ]
     },
+    {
+      "name": "createSubAccount",
+      "accounts": [
+        {
+          "name": "exchangeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "bump",
+          "type": "u8"
+        },
+        {
+          "name": "index",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "migrateExchangeAccount",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "exchangeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setDelegate",
+      "accounts": [
+        {
+          "name": "exchangeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "delegate",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "permissions",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "migrateState",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateAssetsList",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateVault",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateVaultEntry",
+      "accounts": [
+        {
+          "name": "vaultEntry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
     {
       "name": "createList",
       "accounts": [
@@ -218,7 +419,7 @@ export type Exchange = {
           "isSigner": false
         },
         {
-          "name": "owner",
+          "name": "signer",
           "isMut": false,
           "isSigner": true
         },
@@ -274,7 +475,7 @@ export type Exchange = {
           "isSigner": false
         },
         {
-          "name": "owner",
+          "name": "signer",
           "isMut": false,
           "isSigner": true
         }
@@ -325,7 +526,7 @@ export type Exchange = {
           "isSigner": false
         },
         {
-          "name": "owner",
+          "name": "signer",
           "isMut": false,
           "isSigner": true
         }
@@ -338,45 +539,25 @@ export type Exchange = {
       ]
     },
     {
-      "name": "swap",
+      "name": "transferCollateral",
       "accounts": [
         {
           "name": "state",
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "exchangeAuthority",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "assetsList",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenIn",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "tokenFor",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "userTokenAccountIn",
+          "name": "exchangeAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "userTokenAccountFor",
+          "name": "toExchangeAccount",
           "isMut": true,
           "isSigner": false
         },
@@ -387,6 +568,10 @@ export type Exchange = {
         }
       ],
       "args": [
+        {
+          "name": "collateralAddress",
+          "type": "publicKey"
+        },
         {
           "name": "amount",
           "type": "u64"
@@ -394,58 +579,43 @@ export type Exchange = {
       ]
     },
     {
-      "name": "burn",
+      "name": "transferPosition",
       "accounts": [
         {
           "name": "state",
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "exchangeAuthority",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "assetsList",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "usdToken",
+          "name": "exchangeAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "userTokenAccountBurn",
+          "name": "toExchangeAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "exchangeAccount",
-          "isMut": true,
-          "isSigner": false
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
         },
         {
-          "name": "owner",
+          "name": "toOwner",
           "isMut": false,
           "isSigner": true
         }
       ],
-      "args": [
-        {
-          "name": "amount",
-          "type": "u64"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "liquidate",
+      "name": "swap",
       "accounts": [
         {
           "name": "state",
@@ -468,39 +638,29 @@ export type Exchange = {
           "isSigner": false
         },
         {
-          "name": "usdToken",
+          "name": "tokenIn",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "liquidatorUsdAccount",
+          "name": "tokenFor",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "liquidatorCollateralAccount",
+          "name": "userTokenAccountIn",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "exchangeAccount",
+          "name": "userTokenAccountFor",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "signer",
+          "name": "owner",
           "isMut": false,
           "isSigner": true
-        },
-        {
-          "name": "liquidationFund",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "reserveAccount",
-          "isMut": true,
-          "isSigner": false
         }
       ],
       "args": [
@@ -511,7 +671,7 @@ export type Exchange = {
       ]
     },
     {
-      "name": "checkAccountCollateralization",
+      "name": "burn",
       "accounts": [
         {
           "name": "state",
@@ -519,23 +679,27 @@ export type Exchange = {
           "isSigner": false
         },
         {
-          "name": "exchangeAccount",
-          "isMut": true,
+          "name": "exchangeAuthority",
+          "isMut": false,
           "isSigner": false
         },
         {
           "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
-        }
-      ],
-      "args": []
-    },
-    {
-      "name": "claimRewards",
-      "accounts": [
+        },
         {
-          "name": "state",
+          "name": "usdToken",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userTokenAccountBurn",
           "isMut": true,
           "isSigner": false
         },
@@ -543,12 +707,22 @@ export type Exchange = {
           "name": "exchangeAccount",
           "isMut": true,
           "isSigner": false
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "name": "withdrawRewards",
+      "name": "liquidate",
       "accounts": [
         {
           "name": "state",
@@ -556,18 +730,13 @@ export type Exchange = {
           "isSigner": false
         },
         {
-          "name": "exchangeAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "owner",
+          "name": "exchangeAuthority",
           "isMut": false,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "exchangeAuthority",
-          "isMut": false,
+          "name": "assetsList",
+          "isMut": true,
           "isSigner": false
         },
         {
@@ -576,45 +745,29 @@ export type Exchange = {
           "isSigner": false
         },
         {
-          "name": "userTokenAccount",
+          "name": "usdToken",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "stakingFundAccount",
+          "name": "liquidatorUsdAccount",
           "isMut": true,
           "isSigner": false
-        }
-      ],
-      "args": []
-    },
-    {
-      "name": "withdrawLiquidationPenalty",
-      "accounts": [
+        },
         {
-          "name": "state",
+          "name": "liquidatorCollateralAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "admin",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "exchangeAuthority",
-          "isMut": false,
+          "name": "exchangeAccount",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "signer",
           "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "to",
-          "isMut": true,
-          "isSigner": false
+          "isSigner": true
         },
         {
           "name": "liquidationFund",
@@ -622,7 +775,7 @@ export type Exchange = {
           "isSigner": false
         },
         {
-          "name": "assetsList",
+          "name": "reserveAccount",
           "isMut": true,
           "isSigner": false
         }
@@ -630,40 +783,88 @@ export type Exchange = {
       "args": [
         {
           "name": "amount",
-          "type": {
-            "defined": "Decimal"
-          }
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "addNewAsset",
+      "name": "flashLiquidate",
       "accounts": [
         {
-          "name": "state",
-          "isMut": true,
-          "isSigner": false
+          "name": "liquidate",
+          "accounts": [
+            {
+              "name": "state",
+              "isMut": true,
+              "isSigner": false
+            },
+            {
+              "name": "exchangeAuthority",
+              "isMut": false,
+              "isSigner": false
+            },
+            {
+              "name": "assetsList",
+              "isMut": true,
+              "isSigner": false
+            },
+            {
+              "name": "tokenProgram",
+              "isMut": false,
+              "isSigner": false
+            },
+            {
+              "name": "usdToken",
+              "isMut": true,
+              "isSigner": false
+            },
+            {
+              "name": "liquidatorUsdAccount",
+              "isMut": true,
+              "isSigner": false
+            },
+            {
+              "name": "liquidatorCollateralAccount",
+              "isMut": true,
+              "isSigner": false
+            },
+            {
+              "name": "exchangeAccount",
+              "isMut": true,
+              "isSigner": false
+            },
+            {
+              "name": "signer",
+              "isMut": false,
+              "isSigner": true
+            },
+            {
+              "name": "liquidationFund",
+              "isMut": true,
+              "isSigner": false
+            },
+            {
+              "name": "reserveAccount",
+              "isMut": true,
+              "isSigner": false
+            }
+          ]
         },
         {
-          "name": "signer",
+          "name": "instructions",
           "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "assetsList",
-          "isMut": true,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "newAssetFeedAddress",
-          "type": "publicKey"
+          "name": "amount",
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "withdrawSwapTax",
+      "name": "repayFlashLiquidation",
       "accounts": [
         {
           "name": "state",
@@ -671,17 +872,17 @@ export type Exchange = {
           "isSigner": false
         },
         {
-          "name": "admin",
+          "name": "exchangeAuthority",
           "isMut": false,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "exchangeAuthority",
+          "name": "assetsList",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "assetsList",
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         },
@@ -691,36 +892,26 @@ export type Exchange = {
           "isSigner": false
         },
         {
-          "name": "to",
+          "name": "liquidatorUsdAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "signer",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         }
       ],
-      "args": [
-        {
-          "name": "amount",
-          "type": "u64"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "withdrawAccumulatedDebtInterest",
+      "name": "liquidateMulti",
       "accounts": [
         {
           "name": "state",
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "admin",
-          "isMut": false,
-          "isSigner": true
-        },
         {
           "name": "exchangeAuthority",
           "isMut": false,
@@ -731,20 +922,30 @@ export type Exchange = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "usdToken",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "to",
+          "name": "liquidatorUsdAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
-          "isMut": false,
+          "name": "exchangeAccount",
+          "isMut": true,
           "isSigner": false
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true
         }
       ],
       "args": [
@@ -755,30 +956,37 @@ export type Exchange = {
       ]
     },
     {
-      "name": "setSwapTaxRatio",
+      "name": "previewLiquidation",
       "accounts": [
         {
           "name": "state",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "admin",
+          "name": "assetsList",
           "isMut": false,
-          "isSigner": true
+          "isSigner": false
+        },
+        {
+          "name": "exchangeAccount",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "swapTaxRatio",
-          "type": {
-            "defined": "Decimal"
-          }
+          "name": "collateralAddress",
+          "type": "publicKey"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "setDebtInterestRate",
+      "name": "checkAccountCollateralization",
       "accounts": [
         {
           "name": "state",
@@ -786,22 +994,20 @@ export type Exchange = {
           "isSigner": false
         },
         {
-          "name": "admin",
-          "isMut": false,
-          "isSigner": true
-        }
-      ],
-      "args": [
+          "name": "exchangeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
         {
-          "name": "debtInterestRate",
-          "type": {
-            "defined": "Decimal"
-          }
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
         }
-      ]
+      ],
+      "args": []
     },
     {
-      "name": "setLiquidationBuffer",
+      "name": "writeOffBadDebt",
       "accounts": [
         {
           "name": "state",
@@ -809,20 +1015,20 @@ export type Exchange = {
           "isSigner": false
         },
         {
-          "name": "admin",
-          "isMut": false,
-          "isSigner": true
-        }
-      ],
-      "args": [
+          "name": "exchangeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
         {
-          "name": "liquidationBuffer",
-          "type": "u32"
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
         }
-      ]
+      ],
+      "args": []
     },
     {
-      "name": "setLiquidationRate",
+      "name": "claimRewards",
       "accounts": [
         {
           "name": "state",
@@ -830,22 +1036,15 @@ export type Exchange = {
           "isSigner": false
         },
         {
-          "name": "admin",
-          "isMut": false,
-          "isSigner": true
+          "name": "exchangeAccount",
+          "isMut": true,
+          "isSigner": false
         }
       ],
-      "args": [
-        {
-          "name": "liquidationRate",
-          "type": {
-            "defined": "Decimal"
-          }
-        }
-      ]
+      "args": []
     },
     {
-      "name": "setFee",
+      "name": "withdrawRewards",
       "accounts": [
         {
           "name": "state",
@@ -853,87 +1052,40 @@ export type Exchange = {
           "isSigner": false
         },
         {
-          "name": "admin",
-          "isMut": false,
-          "isSigner": true
-        }
-      ],
-      "args": [
-        {
-          "name": "fee",
-          "type": {
-            "defined": "Decimal"
-          }
-        }
-      ]
-    },
-    {
-      "name": "setMaxDelay",
-      "accounts": [
-        {
-          "name": "state",
+          "name": "exchangeAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "admin",
+          "name": "signer",
           "isMut": false,
           "isSigner": true
-        }
-      ],
-      "args": [
-        {
-          "name": "maxDelay",
-          "type": "u32"
-        }
-      ]
-    },
-    {
-      "name": "setHalted",
-      "accounts": [
+        },
         {
-          "name": "state",
-          "isMut": true,
+          "name": "exchangeAuthority",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "admin",
+          "name": "tokenProgram",
           "isMut": false,
-          "isSigner": true
-        }
-      ],
-      "args": [
-        {
-          "name": "halted",
-          "type": "bool"
-        }
-      ]
-    },
-    {
-      "name": "setHealthFactor",
-      "accounts": [
+          "isSigner": false
+        },
         {
-          "name": "state",
+          "name": "userTokenAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "admin",
-          "isMut": false,
-          "isSigner": true
+          "name": "stakingFundAccount",
+          "isMut": true,
+          "isSigner": false
         }
       ],
-      "args": [
-        {
-          "name": "factor",
-          "type": {
-            "defined": "Decimal"
-          }
-        }
-      ]
+      "args": []
     },
     {
-      "name": "setStakingAmountPerRound",
+      "name": "withdrawRewardsEarly",
       "accounts": [
         {
           "name": "state",
@@ -941,75 +1093,40 @@ export type Exchange = {
           "isSigner": false
         },
         {
-          "name": "admin",
-          "isMut": false,
-          "isSigner": true
-        }
-      ],
-      "args": [
-        {
-          "name": "amountPerRound",
-          "type": {
-            "defined": "Decimal"
-          }
-        }
-      ]
-    },
-    {
-      "name": "setStakingRoundLength",
-      "accounts": [
-        {
-          "name": "state",
+          "name": "exchangeAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "admin",
+          "name": "signer",
           "isMut": false,
           "isSigner": true
-        }
-      ],
-      "args": [
-        {
-          "name": "roundLength",
-          "type": "u32"
-        }
-      ]
-    },
-    {
-      "name": "setMaxSupply",
-      "accounts": [
+        },
         {
-          "name": "state",
-          "isMut": true,
+          "name": "exchangeAuthority",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "signer",
+          "name": "tokenProgram",
           "isMut": false,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "assetsList",
+          "name": "userTokenAccount",
           "isMut": true,
           "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "assetAddress",
-          "type": "publicKey"
         },
         {
-          "name": "newMaxSupply",
-          "type": {
-            "defined": "Decimal"
-          }
+          "name": "stakingFundAccount",
+          "isMut": true,
+          "isSigner": false
         }
-      ]
+      ],
+      "args": []
     },
     {
-      "name": "setPriceFeed",
+      "name": "compoundRewards",
       "accounts": [
         {
           "name": "state",
@@ -1017,9 +1134,9 @@ export type Exchange = {
           "isSigner": false
         },
         {
-          "name": "signer",
-          "isMut": false,
-          "isSigner": true
+          "name": "exchangeAccount",
+          "isMut": true,
+          "isSigner": false
         },
         {
           "name": "assetsList",
@@ -1027,49 +1144,35 @@ export type Exchange = {
           "isSigner": false
         },
         {
-          "name": "priceFeed",
-          "isMut": false,
+          "name": "reserveAddress",
+          "isMut": true,
           "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "oldFeedAddress",
-          "type": "publicKey"
-        }
-      ]
-    },
-    {
-      "name": "setLiquidationPenalties",
-      "accounts": [
+        },
         {
-          "name": "state",
+          "name": "stakingFundAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "admin",
+          "name": "signer",
           "isMut": false,
           "isSigner": true
-        }
-      ],
-      "args": [
+        },
         {
-          "name": "penaltyToExchange",
-          "type": {
-            "defined": "Decimal"
-          }
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
         },
         {
-          "name": "penaltyToLiquidator",
-          "type": {
-            "defined": "Decimal"
-          }
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
         }
-      ]
+      ],
+      "args": []
     },
     {
-      "name": "addCollateral",
+      "name": "withdrawLiquidationPenalty",
       "accounts": [
         {
           "name": "state",
@@ -1082,46 +1185,34 @@ export type Exchange = {
           "isSigner": true
         },
         {
-          "name": "assetsList",
-          "isMut": true,
+          "name": "exchangeAuthority",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "assetAddress",
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "liquidationFund",
-          "isMut": false,
+          "name": "to",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "reserveAccount",
-          "isMut": false,
+          "name": "liquidationFund",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "feedAddress",
-          "isMut": false,
+          "name": "assetsList",
+          "isMut": true,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "reserveBalance",
-          "type": {
-            "defined": "Decimal"
-          }
-        },
-        {
-          "name": "maxCollateral",
-          "type": {
-            "defined": "Decimal"
-          }
-        },
-        {
-          "name": "collateralRatio",
+          "name": "amount",
           "type": {
             "defined": "Decimal"
           }
@@ -1129,7 +1220,7 @@ export type Exchange = {
       ]
     },
     {
-      "name": "setCollateralRatio",
+      "name": "addNewAsset",
       "accounts": [
         {
           "name": "state",
@@ -1137,7 +1228,7 @@ export type Exchange = {
           "isSigner": false
         },
         {
-          "name": "admin",
+          "name": "signer",
           "isMut": false,
           "isSigner": true
         },
@@ -1145,24 +1236,17 @@ export type Exchange = {
           "name": "assetsList",
           "isMut": true,
           "isSigner": false
-        },
-        {
-          "name": "collateralAddress",
-          "isMut": false,
-          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "collateralRatio",
-          "type": {
-            "defined": "Decimal"
-          }
+          "name": "newAssetFeedAddress",
+          "type": "publicKey"
         }
       ]
     },
     {
-      "name": "setMaxCollateral",
+      "name": "withdrawSwapTax",
       "accounts": [
         {
           "name": "state",
@@ -1174,28 +1258,41 @@ export type Exchange = {
           "isMut": false,
           "isSigner": true
         },
+        {
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "assetsList",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "usdToken",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "collateralAddress",
+          "name": "to",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "maxCollateral",
-          "type": {
-            "defined": "Decimal"
-          }
+          "name": "amount",
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "setAdmin",
+      "name": "withdrawSwapTaxRewards",
       "accounts": [
         {
           "name": "state",
@@ -1203,115 +1300,63 @@ export type Exchange = {
           "isSigner": false
         },
         {
-          "name": "admin",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "newAdmin",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": []
-    },
-    {
-      "name": "setSettlementSlot",
-      "accounts": [
-        {
-          "name": "state",
+          "name": "exchangeAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "admin",
+          "name": "signer",
           "isMut": false,
           "isSigner": true
         },
         {
-          "name": "assetsList",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "syntheticAddress",
+          "name": "exchangeAuthority",
           "isMut": false,
           "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "settlementSlot",
-          "type": "u64"
-        }
-      ]
-    },
-    {
-      "name": "addSynthetic",
-      "accounts": [
-        {
-          "name": "state",
-          "isMut": true,
-          "isSigner": false
         },
         {
-          "name": "admin",
+          "name": "assetsList",
           "isMut": false,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "assetsList",
+          "name": "usdToken",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "assetAddress",
-          "isMut": false,
+          "name": "to",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "feedAddress",
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": [
-        {
-          "name": "maxSupply",
-          "type": "u64"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "settleSynthetic",
+      "name": "withdrawAccumulatedDebtInterest",
       "accounts": [
-        {
-          "name": "settlement",
-          "isMut": true,
-          "isSigner": false
-        },
         {
           "name": "state",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "assetsList",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "payer",
+          "name": "admin",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "tokenToSettle",
+          "name": "exchangeAuthority",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "settlementReserve",
+          "name": "assetsList",
           "isMut": true,
           "isSigner": false
         },
@@ -1321,18 +1366,8 @@ export type Exchange = {
           "isSigner": false
         },
         {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "exchangeAuthority",
-          "isMut": false,
+          "name": "to",
+          "isMut": true,
           "isSigner": false
         },
         {
@@ -1343,209 +1378,136 @@ export type Exchange = {
       ],
       "args": [
         {
-          "name": "bump",
-          "type": "u8"
+          "name": "amount",
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "swapSettledSynthetic",
+      "name": "setSwapTaxRatio",
       "accounts": [
-        {
-          "name": "settlement",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "state",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenToSettle",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "userSettledTokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "userUsdAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "settlementReserve",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "usdToken",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "exchangeAuthority",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "signer",
+          "name": "admin",
           "isMut": false,
           "isSigner": true
         }
       ],
       "args": [
         {
-          "name": "amount",
-          "type": "u64"
+          "name": "swapTaxRatio",
+          "type": {
+            "defined": "Decimal"
+          }
         }
       ]
     },
     {
-      "name": "createSwapline",
+      "name": "setSwapTaxRewardRatio",
       "accounts": [
         {
           "name": "state",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "swapline",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "synthetic",
+          "name": "admin",
           "isMut": false,
-          "isSigner": false
-        },
+          "isSigner": true
+        }
+      ],
+      "args": [
         {
-          "name": "collateral",
-          "isMut": false,
-          "isSigner": false
-        },
+          "name": "swapTaxRewardRatio",
+          "type": {
+            "defined": "Decimal"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setMaxStakingBoost",
+      "accounts": [
         {
-          "name": "assetsList",
+          "name": "state",
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "collateralReserve",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "admin",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         }
       ],
       "args": [
         {
-          "name": "bump",
-          "type": "u8"
-        },
-        {
-          "name": "limit",
-          "type": "u64"
+          "name": "maxStakingBoost",
+          "type": {
+            "defined": "Decimal"
+          }
         }
       ]
     },
     {
-      "name": "withdrawSwaplineFee",
+      "name": "setInsuranceFundRatio",
       "accounts": [
         {
           "name": "state",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "swapline",
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "synthetic",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "collateral",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "admin",
           "isMut": false,
           "isSigner": true
-        },
-        {
-          "name": "exchangeAuthority",
-          "isMut": false,
-          "isSigner": false
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "collateralReserve",
-          "isMut": true,
-          "isSigner": false
-        },
+          "name": "insuranceFundRatio",
+          "type": {
+            "defined": "Decimal"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setKeeperBounty",
+      "accounts": [
         {
-          "name": "to",
+          "name": "state",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "admin",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         }
       ],
       "args": [
         {
-          "name": "amount",
-          "type": "u64"
+          "name": "keeperBounty",
+          "type": {
+            "defined": "Decimal"
+          }
+        },
+        {
+          "name": "keeperBountyCooldown",
+          "type": "u32"
         }
       ]
     },
     {
-      "name": "setHaltedSwapline",
+      "name": "setDebtInterestRate",
       "accounts": [
         {
           "name": "state",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "swapline",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "synthetic",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "collateral",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false
         },
         {
@@ -1556,363 +1518,496 @@ export type Exchange = {
       ],
       "args": [
         {
-          "name": "halted",
-          "type": "bool"
+          "name": "debtInterestRate",
+          "type": {
+            "defined": "Decimal"
+          }
         }
       ]
     },
     {
-      "name": "nativeToSynthetic",
+      "name": "setLiquidationBuffer",
       "accounts": [
         {
           "name": "state",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "swapline",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "synthetic",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "collateral",
+          "name": "admin",
           "isMut": false,
-          "isSigner": false
-        },
+          "isSigner": true
+        }
+      ],
+      "args": [
         {
-          "name": "userCollateralAccount",
-          "isMut": true,
-          "isSigner": false
-        },
+          "name": "liquidationBuffer",
+          "type": "u32"
+        }
+      ]
+    },
+    {
+      "name": "setLiquidationRate",
+      "accounts": [
         {
-          "name": "userSyntheticAccount",
+          "name": "state",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "assetsList",
-          "isMut": true,
-          "isSigner": false
-        },
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
         {
-          "name": "collateralReserve",
+          "name": "liquidationRate",
+          "type": {
+            "defined": "Decimal"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setFullLiquidationHealth",
+      "accounts": [
+        {
+          "name": "state",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "signer",
+          "name": "admin",
           "isMut": false,
           "isSigner": true
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "exchangeAuthority",
-          "isMut": false,
+          "name": "fullLiquidationHealth",
+          "type": {
+            "defined": "Decimal"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setFee",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "admin",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         }
       ],
       "args": [
         {
-          "name": "amount",
-          "type": "u64"
+          "name": "fee",
+          "type": {
+            "defined": "Decimal"
+          }
         }
       ]
     },
     {
-      "name": "syntheticToNative",
+      "name": "setMaxDelay",
       "accounts": [
         {
           "name": "state",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "swapline",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "synthetic",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "collateral",
+          "name": "admin",
           "isMut": false,
-          "isSigner": false
-        },
+          "isSigner": true
+        }
+      ],
+      "args": [
         {
-          "name": "userCollateralAccount",
-          "isMut": true,
-          "isSigner": false
-        },
+          "name": "maxDelay",
+          "type": "u32"
+        }
+      ]
+    },
+    {
+      "name": "setHalted",
+      "accounts": [
         {
-          "name": "userSyntheticAccount",
+          "name": "state",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "assetsList",
-          "isMut": true,
-          "isSigner": false
-        },
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
         {
-          "name": "collateralReserve",
+          "name": "halted",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "setHealthFactor",
+      "accounts": [
+        {
+          "name": "state",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "signer",
+          "name": "admin",
           "isMut": false,
           "isSigner": true
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "exchangeAuthority",
-          "isMut": false,
+          "name": "factor",
+          "type": {
+            "defined": "Decimal"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setStakingAmountPerRound",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "admin",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         }
       ],
       "args": [
         {
-          "name": "amount",
-          "type": "u64"
+          "name": "amountPerRound",
+          "type": {
+            "defined": "Decimal"
+          }
         }
       ]
     },
     {
-      "name": "createVault",
+      "name": "setStakingRoundLength",
       "accounts": [
         {
           "name": "state",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "vault",
           "isMut": true,
           "isSigner": false
         },
         {
           "name": "admin",
-          "isMut": true,
+          "isMut": false,
           "isSigner": true
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "assetsList",
-          "isMut": false,
-          "isSigner": false
-        },
+          "name": "roundLength",
+          "type": "u32"
+        }
+      ]
+    },
+    {
+      "name": "addRewardStream",
+      "accounts": [
         {
-          "name": "collateralReserve",
-          "isMut": false,
+          "name": "state",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "liquidationFund",
+          "name": "admin",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "synthetic",
+          "name": "mint",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "collateral",
+          "name": "fundAccount",
           "isMut": false,
           "isSigner": false
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "collateralPriceFeed",
-          "isMut": false,
-          "isSigner": false
+          "name": "amountPerSlot",
+          "type": "u64"
         },
         {
-          "name": "rent",
-          "isMut": false,
+          "name": "endSlot",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setRewardStream",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "admin",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         }
       ],
       "args": [
         {
-          "name": "bump",
-          "type": "u8"
-        },
-        {
-          "name": "vaultType",
+          "name": "index",
           "type": "u8"
         },
         {
-          "name": "openFee",
+          "name": "amountPerSlot",
           "type": {
             "defined": "Decimal"
           }
         },
         {
-          "name": "debtInterestRate",
-          "type": {
-            "defined": "Decimal"
-          }
-        },
+          "name": "endSlot",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setRewardsVesting",
+      "accounts": [
         {
-          "name": "collateralRatio",
-          "type": {
-            "defined": "Decimal"
-          }
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
         },
         {
-          "name": "maxBorrow",
-          "type": {
-            "defined": "Decimal"
-          }
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "vestingDuration",
+          "type": "u32"
         },
         {
-          "name": "liquidationThreshold",
+          "name": "earlyExitPenalty",
           "type": {
             "defined": "Decimal"
           }
+        }
+      ]
+    },
+    {
+      "name": "setMaxSupply",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
         },
         {
-          "name": "penaltyToLiquidator",
-          "type": {
-            "defined": "Decimal"
-          }
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true
         },
         {
-          "name": "penaltyToExchange",
-          "type": {
-            "defined": "Decimal"
-          }
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "assetAddress",
+          "type": "publicKey"
         },
         {
-          "name": "liquidationRatio",
+          "name": "newMaxSupply",
           "type": {
             "defined": "Decimal"
           }
-        },
-        {
-          "name": "oracleType",
-          "type": "u8"
         }
       ]
     },
     {
-      "name": "createVaultEntry",
+      "name": "setPriceFeed",
       "accounts": [
         {
           "name": "state",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "vaultEntry",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "owner",
-          "isMut": true,
+          "name": "signer",
+          "isMut": false,
           "isSigner": true
         },
         {
-          "name": "vault",
+          "name": "assetsList",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "assetsList",
+          "name": "priceFeed",
           "isMut": false,
           "isSigner": false
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "synthetic",
-          "isMut": false,
+          "name": "oldFeedAddress",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "setLiquidationPenalties",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "collateral",
+          "name": "admin",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "penaltyToExchange",
+          "type": {
+            "defined": "Decimal"
+          }
         },
         {
-          "name": "rent",
-          "isMut": false,
+          "name": "penaltyToLiquidator",
+          "type": {
+            "defined": "Decimal"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setLiquidationAuction",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "admin",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         }
       ],
       "args": [
         {
-          "name": "bump",
-          "type": "u8"
+          "name": "liquidationAuctionDuration",
+          "type": "u32"
+        },
+        {
+          "name": "maxPenaltyToLiquidator",
+          "type": {
+            "defined": "Decimal"
+          }
         }
       ]
     },
     {
-      "name": "depositVault",
+      "name": "addCollateral",
       "accounts": [
         {
           "name": "state",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "vaultEntry",
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "assetsList",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "vault",
-          "isMut": true,
+          "name": "assetAddress",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "synthetic",
+          "name": "liquidationFund",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "collateral",
+          "name": "reserveAccount",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "reserveAddress",
-          "isMut": true,
+          "name": "feedAddress",
+          "isMut": false,
           "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "reserveBalance",
+          "type": {
+            "defined": "Decimal"
+          }
         },
         {
-          "name": "userCollateralAccount",
+          "name": "maxCollateral",
+          "type": {
+            "defined": "Decimal"
+          }
+        },
+        {
+          "name": "collateralRatio",
+          "type": {
+            "defined": "Decimal"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setCollateralRatio",
+      "accounts": [
+        {
+          "name": "state",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "admin",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         },
         {
           "name": "assetsList",
@@ -1920,136 +2015,156 @@ export type Exchange = {
           "isSigner": false
         },
         {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "exchangeAuthority",
+          "name": "collateralAddress",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "amount",
-          "type": "u64"
+          "name": "collateralRatio",
+          "type": {
+            "defined": "Decimal"
+          }
         }
       ]
     },
     {
-      "name": "borrowVault",
+      "name": "setMaxCollateral",
       "accounts": [
         {
           "name": "state",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "vaultEntry",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "vault",
-          "isMut": true,
-          "isSigner": false
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
         },
         {
-          "name": "synthetic",
+          "name": "assetsList",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "collateral",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "collateralPriceFeed",
+          "name": "collateralAddress",
           "isMut": false,
           "isSigner": false
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "assetsList",
-          "isMut": true,
-          "isSigner": false
-        },
+          "name": "maxCollateral",
+          "type": {
+            "defined": "Decimal"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setCollateralLiquidationPenalties",
+      "accounts": [
         {
-          "name": "to",
+          "name": "state",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "admin",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "owner",
+          "name": "assetsList",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "exchangeAuthority",
+          "name": "collateralAddress",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "amount",
-          "type": "u64"
+          "name": "penaltyToExchange",
+          "type": {
+            "defined": "Decimal"
+          }
+        },
+        {
+          "name": "penaltyToLiquidator",
+          "type": {
+            "defined": "Decimal"
+          }
         }
       ]
     },
     {
-      "name": "withdrawVault",
+      "name": "setAdmin",
       "accounts": [
         {
           "name": "state",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "vaultEntry",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "vault",
-          "isMut": true,
-          "isSigner": false
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
         },
         {
-          "name": "synthetic",
+          "name": "newAdmin",
           "isMut": false,
           "isSigner": false
-        },
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setSettlementSlot",
+      "accounts": [
         {
-          "name": "collateral",
-          "isMut": false,
+          "name": "state",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "collateralPriceFeed",
+          "name": "admin",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "reserveAddress",
+          "name": "assetsList",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "userCollateralAccount",
+          "name": "syntheticAddress",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "settlementSlot",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "addSynthetic",
+      "accounts": [
+        {
+          "name": "state",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "admin",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         },
         {
           "name": "assetsList",
@@ -2057,161 +2172,141 @@ export type Exchange = {
           "isSigner": false
         },
         {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": true
+          "name": "assetAddress",
+          "isMut": false,
+          "isSigner": false
         },
         {
-          "name": "exchangeAuthority",
+          "name": "feedAddress",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "amount",
+          "name": "maxSupply",
           "type": "u64"
         }
       ]
     },
     {
-      "name": "repayVault",
+      "name": "settleSynthetic",
       "accounts": [
         {
-          "name": "state",
-          "isMut": false,
+          "name": "settlement",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "vaultEntry",
-          "isMut": true,
+          "name": "state",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "vault",
+          "name": "assetsList",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "synthetic",
-          "isMut": true,
+          "name": "payer",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "collateral",
+          "name": "tokenToSettle",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "assetsList",
+          "name": "settlementReserve",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "userTokenAccountRepay",
+          "name": "usdToken",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "rent",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "owner",
+          "name": "systemProgram",
           "isMut": false,
-          "isSigner": true
+          "isSigner": false
         },
         {
           "name": "exchangeAuthority",
           "isMut": false,
           "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "amount",
-          "type": "u64"
+          "name": "bump",
+          "type": "u8"
         }
       ]
     },
     {
-      "name": "liquidateVault",
+      "name": "swapSettledSynthetic",
       "accounts": [
         {
-          "name": "state",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "vaultEntry",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "vault",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "synthetic",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "collateral",
+          "name": "settlement",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "collateralPriceFeed",
+          "name": "state",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "assetsList",
+          "name": "tokenToSettle",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "collateralReserve",
+          "name": "userSettledTokenAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "liquidatorSyntheticAccount",
+          "name": "userUsdAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "liquidatorCollateralAccount",
+          "name": "settlementReserve",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "liquidationFund",
-          "isMut": true,
+          "name": "usdToken",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "exchangeAuthority",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "owner",
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "liquidator",
+          "name": "signer",
           "isMut": false,
           "isSigner": true
-        },
-        {
-          "name": "exchangeAuthority",
-          "isMut": false,
-          "isSigner": false
         }
       ],
       "args": [
@@ -2222,7 +2317,7 @@ export type Exchange = {
       ]
     },
     {
-      "name": "triggerVaultEntryDebtAdjustment",
+      "name": "createSwapline",
       "accounts": [
         {
           "name": "state",
@@ -2230,17 +2325,7 @@ export type Exchange = {
           "isSigner": false
         },
         {
-          "name": "admin",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "vaultEntry",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "vault",
+          "name": "swapline",
           "isMut": true,
           "isSigner": false
         },
@@ -2260,15 +2345,39 @@ export type Exchange = {
           "isSigner": false
         },
         {
-          "name": "owner",
+          "name": "collateralReserve",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "bump",
+          "type": "u8"
+        },
+        {
+          "name": "limit",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "name": "setVaultHalted",
+      "name": "withdrawSwaplineFee",
       "accounts": [
         {
           "name": "state",
@@ -2276,12 +2385,7 @@ export type Exchange = {
           "isSigner": false
         },
         {
-          "name": "admin",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "vault",
+          "name": "swapline",
           "isMut": true,
           "isSigner": false
         },
@@ -2296,10 +2400,25 @@ export type Exchange = {
           "isSigner": false
         },
         {
-          "name": "assetsList",
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "exchangeAuthority",
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "collateralReserve",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "to",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "tokenProgram",
           "isMut": false,
@@ -2308,13 +2427,13 @@ export type Exchange = {
       ],
       "args": [
         {
-          "name": "halted",
-          "type": "bool"
+          "name": "amount",
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "setVaultCollateralRatio",
+      "name": "setHaltedSwapline",
       "accounts": [
         {
           "name": "state",
@@ -2322,12 +2441,7 @@ export type Exchange = {
           "isSigner": false
         },
         {
-          "name": "admin",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "vault",
+          "name": "swapline",
           "isMut": true,
           "isSigner": false
         },
@@ -2340,19 +2454,22 @@ export type Exchange = {
           "name": "collateral",
           "isMut": false,
           "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
         }
       ],
       "args": [
         {
-          "name": "collateralRatio",
-          "type": {
-            "defined": "Decimal"
-          }
+          "name": "halted",
+          "type": "bool"
         }
       ]
     },
     {
-      "name": "setVaultDebtInterestRate",
+      "name": "nativeToSynthetic",
       "accounts": [
         {
           "name": "state",
@@ -2360,75 +2477,65 @@ export type Exchange = {
           "isSigner": false
         },
         {
-          "name": "admin",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "vault",
+          "name": "swapline",
           "isMut": true,
           "isSigner": false
         },
         {
           "name": "synthetic",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false
         },
         {
           "name": "collateral",
           "isMut": false,
           "isSigner": false
-        }
-      ],
-      "args": [
+        },
         {
-          "name": "debtInterestRate",
-          "type": {
-            "defined": "Decimal"
-          }
-        }
-      ]
-    },
-    {
-      "name": "setVaultLiquidationThreshold",
-      "accounts": [
+          "name": "userCollateralAccount",
+          "isMut": true,
+          "isSigner": false
+        },
         {
-          "name": "state",
-          "isMut": false,
+          "name": "userSyntheticAccount",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "admin",
-          "isMut": false,
-          "isSigner": true
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
         },
         {
-          "name": "vault",
+          "name": "collateralReserve",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "synthetic",
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "exchangeAuthority",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "collateral",
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "liquidationThreshold",
-          "type": {
-            "defined": "Decimal"
-          }
+          "name": "amount",
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "setVaultSetLiquidationRatio",
+      "name": "syntheticToNative",
       "accounts": [
         {
           "name": "state",
@@ -2436,151 +2543,191 @@ export type Exchange = {
           "isSigner": false
         },
         {
-          "name": "admin",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "vault",
+          "name": "swapline",
           "isMut": true,
           "isSigner": false
         },
         {
           "name": "synthetic",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false
         },
         {
           "name": "collateral",
           "isMut": false,
           "isSigner": false
-        }
-      ],
-      "args": [
+        },
         {
-          "name": "liquidationRatio",
-          "type": {
-            "defined": "Decimal"
-          }
-        }
-      ]
-    },
-    {
-      "name": "setVaultLiquidationPenaltyLiquidator",
-      "accounts": [
+          "name": "userCollateralAccount",
+          "isMut": true,
+          "isSigner": false
+        },
         {
-          "name": "state",
-          "isMut": false,
+          "name": "userSyntheticAccount",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "admin",
-          "isMut": false,
-          "isSigner": true
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
         },
         {
-          "name": "vault",
+          "name": "collateralReserve",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "synthetic",
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "exchangeAuthority",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "collateral",
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "liquidationPenaltyLiquidator",
-          "type": {
-            "defined": "Decimal"
-          }
+          "name": "amount",
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "setVaultLiquidationPenaltyExchange",
+      "name": "createVault",
       "accounts": [
         {
           "name": "state",
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "admin",
-          "isMut": false,
+          "isMut": true,
           "isSigner": true
         },
         {
-          "name": "vault",
-          "isMut": true,
+          "name": "assetsList",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "synthetic",
+          "name": "collateralReserve",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "collateral",
+          "name": "liquidationFund",
           "isMut": false,
           "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "liquidationPenaltyExchange",
-          "type": {
-            "defined": "Decimal"
-          }
-        }
-      ]
-    },
-    {
-      "name": "setVaultMaxBorrow",
-      "accounts": [
+        },
         {
-          "name": "state",
+          "name": "synthetic",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "admin",
+          "name": "collateral",
           "isMut": false,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "vault",
-          "isMut": true,
+          "name": "collateralPriceFeed",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "synthetic",
+          "name": "rent",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "collateral",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
+        {
+          "name": "bump",
+          "type": "u8"
+        },
+        {
+          "name": "vaultType",
+          "type": "u8"
+        },
+        {
+          "name": "kind",
+          "type": "u8"
+        },
+        {
+          "name": "openFee",
+          "type": {
+            "defined": "Decimal"
+          }
+        },
+        {
+          "name": "debtInterestRate",
+          "type": {
+            "defined": "Decimal"
+          }
+        },
+        {
+          "name": "collateralRatio",
+          "type": {
+            "defined": "Decimal"
+          }
+        },
         {
           "name": "maxBorrow",
           "type": {
             "defined": "Decimal"
           }
+        },
+        {
+          "name": "liquidationThreshold",
+          "type": {
+            "defined": "Decimal"
+          }
+        },
+        {
+          "name": "penaltyToLiquidator",
+          "type": {
+            "defined": "Decimal"
+          }
+        },
+        {
+          "name": "penaltyToExchange",
+          "type": {
+            "defined": "Decimal"
+          }
+        },
+        {
+          "name": "liquidationRatio",
+          "type": {
+            "defined": "Decimal"
+          }
+        },
+        {
+          "name": "oracleType",
+          "type": "u8"
         }
       ]
     },
     {
-      "name": "withdrawVaultAccumulatedInterest",
+      "name": "createVaultEntry",
       "accounts": [
         {
           "name": "state",
@@ -2588,55 +2735,55 @@ export type Exchange = {
           "isSigner": false
         },
         {
-          "name": "admin",
-          "isMut": false,
-          "isSigner": true
+          "name": "vaultEntry",
+          "isMut": true,
+          "isSigner": false
         },
         {
-          "name": "vault",
+          "name": "owner",
           "isMut": true,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "synthetic",
+          "name": "vault",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "collateral",
+          "name": "assetsList",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "exchangeAuthority",
+          "name": "synthetic",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "assetsList",
+          "name": "collateral",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "to",
-          "isMut": true,
+          "name": "rent",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "amount",
-          "type": "u64"
+          "name": "bump",
+          "type": "u8"
         }
       ]
     },
     {
-      "name": "withdrawVaultLiquidationPenalty",
+      "name": "depositVault",
       "accounts": [
         {
           "name": "state",
@@ -2644,13 +2791,13 @@ export type Exchange = {
           "isSigner": false
         },
         {
-          "name": "admin",
-          "isMut": false,
-          "isSigner": true
+          "name": "vaultEntry",
+          "isMut": true,
+          "isSigner": false
         },
         {
           "name": "vault",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false
         },
         {
@@ -2664,17 +2811,83 @@ export type Exchange = {
           "isSigner": false
         },
         {
-          "name": "exchangeAuthority",
-          "isMut": false,
-          "isSigner": false
+          "name": "reserveAddress",
+          "isMut": true,
+          "isSigner": false
         },
         {
-          "name": "to",
+          "name": "userCollateralAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "liquidationFund",
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "borrowVault",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultEntry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "synthetic",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collateral",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateralPriceFeed",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "to",
           "isMut": true,
           "isSigner": false
         },
@@ -2682,6 +2895,16 @@ export type Exchange = {
           "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
@@ -2690,207 +2913,2071 @@ export type Exchange = {
           "type": "u64"
         }
       ]
-    }
-  ],
-  "accounts": [
+    },
     {
-      "name": "settlement",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "bump",
-            "type": "u8"
-          },
-          {
-            "name": "reserveAddress",
-            "type": "publicKey"
-          },
-          {
-            "name": "tokenInAddress",
-            "type": "publicKey"
-          },
-          {
-            "name": "tokenOutAddress",
-            "type": "publicKey"
-          },
-          {
-            "name": "decimalsIn",
-            "type": "u8"
-          },
-          {
-            "name": "decimalsOut",
-            "type": "u8"
-          },
-          {
-            "name": "ratio",
-            "type": {
-              "defined": "Decimal"
-            }
+      "name": "leverageVault",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultEntry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "swapline",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "synthetic",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateral",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateralPriceFeed",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultCollateralReserve",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "swaplineCollateralReserve",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "targetRatio",
+          "type": {
+            "defined": "Decimal"
           }
-        ]
-      }
+        }
+      ]
     },
     {
-      "name": "state",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "admin",
-            "type": "publicKey"
-          },
-          {
-            "name": "halted",
-            "type": "bool"
-          },
-          {
-            "name": "nonce",
-            "type": "u8"
-          },
-          {
-            "name": "debtShares",
-            "type": "u64"
-          },
-          {
-            "name": "assetsList",
-            "type": "publicKey"
-          },
-          {
-            "name": "healthFactor",
-            "type": {
-              "defined": "Decimal"
-            }
-          },
-          {
-            "name": "maxDelay",
-            "type": "u32"
-          },
-          {
-            "name": "fee",
-            "type": {
-              "defined": "Decimal"
-            }
-          },
-          {
-            "name": "swapTaxRatio",
-            "type": {
-              "defined": "Decimal"
-            }
-          },
-          {
-            "name": "swapTaxReserve",
-            "type": {
-              "defined": "Decimal"
-            }
-          },
-          {
-            "name": "liquidationRate",
-            "type": {
-              "defined": "Decimal"
-            }
-          },
-          {
-            "name": "penaltyToLiquidator",
-            "type": {
-              "defined": "Decimal"
-            }
+      "name": "withdrawVault",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultEntry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "synthetic",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateral",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateralPriceFeed",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "reserveAddress",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userCollateralAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "repayVault",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultEntry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "synthetic",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collateral",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userTokenAccountRepay",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "previewVaultLiquidation",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultEntry",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "synthetic",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateral",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateralPriceFeed",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "liquidateVault",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultEntry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "synthetic",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collateral",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateralPriceFeed",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collateralReserve",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "liquidatorSyntheticAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "liquidatorCollateralAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "liquidationFund",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liquidator",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setVaultEntryDeleverage",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultEntry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "threshold",
+          "type": {
+            "defined": "Decimal"
+          }
+        },
+        {
+          "name": "target",
+          "type": {
+            "defined": "Decimal"
+          }
+        }
+      ]
+    },
+    {
+      "name": "deleverageVault",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultEntry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "swapline",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "synthetic",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateral",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateralPriceFeed",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultCollateralReserve",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "swaplineCollateralReserve",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "keeperCollateralAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "keeper",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "closeVaultEntry",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultEntry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "synthetic",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateral",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "transferVaultEntry",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultEntry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "recipientVaultEntry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "synthetic",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateral",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "recipient",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "bump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "depositVaultCollateral",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultEntry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveAddress",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userCollateralAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "index",
+          "type": "u8"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "withdrawVaultCollateral",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultEntry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "synthetic",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateralPriceFeed",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "reserveAddress",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userCollateralAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "index",
+          "type": "u8"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "liquidateVaultCollateral",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultEntry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "synthetic",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collateralPriceFeed",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collateralReserve",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "liquidatorSyntheticAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "liquidatorCollateralAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "liquidationFund",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liquidator",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "index",
+          "type": "u8"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "triggerVaultEntryDebtAdjustment",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "vaultEntry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "synthetic",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateral",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setVaultHalted",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "synthetic",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateral",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "halted",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "setVaultCollateralRatio",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "synthetic",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateral",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "collateralRatio",
+          "type": {
+            "defined": "Decimal"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setVaultDebtInterestRate",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "synthetic",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateral",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "debtInterestRate",
+          "type": {
+            "defined": "Decimal"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setVaultInterestRateCurve",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "synthetic",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateral",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "enabled",
+          "type": "bool"
+        },
+        {
+          "name": "optimalUtilization",
+          "type": {
+            "defined": "Decimal"
+          }
+        },
+        {
+          "name": "interestRateAtOptimal",
+          "type": {
+            "defined": "Decimal"
+          }
+        },
+        {
+          "name": "maxInterestRate",
+          "type": {
+            "defined": "Decimal"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setVaultDeleverageFee",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "synthetic",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateral",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "deleverageFee",
+          "type": {
+            "defined": "Decimal"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setVaultLiquidationThreshold",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "synthetic",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateral",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "liquidationThreshold",
+          "type": {
+            "defined": "Decimal"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setVaultSetLiquidationRatio",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "synthetic",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateral",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "liquidationRatio",
+          "type": {
+            "defined": "Decimal"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setVaultLiquidationPenaltyLiquidator",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "synthetic",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateral",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "liquidationPenaltyLiquidator",
+          "type": {
+            "defined": "Decimal"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setVaultLiquidationPenaltyExchange",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "synthetic",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateral",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "liquidationPenaltyExchange",
+          "type": {
+            "defined": "Decimal"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setVaultMaxBorrow",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "synthetic",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateral",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "maxBorrow",
+          "type": {
+            "defined": "Decimal"
+          }
+        }
+      ]
+    },
+    {
+      "name": "addVaultCollateral",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collateral",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateralReserve",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liquidationFund",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateralPriceFeed",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "collateralRatio",
+          "type": {
+            "defined": "Decimal"
+          }
+        },
+        {
+          "name": "liquidationThreshold",
+          "type": {
+            "defined": "Decimal"
+          }
+        },
+        {
+          "name": "oracleType",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "withdrawVaultAccumulatedInterest",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "synthetic",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collateral",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "to",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "withdrawVaultLiquidationPenalty",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "vault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "synthetic",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateral",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "to",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "liquidationFund",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    }
+  ],
+  "accounts": [
+    {
+      "name": "settlement",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "reserveAddress",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenInAddress",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenOutAddress",
+            "type": "publicKey"
+          },
+          {
+            "name": "decimalsIn",
+            "type": "u8"
+          },
+          {
+            "name": "decimalsOut",
+            "type": "u8"
+          },
+          {
+            "name": "ratio",
+            "type": {
+              "defined": "Decimal"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "state",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "type": "publicKey"
+          },
+          {
+            "name": "halted",
+            "type": "bool"
+          },
+          {
+            "name": "nonce",
+            "type": "u8"
+          },
+          {
+            "name": "debtShares",
+            "type": "u64"
+          },
+          {
+            "name": "assetsList",
+            "type": "publicKey"
+          },
+          {
+            "name": "healthFactor",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "maxDelay",
+            "type": "u32"
+          },
+          {
+            "name": "fee",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "swapTaxRatio",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "swapTaxReserve",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "liquidationRate",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "penaltyToLiquidator",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "penaltyToExchange",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "liquidationBuffer",
+            "type": "u32"
+          },
+          {
+            "name": "debtInterestRate",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "accumulatedDebtInterest",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "lastDebtAdjustment",
+            "type": "i64"
+          },
+          {
+            "name": "staking",
+            "type": {
+              "defined": "Staking"
+            }
+          },
+          {
+            "name": "exchangeAuthority",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "liquidationAuctionDuration",
+            "type": "u32"
+          },
+          {
+            "name": "maxPenaltyToLiquidator",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "insuranceFund",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "insuranceFundRatio",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "keeperBounty",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "keeperBountyCooldown",
+            "type": "u32"
+          },
+          {
+            "name": "flashLiquidationDebt",
+            "type": "u64"
+          },
+          {
+            "name": "fullLiquidationHealth",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "rewardPerShare",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "lastRewardUpdate",
+            "type": "u64"
+          },
+          {
+            "name": "headRewardStreams",
+            "type": "u8"
+          },
+          {
+            "name": "rewardStreams",
+            "type": {
+              "array": [
+                {
+                  "defined": "RewardStream"
+                },
+                4
+              ]
+            }
+          },
+          {
+            "name": "rewardsVestingDuration",
+            "type": "u32"
+          },
+          {
+            "name": "earlyExitPenalty",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "swapTaxRewardRatio",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "swapTaxRewardPerShare",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "maxStakingBoost",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "boostedDebtShares",
+            "type": "u64"
+          },
+          {
+            "name": "padding",
+            "type": {
+              "array": [
+                "u8",
+                956
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "exchangeAccount",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "owner",
+            "type": "publicKey"
+          },
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "debtShares",
+            "type": "u64"
+          },
+          {
+            "name": "liquidationDeadline",
+            "type": "u64"
+          },
+          {
+            "name": "userStakingData",
+            "type": {
+              "defined": "UserStaking"
+            }
+          },
+          {
+            "name": "head",
+            "type": "u8"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "collaterals",
+            "type": {
+              "array": [
+                {
+                  "defined": "CollateralEntry"
+                },
+                32
+              ]
+            }
+          },
+          {
+            "name": "delegate",
+            "type": "publicKey"
+          },
+          {
+            "name": "delegatePermissions",
+            "type": "u8"
+          },
+          {
+            "name": "subAccountIndex",
+            "type": "u16"
+          },
+          {
+            "name": "lastKeeperBounty",
+            "type": "u64"
+          },
+          {
+            "name": "lastRewardPerShare",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "rewardStreams",
+            "type": {
+              "array": [
+                {
+                  "defined": "UserRewardStream"
+                },
+                4
+              ]
+            }
+          },
+          {
+            "name": "vestingAmount",
+            "type": "u64"
+          },
+          {
+            "name": "vestingStart",
+            "type": "u64"
+          },
+          {
+            "name": "vestingEnd",
+            "type": "u64"
+          },
+          {
+            "name": "swapTaxRewards",
+            "type": {
+              "defined": "UserRewardStream"
+            }
+          },
+          {
+            "name": "stakingBoost",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "boostedDebtShares",
+            "type": "u64"
+          },
+          {
+            "name": "padding",
+            "type": {
+              "array": [
+                "u8",
+                233
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "swapline",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "synthetic",
+            "type": "publicKey"
+          },
+          {
+            "name": "collateral",
+            "type": "publicKey"
+          },
+          {
+            "name": "fee",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "accumulatedFee",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "balance",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "limit",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "collateralReserve",
+            "type": "publicKey"
+          },
+          {
+            "name": "halted",
+            "type": "bool"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "assetsList",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "headAssets",
+            "type": "u8"
+          },
+          {
+            "name": "headCollaterals",
+            "type": "u8"
+          },
+          {
+            "name": "headSynthetics",
+            "type": "u8"
+          },
+          {
+            "name": "assets",
+            "type": {
+              "array": [
+                {
+                  "defined": "Asset"
+                },
+                255
+              ]
+            }
+          },
+          {
+            "name": "collaterals",
+            "type": {
+              "array": [
+                {
+                  "defined": "Collateral"
+                },
+                255
+              ]
+            }
+          },
+          {
+            "name": "synthetics",
+            "type": {
+              "array": [
+                {
+                  "defined": "Synthetic"
+                },
+                255
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "vault",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "halted",
+            "type": "bool"
+          },
+          {
+            "name": "synthetic",
+            "type": "publicKey"
+          },
+          {
+            "name": "collateral",
+            "type": "publicKey"
+          },
+          {
+            "name": "collateralPriceFeed",
+            "type": "publicKey"
           },
           {
-            "name": "penaltyToExchange",
+            "name": "oracleType",
+            "type": "u8"
+          },
+          {
+            "name": "openFee",
             "type": {
               "defined": "Decimal"
             }
           },
           {
-            "name": "liquidationBuffer",
-            "type": "u32"
+            "name": "debtInterestRate",
+            "type": {
+              "defined": "Decimal"
+            }
           },
           {
-            "name": "debtInterestRate",
+            "name": "collateralRatio",
             "type": {
               "defined": "Decimal"
             }
           },
           {
-            "name": "accumulatedDebtInterest",
+            "name": "liquidationThreshold",
             "type": {
               "defined": "Decimal"
             }
           },
           {
-            "name": "lastDebtAdjustment",
-            "type": "i64"
+            "name": "liquidationRatio",
+            "type": {
+              "defined": "Decimal"
+            }
           },
           {
-            "name": "staking",
+            "name": "liquidationPenaltyLiquidator",
             "type": {
-              "defined": "Staking"
+              "defined": "Decimal"
             }
           },
           {
-            "name": "exchangeAuthority",
-            "type": "publicKey"
+            "name": "liquidationPenaltyExchange",
+            "type": {
+              "defined": "Decimal"
+            }
           },
           {
-            "name": "bump",
-            "type": "u8"
+            "name": "accumulatedInterest",
+            "type": {
+              "defined": "Decimal"
+            }
           },
           {
-            "name": "padding",
+            "name": "accumulatedInterestRate",
             "type": {
-              "array": [
-                "u8",
-                1620
-              ]
+              "defined": "Decimal"
             }
-          }
-        ]
-      }
-    },
-    {
-      "name": "exchangeAccount",
-      "type": {
-        "kind": "struct",
-        "fields": [
+          },
           {
-            "name": "owner",
+            "name": "liquidationFund",
             "type": "publicKey"
           },
           {
-            "name": "version",
-            "type": "u8"
+            "name": "collateralReserve",
+            "type": "publicKey"
           },
           {
-            "name": "debtShares",
-            "type": "u64"
+            "name": "mintAmount",
+            "type": {
+              "defined": "Decimal"
+            }
           },
           {
-            "name": "liquidationDeadline",
-            "type": "u64"
+            "name": "collateralAmount",
+            "type": {
+              "defined": "Decimal"
+            }
           },
           {
-            "name": "userStakingData",
+            "name": "maxBorrow",
             "type": {
-              "defined": "UserStaking"
+              "defined": "Decimal"
             }
           },
           {
-            "name": "head",
-            "type": "u8"
+            "name": "lastUpdate",
+            "type": "i64"
           },
           {
             "name": "bump",
             "type": "u8"
           },
+          {
+            "name": "vaultType",
+            "type": "u8"
+          },
+          {
+            "name": "headCollaterals",
+            "type": "u8"
+          },
           {
             "name": "collaterals",
             "type": {
               "array": [
                 {
-                  "defined": "CollateralEntry"
+                  "defined": "VaultCollateral"
                 },
-                32
+                4
+              ]
+            }
+          },
+          {
+            "name": "interestCurveEnabled",
+            "type": "bool"
+          },
+          {
+            "name": "optimalUtilization",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "interestRateAtOptimal",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "maxInterestRate",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "currentInterestRate",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "deleverageFee",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "kind",
+            "type": "u8"
+          },
+          {
+            "name": "padding",
+            "type": {
+              "array": [
+                "u8",
+                352
               ]
             }
           }
@@ -2898,940 +4985,2457 @@ export type Exchange = {
       }
     },
     {
-      "name": "swapline",
+      "name": "vaultEntry",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "synthetic",
+            "name": "owner",
             "type": "publicKey"
           },
           {
-            "name": "collateral",
+            "name": "vault",
             "type": "publicKey"
           },
           {
-            "name": "fee",
+            "name": "lastAccumulatedInterestRate",
             "type": {
               "defined": "Decimal"
             }
           },
           {
-            "name": "accumulatedFee",
+            "name": "syntheticAmount",
             "type": {
               "defined": "Decimal"
             }
           },
           {
-            "name": "balance",
+            "name": "collateralAmount",
             "type": {
               "defined": "Decimal"
             }
           },
           {
-            "name": "limit",
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "collateralAmounts",
+            "type": {
+              "array": [
+                {
+                  "defined": "Decimal"
+                },
+                4
+              ]
+            }
+          },
+          {
+            "name": "deleverageThreshold",
             "type": {
               "defined": "Decimal"
             }
           },
           {
-            "name": "collateralReserve",
-            "type": "publicKey"
+            "name": "deleverageTarget",
+            "type": {
+              "defined": "Decimal"
+            }
           },
           {
-            "name": "halted",
-            "type": "bool"
+            "name": "padding",
+            "type": {
+              "array": [
+                "u8",
+                166
+              ]
+            }
+          }
+        ]
+      }
+    }
+  ],
+  "types": [
+    {
+      "name": "StakingRound",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "start",
+            "type": "u64"
           },
           {
-            "name": "bump",
-            "type": "u8"
+            "name": "amount",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "allPoints",
+            "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "assetsList",
+      "name": "Staking",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "headAssets",
-            "type": "u8"
+            "name": "fundAccount",
+            "type": "publicKey"
           },
           {
-            "name": "headCollaterals",
-            "type": "u8"
+            "name": "roundLength",
+            "type": "u32"
           },
           {
-            "name": "headSynthetics",
-            "type": "u8"
+            "name": "amountPerRound",
+            "type": {
+              "defined": "Decimal"
+            }
           },
           {
-            "name": "assets",
+            "name": "finishedRound",
             "type": {
-              "array": [
-                {
-                  "defined": "Asset"
-                },
-                255
-              ]
+              "defined": "StakingRound"
             }
           },
           {
-            "name": "collaterals",
+            "name": "currentRound",
             "type": {
-              "array": [
-                {
-                  "defined": "Collateral"
-                },
-                255
-              ]
+              "defined": "StakingRound"
+            }
+          },
+          {
+            "name": "nextRound",
+            "type": {
+              "defined": "StakingRound"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "UserStaking",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amountToClaim",
+            "type": {
+              "defined": "Decimal"
             }
           },
           {
-            "name": "synthetics",
-            "type": {
-              "array": [
-                {
-                  "defined": "Synthetic"
-                },
-                255
-              ]
-            }
+            "name": "finishedRoundPoints",
+            "type": "u64"
+          },
+          {
+            "name": "currentRoundPoints",
+            "type": "u64"
+          },
+          {
+            "name": "nextRoundPoints",
+            "type": "u64"
+          },
+          {
+            "name": "lastUpdate",
+            "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "vault",
+      "name": "RewardStream",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "halted",
-            "type": "bool"
-          },
-          {
-            "name": "synthetic",
+            "name": "mint",
             "type": "publicKey"
           },
           {
-            "name": "collateral",
+            "name": "fundAccount",
             "type": "publicKey"
           },
           {
-            "name": "collateralPriceFeed",
-            "type": "publicKey"
+            "name": "amountPerSlot",
+            "type": {
+              "defined": "Decimal"
+            }
           },
           {
-            "name": "oracleType",
-            "type": "u8"
+            "name": "endSlot",
+            "type": "u64"
           },
           {
-            "name": "openFee",
+            "name": "rewardPerShare",
             "type": {
               "defined": "Decimal"
             }
           },
           {
-            "name": "debtInterestRate",
+            "name": "lastUpdate",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "UserRewardStream",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amountToClaim",
             "type": {
               "defined": "Decimal"
             }
           },
           {
-            "name": "collateralRatio",
+            "name": "lastRewardPerShare",
             "type": {
               "defined": "Decimal"
             }
+          }
+        ]
+      }
+    },
+    {
+      "name": "Asset",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "feedAddress",
+            "type": "publicKey"
           },
           {
-            "name": "liquidationThreshold",
+            "name": "price",
             "type": {
               "defined": "Decimal"
             }
           },
           {
-            "name": "liquidationRatio",
-            "type": {
-              "defined": "Decimal"
-            }
+            "name": "lastUpdate",
+            "type": "u64"
           },
           {
-            "name": "liquidationPenaltyLiquidator",
+            "name": "twap",
             "type": {
               "defined": "Decimal"
             }
           },
           {
-            "name": "liquidationPenaltyExchange",
+            "name": "twac",
             "type": {
               "defined": "Decimal"
             }
           },
           {
-            "name": "accumulatedInterest",
-            "type": {
-              "defined": "Decimal"
-            }
+            "name": "status",
+            "type": "u8"
           },
           {
-            "name": "accumulatedInterestRate",
+            "name": "confidence",
             "type": {
               "defined": "Decimal"
             }
+          }
+        ]
+      }
+    },
+    {
+      "name": "Collateral",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "assetIndex",
+            "type": "u8"
           },
           {
-            "name": "liquidationFund",
+            "name": "collateralAddress",
             "type": "publicKey"
           },
           {
-            "name": "collateralReserve",
+            "name": "reserveAddress",
             "type": "publicKey"
           },
           {
-            "name": "mintAmount",
+            "name": "liquidationFund",
+            "type": "publicKey"
+          },
+          {
+            "name": "reserveBalance",
             "type": {
               "defined": "Decimal"
             }
           },
           {
-            "name": "collateralAmount",
+            "name": "collateralRatio",
             "type": {
               "defined": "Decimal"
             }
           },
           {
-            "name": "maxBorrow",
+            "name": "maxCollateral",
             "type": {
               "defined": "Decimal"
             }
           },
           {
-            "name": "lastUpdate",
-            "type": "i64"
-          },
-          {
-            "name": "bump",
-            "type": "u8"
+            "name": "penaltyToLiquidator",
+            "type": {
+              "defined": "Decimal"
+            }
           },
           {
-            "name": "vaultType",
-            "type": "u8"
+            "name": "penaltyToExchange",
+            "type": {
+              "defined": "Decimal"
+            }
           }
         ]
       }
     },
     {
-      "name": "vaultEntry",
+      "name": "Synthetic",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "owner",
-            "type": "publicKey"
+            "name": "assetIndex",
+            "type": "u8"
           },
           {
-            "name": "vault",
+            "name": "assetAddress",
             "type": "publicKey"
           },
           {
-            "name": "lastAccumulatedInterestRate",
+            "name": "supply",
             "type": {
               "defined": "Decimal"
             }
           },
           {
-            "name": "syntheticAmount",
+            "name": "maxSupply",
             "type": {
               "defined": "Decimal"
             }
           },
           {
-            "name": "collateralAmount",
+            "name": "borrowedSupply",
             "type": {
               "defined": "Decimal"
             }
           },
           {
-            "name": "bump",
-            "type": "u8"
+            "name": "swaplineSupply",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "settlementSlot",
+            "type": "u64"
           }
         ]
       }
-    }
-  ],
-  "types": [
+    },
     {
-      "name": "StakingRound",
+      "name": "CollateralEntry",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "start",
+            "name": "amount",
             "type": "u64"
           },
           {
-            "name": "amount",
-            "type": {
-              "defined": "Decimal"
-            }
+            "name": "collateralAddress",
+            "type": "publicKey"
           },
           {
-            "name": "allPoints",
-            "type": "u64"
+            "name": "index",
+            "type": "u8"
           }
         ]
       }
     },
     {
-      "name": "Staking",
+      "name": "Decimal",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "fundAccount",
+            "name": "val",
+            "type": "u128"
+          },
+          {
+            "name": "scale",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "VaultCollateral",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "collateral",
             "type": "publicKey"
           },
           {
-            "name": "roundLength",
-            "type": "u32"
+            "name": "collateralPriceFeed",
+            "type": "publicKey"
           },
           {
-            "name": "amountPerRound",
+            "name": "oracleType",
+            "type": "u8"
+          },
+          {
+            "name": "collateralRatio",
             "type": {
               "defined": "Decimal"
             }
           },
           {
-            "name": "finishedRound",
+            "name": "liquidationThreshold",
             "type": {
-              "defined": "StakingRound"
+              "defined": "Decimal"
             }
           },
           {
-            "name": "currentRound",
-            "type": {
-              "defined": "StakingRound"
-            }
+            "name": "collateralReserve",
+            "type": "publicKey"
           },
           {
-            "name": "nextRound",
+            "name": "liquidationFund",
+            "type": "publicKey"
+          },
+          {
+            "name": "collateralAmount",
             "type": {
-              "defined": "StakingRound"
+              "defined": "Decimal"
             }
           }
         ]
       }
     },
     {
-      "name": "UserStaking",
+      "name": "LiquidationPreview",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "amountToClaim",
-            "type": {
-              "defined": "Decimal"
-            }
+            "name": "liquidatable",
+            "type": "bool"
           },
           {
-            "name": "finishedRoundPoints",
+            "name": "maxRepay",
             "type": "u64"
           },
           {
-            "name": "currentRoundPoints",
+            "name": "repayAmount",
             "type": "u64"
           },
           {
-            "name": "nextRoundPoints",
+            "name": "seizedCollateral",
             "type": "u64"
           },
           {
-            "name": "lastUpdate",
+            "name": "collateralToLiquidator",
             "type": "u64"
-          }
-        ]
-      }
-    },
-    {
-      "name": "Asset",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "feedAddress",
-            "type": "publicKey"
-          },
-          {
-            "name": "price",
-            "type": {
-              "defined": "Decimal"
-            }
           },
           {
-            "name": "lastUpdate",
+            "name": "collateralToExchange",
             "type": "u64"
           },
           {
-            "name": "twap",
-            "type": {
-              "defined": "Decimal"
-            }
-          },
-          {
-            "name": "twac",
-            "type": {
-              "defined": "Decimal"
-            }
-          },
-          {
-            "name": "status",
-            "type": "u8"
+            "name": "debtAfter",
+            "type": "u64"
           },
           {
-            "name": "confidence",
-            "type": {
-              "defined": "Decimal"
-            }
+            "name": "maxDebtAfter",
+            "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "Collateral",
+      "name": "OracleType",
       "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "assetIndex",
-            "type": "u8"
-          },
-          {
-            "name": "collateralAddress",
-            "type": "publicKey"
-          },
+        "kind": "enum",
+        "variants": [
           {
-            "name": "reserveAddress",
-            "type": "publicKey"
+            "name": "Pyth"
           },
           {
-            "name": "liquidationFund",
-            "type": "publicKey"
-          },
+            "name": "Chainlink"
+          }
+        ]
+      }
+    },
+    {
+      "name": "VaultType",
+      "type": {
+        "kind": "enum",
+        "variants": [
           {
-            "name": "reserveBalance",
-            "type": {
-              "defined": "Decimal"
-            }
+            "name": "Standard"
           },
           {
-            "name": "collateralRatio",
-            "type": {
-              "defined": "Decimal"
-            }
+            "name": "Stablecoin"
           },
           {
-            "name": "maxCollateral",
-            "type": {
-              "defined": "Decimal"
-            }
+            "name": "InterestFree"
           }
         ]
       }
+    }
+  ],
+  "events": [
+    {
+      "name": "BadDebtWriteOff",
+      "fields": [
+        {
+          "name": "exchangeAccount",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "owner",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "debt",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "coveredByInsurance",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "socialized",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "debtShares",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    }
+  ],
+  "errors": [
+    {
+      "code": 300,
+      "name": "Unauthorized",
+      "msg": "You are not admin"
+    },
+    {
+      "code": 301,
+      "name": "NotSyntheticUsd",
+      "msg": "Not synthetic USD asset"
+    },
+    {
+      "code": 302,
+      "name": "OutdatedOracle",
+      "msg": "Oracle price is outdated"
+    },
+    {
+      "code": 303,
+      "name": "MintLimit",
+      "msg": "Mint limit"
+    },
+    {
+      "code": 304,
+      "name": "WithdrawLimit",
+      "msg": "Withdraw limit"
+    },
+    {
+      "code": 305,
+      "name": "CollateralAccountError",
+      "msg": "Invalid collateral_account"
+    },
+    {
+      "code": 306,
+      "name": "SyntheticCollateral",
+      "msg": "Synthetic collateral is not supported"
+    },
+    {
+      "code": 307,
+      "name": "InvalidAssetsList",
+      "msg": "Invalid Assets List"
+    },
+    {
+      "code": 308,
+      "name": "InvalidLiquidation",
+      "msg": "Invalid Liquidation"
+    },
+    {
+      "code": 309,
+      "name": "InvalidSigner",
+      "msg": "Invalid signer"
+    },
+    {
+      "code": 310,
+      "name": "WashTrade",
+      "msg": "Wash trade"
+    },
+    {
+      "code": 311,
+      "name": "ExchangeLiquidationAccount",
+      "msg": "Invalid exchange liquidation account"
+    },
+    {
+      "code": 312,
+      "name": "LiquidationDeadline",
+      "msg": "Liquidation deadline not passed"
+    },
+    {
+      "code": 313,
+      "name": "Halted",
+      "msg": "Program is currently Halted"
+    },
+    {
+      "code": 314,
+      "name": "NoRewards",
+      "msg": "No rewards to claim"
+    },
+    {
+      "code": 315,
+      "name": "FundAccountError",
+      "msg": "Invalid fund_account"
+    },
+    {
+      "code": 317,
+      "name": "Initialized",
+      "msg": "Assets list already initialized"
+    },
+    {
+      "code": 316,
+      "name": "SwapUnavailable",
+      "msg": "Swap Unavailable"
+    },
+    {
+      "code": 318,
+      "name": "Uninitialized",
+      "msg": "Assets list is not initialized"
+    },
+    {
+      "code": 319,
+      "name": "NoAssetFound",
+      "msg": "No asset with such address was found"
+    },
+    {
+      "code": 320,
+      "name": "MaxSupply",
+      "msg": "Asset max_supply crossed"
+    },
+    {
+      "code": 321,
+      "name": "NotCollateral",
+      "msg": "Asset is not collateral"
+    },
+    {
+      "code": 322,
+      "name": "AlreadyACollateral",
+      "msg": "Asset is already a collateral"
+    },
+    {
+      "code": 323,
+      "name": "InsufficientValueTrade",
+      "msg": "Insufficient value trade"
+    },
+    {
+      "code": 324,
+      "name": "InsufficientAmountAdminWithdraw",
+      "msg": "Insufficient amount admin withdraw"
+    },
+    {
+      "code": 325,
+      "name": "SettlementNotReached",
+      "msg": "Settlement slot not reached"
+    },
+    {
+      "code": 326,
+      "name": "UsdSettlement",
+      "msg": "Cannot settle xUSD"
+    },
+    {
+      "code": 327,
+      "name": "ParameterOutOfRange",
+      "msg": "Parameter out of range"
+    },
+    {
+      "code": 328,
+      "name": "Overflow",
+      "msg": "Overflow"
+    },
+    {
+      "code": 329,
+      "name": "DifferentScale",
+      "msg": "Scale is different"
+    },
+    {
+      "code": 330,
+      "name": "MismatchedTokens",
+      "msg": "Tokens does not represent same asset"
+    },
+    {
+      "code": 331,
+      "name": "SwaplineLimit",
+      "msg": "Limit crossed"
+    },
+    {
+      "code": 332,
+      "name": "CollateralLimitExceeded",
+      "msg": "Limit of collateral exceeded"
+    },
+    {
+      "code": 333,
+      "name": "UserBorrowLimit",
+      "msg": "User borrow limit"
+    },
+    {
+      "code": 334,
+      "name": "VaultBorrowLimit",
+      "msg": "Vault borrow limit"
+    },
+    {
+      "code": 335,
+      "name": "VaultWithdrawLimit",
+      "msg": "Vault withdraw limit"
+    },
+    {
+      "code": 336,
+      "name": "InvalidAccount",
+      "msg": "Invalid Account"
+    },
+    {
+      "code": 337,
+      "name": "PriceConfidenceOutOfRange",
+      "msg": "Price confidence out of range"
+    },
+    {
+      "code": 338,
+      "name": "InvalidOracleProgram",
+      "msg": "Invalid oracle program"
     },
     {
-      "name": "Synthetic",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "assetIndex",
-            "type": "u8"
-          },
-          {
-            "name": "assetAddress",
-            "type": "publicKey"
-          },
-          {
-            "name": "supply",
-            "type": {
-              "defined": "Decimal"
-            }
-          },
-          {
-            "name": "maxSupply",
-            "type": {
-              "defined": "Decimal"
-            }
-          },
-          {
-            "name": "borrowedSupply",
-            "type": {
-              "defined": "Decimal"
-            }
-          },
-          {
-            "name": "swaplineSupply",
-            "type": {
-              "defined": "Decimal"
-            }
-          },
-          {
-            "name": "settlementSlot",
-            "type": "u64"
-          }
-        ]
-      }
+      "code": 339,
+      "name": "InvalidExchangeAccount",
+      "msg": "Invalid exchange account"
     },
     {
-      "name": "CollateralEntry",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "amount",
-            "type": "u64"
-          },
-          {
-            "name": "collateralAddress",
-            "type": "publicKey"
-          },
-          {
-            "name": "index",
-            "type": "u8"
-          }
-        ]
-      }
+      "code": 340,
+      "name": "InvalidOracleType",
+      "msg": "Invalid oracle type"
     },
     {
-      "name": "Decimal",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "val",
-            "type": "u128"
-          },
-          {
-            "name": "scale",
-            "type": "u8"
-          }
-        ]
-      }
+      "code": 341,
+      "name": "AccountNotMigrated",
+      "msg": "Account is not migrated to current version"
     },
     {
-      "name": "OracleType",
-      "type": {
-        "kind": "enum",
-        "variants": [
-          {
-            "name": "Pyth"
-          },
-          {
-            "name": "Chainlink"
-          }
-        ]
-      }
-    }
-  ],
-  "errors": [
+      "code": 342,
+      "name": "AccountAlreadyMigrated",
+      "msg": "Account is already migrated"
+    },
     {
-      "code": 300,
-      "name": "Unauthorized",
-      "msg": "You are not admin"
+      "code": 343,
+      "name": "DelegatePermission",
+      "msg": "Delegate has no permission"
     },
     {
-      "code": 301,
-      "name": "NotSyntheticUsd",
-      "msg": "Not synthetic USD asset"
+      "code": 344,
+      "name": "AccountSolvent",
+      "msg": "Account still has collateral"
     },
     {
-      "code": 302,
-      "name": "OutdatedOracle",
-      "msg": "Oracle price is outdated"
+      "code": 345,
+      "name": "FlashLiquidationInProgress",
+      "msg": "Flash liquidation already in progress"
     },
     {
-      "code": 303,
-      "name": "MintLimit",
-      "msg": "Mint limit"
+      "code": 346,
+      "name": "FlashLiquidationNotRepaid",
+      "msg": "Flash liquidation is not repaid in transaction"
     },
     {
-      "code": 304,
-      "name": "WithdrawLimit",
-      "msg": "Withdraw limit"
+      "code": 347,
+      "name": "NoFlashLiquidation",
+      "msg": "No flash liquidation to repay"
     },
     {
-      "code": 305,
-      "name": "CollateralAccountError",
-      "msg": "Invalid collateral_account"
+      "code": 348,
+      "name": "InvalidVaultType",
+      "msg": "Invalid vault type"
     },
     {
-      "code": 306,
-      "name": "SyntheticCollateral",
-      "msg": "Synthetic collateral is not supported"
+      "code": 349,
+      "name": "VaultEntryNotEmpty",
+      "msg": "Vault entry still has debt or collateral"
+    },
+    {
+      "code": 350,
+      "name": "DeleverageNotTriggered",
+      "msg": "Deleverage is not triggered"
+    }
+  ]
+};
+
+export const IDL: Exchange = {
+  "version": "0.0.0",
+  "name": "exchange",
+  "instructions": [
+    {
+      "name": "createExchangeAccount",
+      "accounts": [
+        {
+          "name": "exchangeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "bump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "createSubAccount",
+      "accounts": [
+        {
+          "name": "exchangeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "bump",
+          "type": "u8"
+        },
+        {
+          "name": "index",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "migrateExchangeAccount",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "exchangeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setDelegate",
+      "accounts": [
+        {
+          "name": "exchangeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "delegate",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "permissions",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "migrateState",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": []
     },
     {
-      "code": 307,
-      "name": "InvalidAssetsList",
-      "msg": "Invalid Assets List"
+      "name": "migrateAssetsList",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "code": 308,
-      "name": "InvalidLiquidation",
-      "msg": "Invalid Liquidation"
+      "name": "migrateVault",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "code": 309,
-      "name": "InvalidSigner",
-      "msg": "Invalid signer"
+      "name": "migrateVaultEntry",
+      "accounts": [
+        {
+          "name": "vaultEntry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "code": 310,
-      "name": "WashTrade",
-      "msg": "Wash trade"
+      "name": "createList",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "collateralToken",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateralTokenFeed",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "usdToken",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "snyReserve",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "snyLiquidationFund",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "code": 311,
-      "name": "ExchangeLiquidationAccount",
-      "msg": "Invalid exchange liquidation account"
+      "name": "setAssetsList",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": []
     },
     {
-      "code": 312,
-      "name": "LiquidationDeadline",
-      "msg": "Liquidation deadline not passed"
+      "name": "setAssetsPrices",
+      "accounts": [
+        {
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "code": 313,
-      "name": "Halted",
-      "msg": "Program is currently Halted"
+      "name": "init",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "stakingFundAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "bump",
+          "type": "u8"
+        },
+        {
+          "name": "nonce",
+          "type": "u8"
+        },
+        {
+          "name": "stakingRoundLength",
+          "type": "u32"
+        },
+        {
+          "name": "amountPerRound",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "code": 314,
-      "name": "NoRewards",
-      "msg": "No rewards to claim"
+      "name": "deposit",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "exchangeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveAddress",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userCollateralAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "code": 315,
-      "name": "FundAccountError",
-      "msg": "Invalid fund_account"
+      "name": "mint",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "usdToken",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "to",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "exchangeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "code": 317,
-      "name": "Initialized",
-      "msg": "Assets list already initialized"
+      "name": "withdraw",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "reserveAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userCollateralAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "exchangeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "code": 316,
-      "name": "SwapUnavailable",
-      "msg": "Swap Unavailable"
+      "name": "transferCollateral",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "exchangeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "toExchangeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "collateralAddress",
+          "type": "publicKey"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "code": 318,
-      "name": "Uninitialized",
-      "msg": "Assets list is not initialized"
+      "name": "transferPosition",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "exchangeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "toExchangeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "toOwner",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": []
     },
     {
-      "code": 319,
-      "name": "NoAssetFound",
-      "msg": "No asset with such address was found"
+      "name": "swap",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenIn",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenFor",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userTokenAccountIn",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userTokenAccountFor",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "code": 320,
-      "name": "MaxSupply",
-      "msg": "Asset max_supply crossed"
+      "name": "burn",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "usdToken",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userTokenAccountBurn",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "exchangeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "code": 321,
-      "name": "NotCollateral",
-      "msg": "Asset is not collateral"
+      "name": "liquidate",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "usdToken",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "liquidatorUsdAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "liquidatorCollateralAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "exchangeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "liquidationFund",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveAccount",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "code": 322,
-      "name": "AlreadyACollateral",
-      "msg": "Asset is already a collateral"
+      "name": "flashLiquidate",
+      "accounts": [
+        {
+          "name": "liquidate",
+          "accounts": [
+            {
+              "name": "state",
+              "isMut": true,
+              "isSigner": false
+            },
+            {
+              "name": "exchangeAuthority",
+              "isMut": false,
+              "isSigner": false
+            },
+            {
+              "name": "assetsList",
+              "isMut": true,
+              "isSigner": false
+            },
+            {
+              "name": "tokenProgram",
+              "isMut": false,
+              "isSigner": false
+            },
+            {
+              "name": "usdToken",
+              "isMut": true,
+              "isSigner": false
+            },
+            {
+              "name": "liquidatorUsdAccount",
+              "isMut": true,
+              "isSigner": false
+            },
+            {
+              "name": "liquidatorCollateralAccount",
+              "isMut": true,
+              "isSigner": false
+            },
+            {
+              "name": "exchangeAccount",
+              "isMut": true,
+              "isSigner": false
+            },
+            {
+              "name": "signer",
+              "isMut": false,
+              "isSigner": true
+            },
+            {
+              "name": "liquidationFund",
+              "isMut": true,
+              "isSigner": false
+            },
+            {
+              "name": "reserveAccount",
+              "isMut": true,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "code": 323,
-      "name": "InsufficientValueTrade",
-      "msg": "Insufficient value trade"
+      "name": "repayFlashLiquidation",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "usdToken",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "liquidatorUsdAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": []
     },
     {
-      "code": 324,
-      "name": "InsufficientAmountAdminWithdraw",
-      "msg": "Insufficient amount admin withdraw"
+      "name": "liquidateMulti",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "usdToken",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "liquidatorUsdAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "exchangeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "code": 325,
-      "name": "SettlementNotReached",
-      "msg": "Settlement slot not reached"
+      "name": "previewLiquidation",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "exchangeAccount",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "collateralAddress",
+          "type": "publicKey"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "code": 326,
-      "name": "UsdSettlement",
-      "msg": "Cannot settle xUSD"
+      "name": "checkAccountCollateralization",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "exchangeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "code": 327,
-      "name": "ParameterOutOfRange",
-      "msg": "Parameter out of range"
+      "name": "writeOffBadDebt",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "exchangeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "code": 328,
-      "name": "Overflow",
-      "msg": "Overflow"
+      "name": "claimRewards",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "exchangeAccount",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "code": 329,
-      "name": "DifferentScale",
-      "msg": "Scale is different"
+      "name": "withdrawRewards",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "exchangeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "userTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "stakingFundAccount",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "code": 330,
-      "name": "MismatchedTokens",
-      "msg": "Tokens does not represent same asset"
+      "name": "withdrawRewardsEarly",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "exchangeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "userTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "stakingFundAccount",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "code": 331,
-      "name": "SwaplineLimit",
-      "msg": "Limit crossed"
+      "name": "compoundRewards",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "exchangeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveAddress",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "stakingFundAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "code": 332,
-      "name": "CollateralLimitExceeded",
-      "msg": "Limit of collateral exceeded"
+      "name": "withdrawLiquidationPenalty",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "to",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "liquidationFund",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": {
+            "defined": "Decimal"
+          }
+        }
+      ]
     },
     {
-      "code": 333,
-      "name": "UserBorrowLimit",
-      "msg": "User borrow limit"
+      "name": "addNewAsset",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "newAssetFeedAddress",
+          "type": "publicKey"
+        }
+      ]
     },
     {
-      "code": 334,
-      "name": "VaultBorrowLimit",
-      "msg": "Vault borrow limit"
+      "name": "withdrawSwapTax",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "usdToken",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "to",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "code": 335,
-      "name": "VaultWithdrawLimit",
-      "msg": "Vault withdraw limit"
+      "name": "withdrawSwapTaxRewards",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "exchangeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "usdToken",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "to",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "code": 336,
-      "name": "InvalidAccount",
-      "msg": "Invalid Account"
+      "name": "withdrawAccumulatedDebtInterest",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "usdToken",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "to",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "code": 337,
-      "name": "PriceConfidenceOutOfRange",
-      "msg": "Price confidence out of range"
+      "name": "setSwapTaxRatio",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "swapTaxRatio",
+          "type": {
+            "defined": "Decimal"
+          }
+        }
+      ]
     },
     {
-      "code": 338,
-      "name": "InvalidOracleProgram",
-      "msg": "Invalid oracle program"
+      "name": "setSwapTaxRewardRatio",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "swapTaxRewardRatio",
+          "type": {
+            "defined": "Decimal"
+          }
+        }
+      ]
     },
     {
-      "code": 339,
-      "name": "InvalidExchangeAccount",
-      "msg": "Invalid exchange account"
+      "name": "setMaxStakingBoost",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "maxStakingBoost",
+          "type": {
+            "defined": "Decimal"
+          }
+        }
+      ]
     },
     {
-      "code": 340,
-      "name": "InvalidOracleType",
-      "msg": "Invalid oracle type"
-    }
-  ]
-};
-
-export const IDL: Exchange = {
-  "version": "0.0.0",
-  "name": "exchange",
-  "instructions": [
-    {
-      "name": "createExchangeAccount",
+      "name": "setInsuranceFundRatio",
       "accounts": [
         {
-          "name": "exchangeAccount",
+          "name": "state",
           "isMut": true,
           "isSigner": false
         },
         {
           "name": "admin",
           "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "payer",
-          "isMut": true,
           "isSigner": true
-        },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "bump",
-          "type": "u8"
+          "name": "insuranceFundRatio",
+          "type": {
+            "defined": "Decimal"
+          }
         }
       ]
     },
     {
-      "name": "createList",
+      "name": "setKeeperBounty",
       "accounts": [
         {
           "name": "state",
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "assetsList",
-          "isMut": true,
-          "isSigner": false
-        },
         {
           "name": "admin",
           "isMut": false,
           "isSigner": true
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "collateralToken",
-          "isMut": false,
-          "isSigner": false
+          "name": "keeperBounty",
+          "type": {
+            "defined": "Decimal"
+          }
         },
         {
-          "name": "collateralTokenFeed",
-          "isMut": false,
-          "isSigner": false
-        },
+          "name": "keeperBountyCooldown",
+          "type": "u32"
+        }
+      ]
+    },
+    {
+      "name": "setDebtInterestRate",
+      "accounts": [
         {
-          "name": "usdToken",
-          "isMut": false,
+          "name": "state",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "snyReserve",
+          "name": "admin",
           "isMut": false,
-          "isSigner": false
-        },
+          "isSigner": true
+        }
+      ],
+      "args": [
         {
-          "name": "snyLiquidationFund",
-          "isMut": false,
-          "isSigner": false
-        },
+          "name": "debtInterestRate",
+          "type": {
+            "defined": "Decimal"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setLiquidationBuffer",
+      "accounts": [
         {
-          "name": "exchangeAuthority",
-          "isMut": false,
+          "name": "state",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "rent",
+          "name": "admin",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "liquidationBuffer",
+          "type": "u32"
+        }
+      ]
     },
     {
-      "name": "setAssetsList",
+      "name": "setLiquidationRate",
       "accounts": [
         {
           "name": "state",
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "assetsList",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "admin",
           "isMut": false,
           "isSigner": true
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "liquidationRate",
+          "type": {
+            "defined": "Decimal"
+          }
+        }
+      ]
     },
     {
-      "name": "setAssetsPrices",
+      "name": "setFullLiquidationHealth",
       "accounts": [
         {
-          "name": "assetsList",
+          "name": "state",
           "isMut": true,
           "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "fullLiquidationHealth",
+          "type": {
+            "defined": "Decimal"
+          }
+        }
+      ]
     },
     {
-      "name": "init",
+      "name": "setFee",
       "accounts": [
         {
           "name": "state",
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "payer",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "admin",
           "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "exchangeAuthority",
-          "isMut": false,
-          "isSigner": false
-        },
+          "isSigner": true
+        }
+      ],
+      "args": [
         {
-          "name": "stakingFundAccount",
-          "isMut": false,
-          "isSigner": false
-        },
+          "name": "fee",
+          "type": {
+            "defined": "Decimal"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setMaxDelay",
+      "accounts": [
         {
-          "name": "rent",
-          "isMut": false,
+          "name": "state",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "admin",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         }
       ],
       "args": [
         {
-          "name": "bump",
-          "type": "u8"
-        },
-        {
-          "name": "nonce",
-          "type": "u8"
-        },
-        {
-          "name": "stakingRoundLength",
+          "name": "maxDelay",
           "type": "u32"
-        },
-        {
-          "name": "amountPerRound",
-          "type": "u64"
         }
       ]
     },
     {
-      "name": "deposit",
+      "name": "setHalted",
       "accounts": [
         {
           "name": "state",
@@ -3839,50 +7443,20 @@ export const IDL: Exchange = {
           "isSigner": false
         },
         {
-          "name": "exchangeAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "reserveAddress",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "userCollateralAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "assetsList",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "owner",
+          "name": "admin",
           "isMut": false,
           "isSigner": true
-        },
-        {
-          "name": "exchangeAuthority",
-          "isMut": false,
-          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "amount",
-          "type": "u64"
+          "name": "halted",
+          "type": "bool"
         }
       ]
     },
     {
-      "name": "mint",
+      "name": "setHealthFactor",
       "accounts": [
         {
           "name": "state",
@@ -3890,50 +7464,66 @@ export const IDL: Exchange = {
           "isSigner": false
         },
         {
-          "name": "assetsList",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "exchangeAuthority",
+          "name": "admin",
           "isMut": false,
-          "isSigner": false
-        },
+          "isSigner": true
+        }
+      ],
+      "args": [
         {
-          "name": "usdToken",
-          "isMut": true,
-          "isSigner": false
-        },
+          "name": "factor",
+          "type": {
+            "defined": "Decimal"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setStakingAmountPerRound",
+      "accounts": [
         {
-          "name": "to",
+          "name": "state",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "admin",
           "isMut": false,
-          "isSigner": false
-        },
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "amountPerRound",
+          "type": {
+            "defined": "Decimal"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setStakingRoundLength",
+      "accounts": [
         {
-          "name": "exchangeAccount",
+          "name": "state",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "owner",
+          "name": "admin",
           "isMut": false,
           "isSigner": true
         }
       ],
       "args": [
         {
-          "name": "amount",
-          "type": "u64"
+          "name": "roundLength",
+          "type": "u32"
         }
       ]
     },
     {
-      "name": "withdraw",
+      "name": "addRewardStream",
       "accounts": [
         {
           "name": "state",
@@ -3941,50 +7531,65 @@ export const IDL: Exchange = {
           "isSigner": false
         },
         {
-          "name": "assetsList",
-          "isMut": true,
-          "isSigner": false
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
         },
         {
-          "name": "exchangeAuthority",
+          "name": "mint",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "reserveAccount",
-          "isMut": true,
+          "name": "fundAccount",
+          "isMut": false,
           "isSigner": false
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "userCollateralAccount",
-          "isMut": true,
-          "isSigner": false
+          "name": "amountPerSlot",
+          "type": "u64"
         },
         {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
+          "name": "endSlot",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setRewardStream",
+      "accounts": [
         {
-          "name": "exchangeAccount",
+          "name": "state",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "owner",
+          "name": "admin",
           "isMut": false,
           "isSigner": true
         }
       ],
       "args": [
         {
-          "name": "amount",
+          "name": "index",
+          "type": "u8"
+        },
+        {
+          "name": "amountPerSlot",
+          "type": {
+            "defined": "Decimal"
+          }
+        },
+        {
+          "name": "endSlot",
           "type": "u64"
         }
       ]
     },
     {
-      "name": "swap",
+      "name": "setRewardsVesting",
       "accounts": [
         {
           "name": "state",
@@ -3992,55 +7597,58 @@ export const IDL: Exchange = {
           "isSigner": false
         },
         {
-          "name": "exchangeAuthority",
+          "name": "admin",
           "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "assetsList",
-          "isMut": true,
-          "isSigner": false
-        },
+          "isSigner": true
+        }
+      ],
+      "args": [
         {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
+          "name": "vestingDuration",
+          "type": "u32"
         },
         {
-          "name": "tokenIn",
-          "isMut": true,
-          "isSigner": false
-        },
+          "name": "earlyExitPenalty",
+          "type": {
+            "defined": "Decimal"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setMaxSupply",
+      "accounts": [
         {
-          "name": "tokenFor",
+          "name": "state",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "userTokenAccountIn",
-          "isMut": true,
-          "isSigner": false
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true
         },
         {
-          "name": "userTokenAccountFor",
+          "name": "assetsList",
           "isMut": true,
           "isSigner": false
-        },
-        {
-          "name": "owner",
-          "isMut": false,
-          "isSigner": true
         }
       ],
       "args": [
         {
-          "name": "amount",
-          "type": "u64"
+          "name": "assetAddress",
+          "type": "publicKey"
+        },
+        {
+          "name": "newMaxSupply",
+          "type": {
+            "defined": "Decimal"
+          }
         }
       ]
     },
     {
-      "name": "burn",
+      "name": "setPriceFeed",
       "accounts": [
         {
           "name": "state",
@@ -4048,9 +7656,9 @@ export const IDL: Exchange = {
           "isSigner": false
         },
         {
-          "name": "exchangeAuthority",
+          "name": "signer",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         },
         {
           "name": "assetsList",
@@ -4058,40 +7666,76 @@ export const IDL: Exchange = {
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "priceFeed",
           "isMut": false,
           "isSigner": false
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "usdToken",
+          "name": "oldFeedAddress",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "setLiquidationPenalties",
+      "accounts": [
+        {
+          "name": "state",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "userTokenAccountBurn",
-          "isMut": true,
-          "isSigner": false
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "penaltyToExchange",
+          "type": {
+            "defined": "Decimal"
+          }
         },
         {
-          "name": "exchangeAccount",
+          "name": "penaltyToLiquidator",
+          "type": {
+            "defined": "Decimal"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setLiquidationAuction",
+      "accounts": [
+        {
+          "name": "state",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "owner",
+          "name": "admin",
           "isMut": false,
           "isSigner": true
         }
       ],
       "args": [
         {
-          "name": "amount",
-          "type": "u64"
+          "name": "liquidationAuctionDuration",
+          "type": "u32"
+        },
+        {
+          "name": "maxPenaltyToLiquidator",
+          "type": {
+            "defined": "Decimal"
+          }
         }
       ]
     },
     {
-      "name": "liquidate",
+      "name": "addCollateral",
       "accounts": [
         {
           "name": "state",
@@ -4099,9 +7743,9 @@ export const IDL: Exchange = {
           "isSigner": false
         },
         {
-          "name": "exchangeAuthority",
+          "name": "admin",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         },
         {
           "name": "assetsList",
@@ -4109,55 +7753,82 @@ export const IDL: Exchange = {
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "assetAddress",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "usdToken",
-          "isMut": true,
+          "name": "liquidationFund",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "liquidatorUsdAccount",
-          "isMut": true,
+          "name": "reserveAccount",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "liquidatorCollateralAccount",
-          "isMut": true,
+          "name": "feedAddress",
+          "isMut": false,
           "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "reserveBalance",
+          "type": {
+            "defined": "Decimal"
+          }
         },
         {
-          "name": "exchangeAccount",
+          "name": "maxCollateral",
+          "type": {
+            "defined": "Decimal"
+          }
+        },
+        {
+          "name": "collateralRatio",
+          "type": {
+            "defined": "Decimal"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setCollateralRatio",
+      "accounts": [
+        {
+          "name": "state",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "signer",
+          "name": "admin",
           "isMut": false,
           "isSigner": true
         },
         {
-          "name": "liquidationFund",
+          "name": "assetsList",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "reserveAccount",
-          "isMut": true,
+          "name": "collateralAddress",
+          "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "amount",
-          "type": "u64"
+          "name": "collateralRatio",
+          "type": {
+            "defined": "Decimal"
+          }
         }
       ]
     },
     {
-      "name": "checkAccountCollateralization",
+      "name": "setMaxCollateral",
       "accounts": [
         {
           "name": "state",
@@ -4165,36 +7836,32 @@ export const IDL: Exchange = {
           "isSigner": false
         },
         {
-          "name": "exchangeAccount",
-          "isMut": true,
-          "isSigner": false
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
         },
         {
           "name": "assetsList",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": []
-    },
-    {
-      "name": "claimRewards",
-      "accounts": [
-        {
-          "name": "state",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "exchangeAccount",
-          "isMut": true,
+          "name": "collateralAddress",
+          "isMut": false,
           "isSigner": false
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "maxCollateral",
+          "type": {
+            "defined": "Decimal"
+          }
+        }
+      ]
     },
     {
-      "name": "withdrawRewards",
+      "name": "setCollateralLiquidationPenalties",
       "accounts": [
         {
           "name": "state",
@@ -4202,40 +7869,38 @@ export const IDL: Exchange = {
           "isSigner": false
         },
         {
-          "name": "exchangeAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "owner",
+          "name": "admin",
           "isMut": false,
           "isSigner": true
         },
         {
-          "name": "exchangeAuthority",
-          "isMut": false,
+          "name": "assetsList",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "collateralAddress",
           "isMut": false,
           "isSigner": false
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "userTokenAccount",
-          "isMut": true,
-          "isSigner": false
+          "name": "penaltyToExchange",
+          "type": {
+            "defined": "Decimal"
+          }
         },
         {
-          "name": "stakingFundAccount",
-          "isMut": true,
-          "isSigner": false
+          "name": "penaltyToLiquidator",
+          "type": {
+            "defined": "Decimal"
+          }
         }
-      ],
-      "args": []
+      ]
     },
     {
-      "name": "withdrawLiquidationPenalty",
+      "name": "setAdmin",
       "accounts": [
         {
           "name": "state",
@@ -4248,42 +7913,46 @@ export const IDL: Exchange = {
           "isSigner": true
         },
         {
-          "name": "exchangeAuthority",
+          "name": "newAdmin",
           "isMut": false,
           "isSigner": false
-        },
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setSettlementSlot",
+      "accounts": [
         {
-          "name": "tokenProgram",
-          "isMut": false,
+          "name": "state",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "to",
-          "isMut": true,
-          "isSigner": false
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
         },
         {
-          "name": "liquidationFund",
+          "name": "assetsList",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "assetsList",
-          "isMut": true,
+          "name": "syntheticAddress",
+          "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "amount",
-          "type": {
-            "defined": "Decimal"
-          }
+          "name": "settlementSlot",
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "addNewAsset",
+      "name": "addSynthetic",
       "accounts": [
         {
           "name": "state",
@@ -4291,7 +7960,7 @@ export const IDL: Exchange = {
           "isSigner": false
         },
         {
-          "name": "signer",
+          "name": "admin",
           "isMut": false,
           "isSigner": true
         },
@@ -4299,48 +7968,78 @@ export const IDL: Exchange = {
           "name": "assetsList",
           "isMut": true,
           "isSigner": false
+        },
+        {
+          "name": "assetAddress",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "feedAddress",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "newAssetFeedAddress",
-          "type": "publicKey"
+          "name": "maxSupply",
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "withdrawSwapTax",
+      "name": "settleSynthetic",
       "accounts": [
         {
-          "name": "state",
+          "name": "settlement",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "admin",
+          "name": "state",
           "isMut": false,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "exchangeAuthority",
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "assetsList",
+          "name": "tokenToSettle",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "usdToken",
+          "name": "settlementReserve",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "to",
+          "name": "usdToken",
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "tokenProgram",
           "isMut": false,
@@ -4349,199 +8048,211 @@ export const IDL: Exchange = {
       ],
       "args": [
         {
-          "name": "amount",
-          "type": "u64"
+          "name": "bump",
+          "type": "u8"
         }
       ]
     },
     {
-      "name": "withdrawAccumulatedDebtInterest",
+      "name": "swapSettledSynthetic",
       "accounts": [
         {
-          "name": "state",
-          "isMut": true,
+          "name": "settlement",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "admin",
+          "name": "state",
           "isMut": false,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "exchangeAuthority",
-          "isMut": false,
+          "name": "tokenToSettle",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "assetsList",
+          "name": "userSettledTokenAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "usdToken",
+          "name": "userUsdAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "to",
+          "name": "settlementReserve",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "usdToken",
           "isMut": false,
           "isSigner": false
-        }
-      ],
-      "args": [
+        },
         {
-          "name": "amount",
-          "type": "u64"
-        }
-      ]
-    },
-    {
-      "name": "setSwapTaxRatio",
-      "accounts": [
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
         {
-          "name": "state",
-          "isMut": true,
+          "name": "tokenProgram",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "admin",
+          "name": "signer",
           "isMut": false,
           "isSigner": true
         }
       ],
       "args": [
         {
-          "name": "swapTaxRatio",
-          "type": {
-            "defined": "Decimal"
-          }
+          "name": "amount",
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "setDebtInterestRate",
+      "name": "createSwapline",
       "accounts": [
         {
           "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "swapline",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "admin",
+          "name": "synthetic",
           "isMut": false,
-          "isSigner": true
-        }
-      ],
-      "args": [
+          "isSigner": false
+        },
         {
-          "name": "debtInterestRate",
-          "type": {
-            "defined": "Decimal"
-          }
-        }
-      ]
-    },
-    {
-      "name": "setLiquidationBuffer",
-      "accounts": [
+          "name": "collateral",
+          "isMut": false,
+          "isSigner": false
+        },
         {
-          "name": "state",
+          "name": "assetsList",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "admin",
+          "name": "collateralReserve",
           "isMut": false,
-          "isSigner": true
-        }
-      ],
-      "args": [
-        {
-          "name": "liquidationBuffer",
-          "type": "u32"
-        }
-      ]
-    },
-    {
-      "name": "setLiquidationRate",
-      "accounts": [
+          "isSigner": false
+        },
         {
-          "name": "state",
+          "name": "admin",
           "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "admin",
+          "name": "systemProgram",
           "isMut": false,
-          "isSigner": true
+          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "liquidationRate",
-          "type": {
-            "defined": "Decimal"
-          }
+          "name": "bump",
+          "type": "u8"
+        },
+        {
+          "name": "limit",
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "setFee",
+      "name": "withdrawSwaplineFee",
       "accounts": [
         {
           "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "swapline",
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "synthetic",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateral",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "admin",
           "isMut": false,
           "isSigner": true
-        }
-      ],
-      "args": [
+        },
         {
-          "name": "fee",
-          "type": {
-            "defined": "Decimal"
-          }
-        }
-      ]
-    },
-    {
-      "name": "setMaxDelay",
-      "accounts": [
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
         {
-          "name": "state",
+          "name": "collateralReserve",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "admin",
+          "name": "to",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
           "isMut": false,
-          "isSigner": true
+          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "maxDelay",
-          "type": "u32"
+          "name": "amount",
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "setHalted",
+      "name": "setHaltedSwapline",
       "accounts": [
         {
           "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "swapline",
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "synthetic",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateral",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "admin",
           "isMut": false,
@@ -4556,109 +8267,111 @@ export const IDL: Exchange = {
       ]
     },
     {
-      "name": "setHealthFactor",
+      "name": "nativeToSynthetic",
       "accounts": [
         {
           "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "swapline",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "admin",
-          "isMut": false,
-          "isSigner": true
-        }
-      ],
-      "args": [
+          "name": "synthetic",
+          "isMut": true,
+          "isSigner": false
+        },
         {
-          "name": "factor",
-          "type": {
-            "defined": "Decimal"
-          }
-        }
-      ]
-    },
-    {
-      "name": "setStakingAmountPerRound",
-      "accounts": [
+          "name": "collateral",
+          "isMut": false,
+          "isSigner": false
+        },
         {
-          "name": "state",
+          "name": "userCollateralAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "admin",
-          "isMut": false,
-          "isSigner": true
-        }
-      ],
-      "args": [
+          "name": "userSyntheticAccount",
+          "isMut": true,
+          "isSigner": false
+        },
         {
-          "name": "amountPerRound",
-          "type": {
-            "defined": "Decimal"
-          }
-        }
-      ]
-    },
-    {
-      "name": "setStakingRoundLength",
-      "accounts": [
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        },
         {
-          "name": "state",
+          "name": "collateralReserve",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "admin",
+          "name": "signer",
           "isMut": false,
           "isSigner": true
+        },
+        {
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "roundLength",
-          "type": "u32"
+          "name": "amount",
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "setMaxSupply",
+      "name": "syntheticToNative",
       "accounts": [
         {
           "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "swapline",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "signer",
+          "name": "synthetic",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collateral",
           "isMut": false,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "assetsList",
+          "name": "userCollateralAccount",
           "isMut": true,
           "isSigner": false
-        }
-      ],
-      "args": [
+        },
         {
-          "name": "assetAddress",
-          "type": "publicKey"
+          "name": "userSyntheticAccount",
+          "isMut": true,
+          "isSigner": false
         },
         {
-          "name": "newMaxSupply",
-          "type": {
-            "defined": "Decimal"
-          }
-        }
-      ]
-    },
-    {
-      "name": "setPriceFeed",
-      "accounts": [
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        },
         {
-          "name": "state",
+          "name": "collateralReserve",
           "isMut": true,
           "isSigner": false
         },
@@ -4668,100 +8381,103 @@ export const IDL: Exchange = {
           "isSigner": true
         },
         {
-          "name": "assetsList",
-          "isMut": true,
+          "name": "exchangeAuthority",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "priceFeed",
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "oldFeedAddress",
-          "type": "publicKey"
+          "name": "amount",
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "setLiquidationPenalties",
+      "name": "createVault",
       "accounts": [
         {
           "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
           "isMut": true,
           "isSigner": false
         },
         {
           "name": "admin",
-          "isMut": false,
+          "isMut": true,
           "isSigner": true
-        }
-      ],
-      "args": [
+        },
         {
-          "name": "penaltyToExchange",
-          "type": {
-            "defined": "Decimal"
-          }
+          "name": "assetsList",
+          "isMut": false,
+          "isSigner": false
         },
         {
-          "name": "penaltyToLiquidator",
-          "type": {
-            "defined": "Decimal"
-          }
-        }
-      ]
-    },
-    {
-      "name": "addCollateral",
-      "accounts": [
+          "name": "collateralReserve",
+          "isMut": false,
+          "isSigner": false
+        },
         {
-          "name": "state",
-          "isMut": true,
+          "name": "liquidationFund",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "admin",
+          "name": "synthetic",
           "isMut": false,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "assetsList",
-          "isMut": true,
+          "name": "collateral",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "assetAddress",
+          "name": "collateralPriceFeed",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "liquidationFund",
+          "name": "rent",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "reserveAccount",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "bump",
+          "type": "u8"
+        },
+        {
+          "name": "vaultType",
+          "type": "u8"
         },
         {
-          "name": "feedAddress",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
+          "name": "kind",
+          "type": "u8"
+        },
         {
-          "name": "reserveBalance",
+          "name": "openFee",
           "type": {
             "defined": "Decimal"
           }
         },
         {
-          "name": "maxCollateral",
+          "name": "debtInterestRate",
           "type": {
             "defined": "Decimal"
           }
@@ -4771,270 +8487,206 @@ export const IDL: Exchange = {
           "type": {
             "defined": "Decimal"
           }
-        }
-      ]
-    },
-    {
-      "name": "setCollateralRatio",
-      "accounts": [
-        {
-          "name": "state",
-          "isMut": true,
-          "isSigner": false
         },
         {
-          "name": "admin",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "assetsList",
-          "isMut": true,
-          "isSigner": false
+          "name": "maxBorrow",
+          "type": {
+            "defined": "Decimal"
+          }
         },
         {
-          "name": "collateralAddress",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "collateralRatio",
+          "name": "liquidationThreshold",
           "type": {
             "defined": "Decimal"
           }
-        }
-      ]
-    },
-    {
-      "name": "setMaxCollateral",
-      "accounts": [
-        {
-          "name": "state",
-          "isMut": true,
-          "isSigner": false
         },
         {
-          "name": "admin",
-          "isMut": false,
-          "isSigner": true
+          "name": "penaltyToLiquidator",
+          "type": {
+            "defined": "Decimal"
+          }
         },
         {
-          "name": "assetsList",
-          "isMut": true,
-          "isSigner": false
+          "name": "penaltyToExchange",
+          "type": {
+            "defined": "Decimal"
+          }
         },
         {
-          "name": "collateralAddress",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "maxCollateral",
+          "name": "liquidationRatio",
           "type": {
             "defined": "Decimal"
           }
+        },
+        {
+          "name": "oracleType",
+          "type": "u8"
         }
       ]
     },
     {
-      "name": "setAdmin",
+      "name": "createVaultEntry",
       "accounts": [
         {
           "name": "state",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "admin",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "newAdmin",
           "isMut": false,
           "isSigner": false
-        }
-      ],
-      "args": []
-    },
-    {
-      "name": "setSettlementSlot",
-      "accounts": [
+        },
         {
-          "name": "state",
+          "name": "vaultEntry",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "admin",
-          "isMut": false,
+          "name": "owner",
+          "isMut": true,
           "isSigner": true
         },
         {
-          "name": "assetsList",
+          "name": "vault",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "syntheticAddress",
+          "name": "assetsList",
           "isMut": false,
           "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "settlementSlot",
-          "type": "u64"
-        }
-      ]
-    },
-    {
-      "name": "addSynthetic",
-      "accounts": [
-        {
-          "name": "state",
-          "isMut": true,
-          "isSigner": false
         },
         {
-          "name": "admin",
+          "name": "synthetic",
           "isMut": false,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "assetsList",
-          "isMut": true,
+          "name": "collateral",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "assetAddress",
+          "name": "rent",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "feedAddress",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "maxSupply",
-          "type": "u64"
+          "name": "bump",
+          "type": "u8"
         }
       ]
     },
     {
-      "name": "settleSynthetic",
+      "name": "depositVault",
       "accounts": [
         {
-          "name": "settlement",
-          "isMut": true,
+          "name": "state",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "state",
-          "isMut": false,
+          "name": "vaultEntry",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "assetsList",
+          "name": "vault",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "payer",
+          "name": "synthetic",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenToSettle",
+          "name": "collateral",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "settlementReserve",
+          "name": "reserveAddress",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "usdToken",
+          "name": "userCollateralAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "rent",
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
-          "isMut": false,
+          "name": "assetsList",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "exchangeAuthority",
-          "isMut": false,
-          "isSigner": false
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
         },
         {
-          "name": "tokenProgram",
+          "name": "exchangeAuthority",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "bump",
-          "type": "u8"
+          "name": "amount",
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "swapSettledSynthetic",
+      "name": "borrowVault",
       "accounts": [
-        {
-          "name": "settlement",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "state",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenToSettle",
+          "name": "vaultEntry",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "userSettledTokenAccount",
+          "name": "vault",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "userUsdAccount",
+          "name": "synthetic",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "settlementReserve",
-          "isMut": true,
+          "name": "collateral",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "usdToken",
+          "name": "collateralPriceFeed",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "exchangeAuthority",
-          "isMut": false,
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "to",
+          "isMut": true,
           "isSigner": false
         },
         {
@@ -5043,9 +8695,14 @@ export const IDL: Exchange = {
           "isSigner": false
         },
         {
-          "name": "signer",
-          "isMut": false,
+          "name": "owner",
+          "isMut": true,
           "isSigner": true
+        },
+        {
+          "name": "exchangeAuthority",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
@@ -5056,13 +8713,23 @@ export const IDL: Exchange = {
       ]
     },
     {
-      "name": "createSwapline",
+      "name": "leverageVault",
       "accounts": [
         {
           "name": "state",
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "vaultEntry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "swapline",
           "isMut": true,
@@ -5078,45 +8745,53 @@ export const IDL: Exchange = {
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "collateralPriceFeed",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "assetsList",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "collateralReserve",
-          "isMut": false,
+          "name": "vaultCollateralReserve",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "admin",
+          "name": "swaplineCollateralReserve",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "rent",
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "exchangeAuthority",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "bump",
-          "type": "u8"
-        },
-        {
-          "name": "limit",
-          "type": "u64"
+          "name": "targetRatio",
+          "type": {
+            "defined": "Decimal"
+          }
         }
       ]
     },
     {
-      "name": "withdrawSwaplineFee",
+      "name": "withdrawVault",
       "accounts": [
         {
           "name": "state",
@@ -5124,37 +8799,37 @@ export const IDL: Exchange = {
           "isSigner": false
         },
         {
-          "name": "swapline",
+          "name": "vaultEntry",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "synthetic",
-          "isMut": false,
+          "name": "vault",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "collateral",
+          "name": "synthetic",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "admin",
+          "name": "collateral",
           "isMut": false,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "exchangeAuthority",
+          "name": "collateralPriceFeed",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "collateralReserve",
+          "name": "reserveAddress",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "to",
+          "name": "userCollateralAccount",
           "isMut": true,
           "isSigner": false
         },
@@ -5162,53 +8837,32 @@ export const IDL: Exchange = {
           "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "amount",
-          "type": "u64"
-        }
-      ]
-    },
-    {
-      "name": "setHaltedSwapline",
-      "accounts": [
-        {
-          "name": "state",
-          "isMut": false,
-          "isSigner": false
         },
         {
-          "name": "swapline",
+          "name": "assetsList",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "synthetic",
-          "isMut": false,
-          "isSigner": false
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
         },
         {
-          "name": "collateral",
+          "name": "exchangeAuthority",
           "isMut": false,
           "isSigner": false
-        },
-        {
-          "name": "admin",
-          "isMut": false,
-          "isSigner": true
         }
       ],
       "args": [
         {
-          "name": "halted",
-          "type": "bool"
+          "name": "amount",
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "nativeToSynthetic",
+      "name": "repayVault",
       "accounts": [
         {
           "name": "state",
@@ -5216,28 +8870,23 @@ export const IDL: Exchange = {
           "isSigner": false
         },
         {
-          "name": "swapline",
+          "name": "vaultEntry",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "synthetic",
+          "name": "vault",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "collateral",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "userCollateralAccount",
+          "name": "synthetic",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "userSyntheticAccount",
-          "isMut": true,
+          "name": "collateral",
+          "isMut": false,
           "isSigner": false
         },
         {
@@ -5246,22 +8895,22 @@ export const IDL: Exchange = {
           "isSigner": false
         },
         {
-          "name": "collateralReserve",
+          "name": "userTokenAccountRepay",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "signer",
+          "name": "tokenProgram",
           "isMut": false,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "exchangeAuthority",
+          "name": "owner",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "tokenProgram",
+          "name": "exchangeAuthority",
           "isMut": false,
           "isSigner": false
         }
@@ -5274,7 +8923,7 @@ export const IDL: Exchange = {
       ]
     },
     {
-      "name": "syntheticToNative",
+      "name": "previewVaultLiquidation",
       "accounts": [
         {
           "name": "state",
@@ -5282,52 +8931,37 @@ export const IDL: Exchange = {
           "isSigner": false
         },
         {
-          "name": "swapline",
-          "isMut": true,
+          "name": "vaultEntry",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "synthetic",
-          "isMut": true,
+          "name": "vault",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "collateral",
+          "name": "synthetic",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "userCollateralAccount",
-          "isMut": true,
+          "name": "collateral",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "userSyntheticAccount",
-          "isMut": true,
+          "name": "collateralPriceFeed",
+          "isMut": false,
           "isSigner": false
         },
         {
           "name": "assetsList",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "collateralReserve",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "signer",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "exchangeAuthority",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "owner",
           "isMut": false,
           "isSigner": false
         }
@@ -5340,185 +8974,213 @@ export const IDL: Exchange = {
       ]
     },
     {
-      "name": "createVault",
+      "name": "liquidateVault",
       "accounts": [
         {
           "name": "state",
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "vaultEntry",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "vault",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "admin",
+          "name": "synthetic",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "assetsList",
+          "name": "collateral",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "collateralReserve",
+          "name": "collateralPriceFeed",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "liquidationFund",
-          "isMut": false,
+          "name": "assetsList",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "synthetic",
-          "isMut": false,
+          "name": "collateralReserve",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "collateral",
-          "isMut": false,
+          "name": "liquidatorSyntheticAccount",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "collateralPriceFeed",
+          "name": "liquidatorCollateralAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "liquidationFund",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "rent",
+          "name": "owner",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "liquidator",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "exchangeAuthority",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "bump",
-          "type": "u8"
-        },
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setVaultEntryDeleverage",
+      "accounts": [
         {
-          "name": "vaultType",
-          "type": "u8"
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
         },
         {
-          "name": "openFee",
-          "type": {
-            "defined": "Decimal"
-          }
+          "name": "vaultEntry",
+          "isMut": true,
+          "isSigner": false
         },
         {
-          "name": "debtInterestRate",
-          "type": {
-            "defined": "Decimal"
-          }
+          "name": "vault",
+          "isMut": false,
+          "isSigner": false
         },
         {
-          "name": "collateralRatio",
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "threshold",
           "type": {
             "defined": "Decimal"
           }
         },
         {
-          "name": "maxBorrow",
+          "name": "target",
           "type": {
             "defined": "Decimal"
           }
+        }
+      ]
+    },
+    {
+      "name": "deleverageVault",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
         },
         {
-          "name": "liquidationThreshold",
-          "type": {
-            "defined": "Decimal"
-          }
+          "name": "vaultEntry",
+          "isMut": true,
+          "isSigner": false
         },
         {
-          "name": "penaltyToLiquidator",
-          "type": {
-            "defined": "Decimal"
-          }
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
         },
         {
-          "name": "penaltyToExchange",
-          "type": {
-            "defined": "Decimal"
-          }
+          "name": "swapline",
+          "isMut": true,
+          "isSigner": false
         },
         {
-          "name": "liquidationRatio",
-          "type": {
-            "defined": "Decimal"
-          }
+          "name": "synthetic",
+          "isMut": false,
+          "isSigner": false
         },
         {
-          "name": "oracleType",
-          "type": "u8"
-        }
-      ]
-    },
-    {
-      "name": "createVaultEntry",
-      "accounts": [
+          "name": "collateral",
+          "isMut": false,
+          "isSigner": false
+        },
         {
-          "name": "state",
+          "name": "collateralPriceFeed",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "vaultEntry",
+          "name": "assetsList",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "owner",
+          "name": "vaultCollateralReserve",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "vault",
+          "name": "swaplineCollateralReserve",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "assetsList",
-          "isMut": false,
+          "name": "keeperCollateralAccount",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "synthetic",
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "collateral",
+          "name": "owner",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "rent",
+          "name": "keeper",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "systemProgram",
+          "name": "exchangeAuthority",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": [
-        {
-          "name": "bump",
-          "type": "u8"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "depositVault",
+      "name": "closeVaultEntry",
       "accounts": [
         {
           "name": "state",
@@ -5545,21 +9207,6 @@ export const IDL: Exchange = {
           "isMut": false,
           "isSigner": false
         },
-        {
-          "name": "reserveAddress",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "userCollateralAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "assetsList",
           "isMut": true,
@@ -5569,22 +9216,12 @@ export const IDL: Exchange = {
           "name": "owner",
           "isMut": true,
           "isSigner": true
-        },
-        {
-          "name": "exchangeAuthority",
-          "isMut": false,
-          "isSigner": false
         }
       ],
-      "args": [
-        {
-          "name": "amount",
-          "type": "u64"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "borrowVault",
+      "name": "transferVaultEntry",
       "accounts": [
         {
           "name": "state",
@@ -5597,22 +9234,22 @@ export const IDL: Exchange = {
           "isSigner": false
         },
         {
-          "name": "vault",
+          "name": "recipientVaultEntry",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "synthetic",
+          "name": "vault",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "collateral",
+          "name": "synthetic",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "collateralPriceFeed",
+          "name": "collateral",
           "isMut": false,
           "isSigner": false
         },
@@ -5622,35 +9259,35 @@ export const IDL: Exchange = {
           "isSigner": false
         },
         {
-          "name": "to",
+          "name": "owner",
           "isMut": true,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "tokenProgram",
+          "name": "recipient",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": true
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
         },
         {
-          "name": "exchangeAuthority",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "amount",
-          "type": "u64"
+          "name": "bump",
+          "type": "u8"
         }
       ]
     },
     {
-      "name": "withdrawVault",
+      "name": "depositVaultCollateral",
       "accounts": [
         {
           "name": "state",
@@ -5667,21 +9304,6 @@ export const IDL: Exchange = {
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "synthetic",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "collateral",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "collateralPriceFeed",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "reserveAddress",
           "isMut": true,
@@ -5697,11 +9319,6 @@ export const IDL: Exchange = {
           "isMut": false,
           "isSigner": false
         },
-        {
-          "name": "assetsList",
-          "isMut": true,
-          "isSigner": false
-        },
         {
           "name": "owner",
           "isMut": true,
@@ -5714,6 +9331,10 @@ export const IDL: Exchange = {
         }
       ],
       "args": [
+        {
+          "name": "index",
+          "type": "u8"
+        },
         {
           "name": "amount",
           "type": "u64"
@@ -5721,7 +9342,7 @@ export const IDL: Exchange = {
       ]
     },
     {
-      "name": "repayVault",
+      "name": "withdrawVaultCollateral",
       "accounts": [
         {
           "name": "state",
@@ -5740,21 +9361,21 @@ export const IDL: Exchange = {
         },
         {
           "name": "synthetic",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "collateral",
+          "name": "collateralPriceFeed",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "assetsList",
+          "name": "reserveAddress",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "userTokenAccountRepay",
+          "name": "userCollateralAccount",
           "isMut": true,
           "isSigner": false
         },
@@ -5763,9 +9384,14 @@ export const IDL: Exchange = {
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "assetsList",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "owner",
-          "isMut": false,
+          "isMut": true,
           "isSigner": true
         },
         {
@@ -5775,6 +9401,10 @@ export const IDL: Exchange = {
         }
       ],
       "args": [
+        {
+          "name": "index",
+          "type": "u8"
+        },
         {
           "name": "amount",
           "type": "u64"
@@ -5782,7 +9412,7 @@ export const IDL: Exchange = {
       ]
     },
     {
-      "name": "liquidateVault",
+      "name": "liquidateVaultCollateral",
       "accounts": [
         {
           "name": "state",
@@ -5804,11 +9434,6 @@ export const IDL: Exchange = {
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "collateral",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "collateralPriceFeed",
           "isMut": false,
@@ -5861,6 +9486,10 @@ export const IDL: Exchange = {
         }
       ],
       "args": [
+        {
+          "name": "index",
+          "type": "u8"
+        },
         {
           "name": "amount",
           "type": "u64"
@@ -5954,13 +9583,143 @@ export const IDL: Exchange = {
       ],
       "args": [
         {
-          "name": "halted",
-          "type": "bool"
+          "name": "halted",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "setVaultCollateralRatio",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "synthetic",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateral",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "collateralRatio",
+          "type": {
+            "defined": "Decimal"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setVaultDebtInterestRate",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "synthetic",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateral",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "debtInterestRate",
+          "type": {
+            "defined": "Decimal"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setVaultInterestRateCurve",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "synthetic",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateral",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "enabled",
+          "type": "bool"
+        },
+        {
+          "name": "optimalUtilization",
+          "type": {
+            "defined": "Decimal"
+          }
+        },
+        {
+          "name": "interestRateAtOptimal",
+          "type": {
+            "defined": "Decimal"
+          }
+        },
+        {
+          "name": "maxInterestRate",
+          "type": {
+            "defined": "Decimal"
+          }
         }
       ]
     },
     {
-      "name": "setVaultCollateralRatio",
+      "name": "setVaultDeleverageFee",
       "accounts": [
         {
           "name": "state",
@@ -5990,7 +9749,7 @@ export const IDL: Exchange = {
       ],
       "args": [
         {
-          "name": "collateralRatio",
+          "name": "deleverageFee",
           "type": {
             "defined": "Decimal"
           }
@@ -5998,7 +9757,7 @@ export const IDL: Exchange = {
       ]
     },
     {
-      "name": "setVaultDebtInterestRate",
+      "name": "setVaultLiquidationThreshold",
       "accounts": [
         {
           "name": "state",
@@ -6028,7 +9787,7 @@ export const IDL: Exchange = {
       ],
       "args": [
         {
-          "name": "debtInterestRate",
+          "name": "liquidationThreshold",
           "type": {
             "defined": "Decimal"
           }
@@ -6036,7 +9795,7 @@ export const IDL: Exchange = {
       ]
     },
     {
-      "name": "setVaultLiquidationThreshold",
+      "name": "setVaultSetLiquidationRatio",
       "accounts": [
         {
           "name": "state",
@@ -6066,7 +9825,7 @@ export const IDL: Exchange = {
       ],
       "args": [
         {
-          "name": "liquidationThreshold",
+          "name": "liquidationRatio",
           "type": {
             "defined": "Decimal"
           }
@@ -6074,7 +9833,7 @@ export const IDL: Exchange = {
       ]
     },
     {
-      "name": "setVaultSetLiquidationRatio",
+      "name": "setVaultLiquidationPenaltyLiquidator",
       "accounts": [
         {
           "name": "state",
@@ -6104,7 +9863,7 @@ export const IDL: Exchange = {
       ],
       "args": [
         {
-          "name": "liquidationRatio",
+          "name": "liquidationPenaltyLiquidator",
           "type": {
             "defined": "Decimal"
           }
@@ -6112,7 +9871,7 @@ export const IDL: Exchange = {
       ]
     },
     {
-      "name": "setVaultLiquidationPenaltyLiquidator",
+      "name": "setVaultLiquidationPenaltyExchange",
       "accounts": [
         {
           "name": "state",
@@ -6142,7 +9901,7 @@ export const IDL: Exchange = {
       ],
       "args": [
         {
-          "name": "liquidationPenaltyLiquidator",
+          "name": "liquidationPenaltyExchange",
           "type": {
             "defined": "Decimal"
           }
@@ -6150,7 +9909,7 @@ export const IDL: Exchange = {
       ]
     },
     {
-      "name": "setVaultLiquidationPenaltyExchange",
+      "name": "setVaultMaxBorrow",
       "accounts": [
         {
           "name": "state",
@@ -6180,7 +9939,7 @@ export const IDL: Exchange = {
       ],
       "args": [
         {
-          "name": "liquidationPenaltyExchange",
+          "name": "maxBorrow",
           "type": {
             "defined": "Decimal"
           }
@@ -6188,7 +9947,7 @@ export const IDL: Exchange = {
       ]
     },
     {
-      "name": "setVaultMaxBorrow",
+      "name": "addVaultCollateral",
       "accounts": [
         {
           "name": "state",
@@ -6206,22 +9965,42 @@ export const IDL: Exchange = {
           "isSigner": false
         },
         {
-          "name": "synthetic",
+          "name": "collateral",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "collateral",
+          "name": "collateralReserve",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liquidationFund",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "collateralPriceFeed",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "maxBorrow",
+          "name": "collateralRatio",
           "type": {
             "defined": "Decimal"
           }
+        },
+        {
+          "name": "liquidationThreshold",
+          "type": {
+            "defined": "Decimal"
+          }
+        },
+        {
+          "name": "oracleType",
+          "type": "u8"
         }
       ]
     },
@@ -6482,12 +10261,115 @@ export const IDL: Exchange = {
             "name": "bump",
             "type": "u8"
           },
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "liquidationAuctionDuration",
+            "type": "u32"
+          },
+          {
+            "name": "maxPenaltyToLiquidator",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "insuranceFund",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "insuranceFundRatio",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "keeperBounty",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "keeperBountyCooldown",
+            "type": "u32"
+          },
+          {
+            "name": "flashLiquidationDebt",
+            "type": "u64"
+          },
+          {
+            "name": "fullLiquidationHealth",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "rewardPerShare",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "lastRewardUpdate",
+            "type": "u64"
+          },
+          {
+            "name": "headRewardStreams",
+            "type": "u8"
+          },
+          {
+            "name": "rewardStreams",
+            "type": {
+              "array": [
+                {
+                  "defined": "RewardStream"
+                },
+                4
+              ]
+            }
+          },
+          {
+            "name": "rewardsVestingDuration",
+            "type": "u32"
+          },
+          {
+            "name": "earlyExitPenalty",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "swapTaxRewardRatio",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "swapTaxRewardPerShare",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "maxStakingBoost",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "boostedDebtShares",
+            "type": "u64"
+          },
           {
             "name": "padding",
             "type": {
               "array": [
                 "u8",
-                1620
+                956
               ]
             }
           }
@@ -6539,6 +10421,76 @@ export const IDL: Exchange = {
                 32
               ]
             }
+          },
+          {
+            "name": "delegate",
+            "type": "publicKey"
+          },
+          {
+            "name": "delegatePermissions",
+            "type": "u8"
+          },
+          {
+            "name": "subAccountIndex",
+            "type": "u16"
+          },
+          {
+            "name": "lastKeeperBounty",
+            "type": "u64"
+          },
+          {
+            "name": "lastRewardPerShare",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "rewardStreams",
+            "type": {
+              "array": [
+                {
+                  "defined": "UserRewardStream"
+                },
+                4
+              ]
+            }
+          },
+          {
+            "name": "vestingAmount",
+            "type": "u64"
+          },
+          {
+            "name": "vestingStart",
+            "type": "u64"
+          },
+          {
+            "name": "vestingEnd",
+            "type": "u64"
+          },
+          {
+            "name": "swapTaxRewards",
+            "type": {
+              "defined": "UserRewardStream"
+            }
+          },
+          {
+            "name": "stakingBoost",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "boostedDebtShares",
+            "type": "u64"
+          },
+          {
+            "name": "padding",
+            "type": {
+              "array": [
+                "u8",
+                233
+              ]
+            }
           }
         ]
       }
@@ -6704,66 +10656,128 @@ export const IDL: Exchange = {
             }
           },
           {
-            "name": "liquidationPenaltyLiquidator",
-            "type": {
-              "defined": "Decimal"
-            }
+            "name": "liquidationPenaltyLiquidator",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "liquidationPenaltyExchange",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "accumulatedInterest",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "accumulatedInterestRate",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "liquidationFund",
+            "type": "publicKey"
+          },
+          {
+            "name": "collateralReserve",
+            "type": "publicKey"
+          },
+          {
+            "name": "mintAmount",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "collateralAmount",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "maxBorrow",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "lastUpdate",
+            "type": "i64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "vaultType",
+            "type": "u8"
+          },
+          {
+            "name": "headCollaterals",
+            "type": "u8"
           },
           {
-            "name": "liquidationPenaltyExchange",
+            "name": "collaterals",
             "type": {
-              "defined": "Decimal"
+              "array": [
+                {
+                  "defined": "VaultCollateral"
+                },
+                4
+              ]
             }
           },
           {
-            "name": "accumulatedInterest",
+            "name": "interestCurveEnabled",
+            "type": "bool"
+          },
+          {
+            "name": "optimalUtilization",
             "type": {
               "defined": "Decimal"
             }
           },
           {
-            "name": "accumulatedInterestRate",
+            "name": "interestRateAtOptimal",
             "type": {
               "defined": "Decimal"
             }
           },
           {
-            "name": "liquidationFund",
-            "type": "publicKey"
-          },
-          {
-            "name": "collateralReserve",
-            "type": "publicKey"
-          },
-          {
-            "name": "mintAmount",
+            "name": "maxInterestRate",
             "type": {
               "defined": "Decimal"
             }
           },
           {
-            "name": "collateralAmount",
+            "name": "currentInterestRate",
             "type": {
               "defined": "Decimal"
             }
           },
           {
-            "name": "maxBorrow",
+            "name": "deleverageFee",
             "type": {
               "defined": "Decimal"
             }
           },
           {
-            "name": "lastUpdate",
-            "type": "i64"
-          },
-          {
-            "name": "bump",
+            "name": "kind",
             "type": "u8"
           },
           {
-            "name": "vaultType",
-            "type": "u8"
+            "name": "padding",
+            "type": {
+              "array": [
+                "u8",
+                352
+              ]
+            }
           }
         ]
       }
@@ -6802,6 +10816,38 @@ export const IDL: Exchange = {
           {
             "name": "bump",
             "type": "u8"
+          },
+          {
+            "name": "collateralAmounts",
+            "type": {
+              "array": [
+                {
+                  "defined": "Decimal"
+                },
+                4
+              ]
+            }
+          },
+          {
+            "name": "deleverageThreshold",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "deleverageTarget",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "padding",
+            "type": {
+              "array": [
+                "u8",
+                166
+              ]
+            }
           }
         ]
       }
@@ -6900,6 +10946,62 @@ export const IDL: Exchange = {
         ]
       }
     },
+    {
+      "name": "RewardStream",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "fundAccount",
+            "type": "publicKey"
+          },
+          {
+            "name": "amountPerSlot",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "endSlot",
+            "type": "u64"
+          },
+          {
+            "name": "rewardPerShare",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "lastUpdate",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "UserRewardStream",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amountToClaim",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "lastRewardPerShare",
+            "type": {
+              "defined": "Decimal"
+            }
+          }
+        ]
+      }
+    },
     {
       "name": "Asset",
       "type": {
@@ -6982,6 +11084,18 @@ export const IDL: Exchange = {
             "type": {
               "defined": "Decimal"
             }
+          },
+          {
+            "name": "penaltyToLiquidator",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "penaltyToExchange",
+            "type": {
+              "defined": "Decimal"
+            }
           }
         ]
       }
@@ -7066,6 +11180,92 @@ export const IDL: Exchange = {
         ]
       }
     },
+    {
+      "name": "VaultCollateral",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "collateral",
+            "type": "publicKey"
+          },
+          {
+            "name": "collateralPriceFeed",
+            "type": "publicKey"
+          },
+          {
+            "name": "oracleType",
+            "type": "u8"
+          },
+          {
+            "name": "collateralRatio",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "liquidationThreshold",
+            "type": {
+              "defined": "Decimal"
+            }
+          },
+          {
+            "name": "collateralReserve",
+            "type": "publicKey"
+          },
+          {
+            "name": "liquidationFund",
+            "type": "publicKey"
+          },
+          {
+            "name": "collateralAmount",
+            "type": {
+              "defined": "Decimal"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "LiquidationPreview",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "liquidatable",
+            "type": "bool"
+          },
+          {
+            "name": "maxRepay",
+            "type": "u64"
+          },
+          {
+            "name": "repayAmount",
+            "type": "u64"
+          },
+          {
+            "name": "seizedCollateral",
+            "type": "u64"
+          },
+          {
+            "name": "collateralToLiquidator",
+            "type": "u64"
+          },
+          {
+            "name": "collateralToExchange",
+            "type": "u64"
+          },
+          {
+            "name": "debtAfter",
+            "type": "u64"
+          },
+          {
+            "name": "maxDebtAfter",
+            "type": "u64"
+          }
+        ]
+      }
+    },
     {
       "name": "OracleType",
       "type": {
@@ -7079,6 +11279,60 @@ export const IDL: Exchange = {
           }
         ]
       }
+    },
+    {
+      "name": "VaultType",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Standard"
+          },
+          {
+            "name": "Stablecoin"
+          },
+          {
+            "name": "InterestFree"
+          }
+        ]
+      }
+    }
+  ],
+  "events": [
+    {
+      "name": "BadDebtWriteOff",
+      "fields": [
+        {
+          "name": "exchangeAccount",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "owner",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "debt",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "coveredByInsurance",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "socialized",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "debtShares",
+          "type": "u64",
+          "index": false
+        }
+      ]
     }
   ],
   "errors": [
@@ -7286,6 +11540,56 @@ export const IDL: Exchange = {
       "code": 340,
       "name": "InvalidOracleType",
       "msg": "Invalid oracle type"
+    },
+    {
+      "code": 341,
+      "name": "AccountNotMigrated",
+      "msg": "Account is not migrated to current version"
+    },
+    {
+      "code": 342,
+      "name": "AccountAlreadyMigrated",
+      "msg": "Account is already migrated"
+    },
+    {
+      "code": 343,
+      "name": "DelegatePermission",
+      "msg": "Delegate has no permission"
+    },
+    {
+      "code": 344,
+      "name": "AccountSolvent",
+      "msg": "Account still has collateral"
+    },
+    {
+      "code": 345,
+      "name": "FlashLiquidationInProgress",
+      "msg": "Flash liquidation already in progress"
+    },
+    {
+      "code": 346,
+      "name": "FlashLiquidationNotRepaid",
+      "msg": "Flash liquidation is not repaid in transaction"
+    },
+    {
+      "code": 347,
+      "name": "NoFlashLiquidation",
+      "msg": "No flash liquidation to repay"
+    },
+    {
+      "code": 348,
+      "name": "InvalidVaultType",
+      "msg": "Invalid vault type"
+    },
+    {
+      "code": 349,
+      "name": "VaultEntryNotEmpty",
+      "msg": "Vault entry still has debt or collateral"
+    },
+    {
+      "code": 350,
+      "name": "DeleverageNotTriggered",
+      "msg": "Deleverage is not triggered"
     }
   ]
 };
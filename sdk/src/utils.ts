@@ -19,6 +19,14 @@ export const UNIFIED_PERCENT_SCALE = 5
 export const SNY_DECIMALS = 6
 export const XUSD_DECIMALS = 6
 export const INTEREST_RATE_DECIMALS = 18
+// Permissions of ExchangeAccount delegate
+export const DELEGATE_DEPOSIT = 1 << 0
+export const DELEGATE_BURN = 1 << 1
+export const DELEGATE_MINT = 1 << 2
+export const DELEGATE_WITHDRAW = 1 << 3 // withdraw only to accounts of owner
+export const DELEGATE_CLAIM_REWARDS = 1 << 4 // withdraw rewards only to accounts of owner
+export const DELEGATE_ALL =
+  DELEGATE_DEPOSIT | DELEGATE_BURN | DELEGATE_MINT | DELEGATE_WITHDRAW | DELEGATE_CLAIM_REWARDS
 // hex code must be at the end of message
 export enum ERRORS {
   SIGNATURE = 'Error: Signature verification failed',